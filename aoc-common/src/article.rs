@@ -0,0 +1,554 @@
+//! Puzzle descriptions converted from the Advent of Code page to Markdown, for reading offline.
+//!
+//! A day's page holds one `<article class="day-desc">` per unlocked part. Only those are kept,
+//! so the answers and links around them are left out. Example blocks become fenced code, and the
+//! highlighted answers in the text stay highlighted as `**strong**` emphasis.
+
+use nom::branch::alt;
+use nom::bytes::complete::{is_not, tag, take_till1, take_until, take_while1};
+use nom::character::complete::{char, multispace0, multispace1};
+use nom::combinator::{map, opt, recognize, rest, value};
+use nom::multi::many0;
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::IResult;
+
+/// The file in a day's directory the description is saved to.
+pub const PUZZLE_FILE: &str = "PUZZLE.md";
+/// Where relative links on the pages point to.
+pub const ORIGIN: &str = "https://adventofcode.com";
+
+/// Elements that never have children or a closing tag.
+const VOID_ELEMENTS: &[&str] = &["br", "hr", "img", "input", "link", "meta", "wbr"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    Close(String),
+    Text(String),
+    /// Comments, the doctype and script bodies, which say nothing about the puzzle
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Element {
+        name: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+/// The Markdown for the puzzle description on the page at `url`, one section per part, or
+/// `None` if the page has no description.
+pub fn to_markdown(html: &str, url: &str) -> Option<String> {
+    let nodes = tree(&tokens(html));
+    let articles = articles(&nodes);
+    if articles.is_empty() {
+        return None;
+    }
+
+    let sections = articles
+        .iter()
+        .map(|article| blocks(article, url).join("\n\n"))
+        .collect::<Vec<_>>();
+    Some(format!("{}\n", sections.join("\n\n")))
+}
+
+fn tokens(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut input = html;
+    while !input.is_empty() {
+        let (rest, token) = token(input).expect("should accept any input");
+        if let Token::Open { name, .. } = &token {
+            if name == "script" || name == "style" {
+                let end = format!("</{name}");
+                input = &rest[rest.find(&end).unwrap_or(rest.len())..];
+                tokens.push(token);
+                continue;
+            }
+        }
+        tokens.push(token);
+        input = rest;
+    }
+    tokens
+}
+
+fn token(input: &str) -> IResult<&str, Token> {
+    alt((
+        value(
+            Token::Skipped,
+            delimited(tag("<!--"), alt((take_until("-->"), rest)), opt(tag("-->"))),
+        ),
+        value(
+            Token::Skipped,
+            delimited(tag("<!"), take_until(">"), char('>')),
+        ),
+        map(
+            delimited(tag("</"), name, preceded(multispace0, char('>'))),
+            Token::Close,
+        ),
+        map(
+            delimited(
+                char('<'),
+                pair(name, many0(attribute)),
+                preceded(multispace0, terminated(opt(char('/')), char('>'))),
+            ),
+            |(name, attributes)| Token::Open { name, attributes },
+        ),
+        map(take_till1(|c| c == '<'), |text| Token::Text(decode(text))),
+        // A `<` that starts no tag
+        map(tag("<"), |text: &str| Token::Text(text.to_string())),
+    ))(input)
+}
+
+fn name(input: &str) -> IResult<&str, String> {
+    map(
+        recognize(take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-')),
+        str::to_ascii_lowercase,
+    )(input)
+}
+
+fn attribute(input: &str) -> IResult<&str, (String, String)> {
+    let quoted = alt((
+        delimited(char('"'), opt(is_not("\"")), char('"')),
+        delimited(char('\''), opt(is_not("'")), char('\'')),
+        map(is_not(" \t\r\n>"), Some),
+    ));
+    map(
+        preceded(
+            multispace1,
+            pair(
+                name,
+                opt(preceded(
+                    tuple((multispace0, char('='), multispace0)),
+                    quoted,
+                )),
+            ),
+        ),
+        |(name, value)| (name, decode(value.flatten().unwrap_or_default())),
+    )(input)
+}
+
+/// Replaces character references with the characters they stand for. Unknown ones are kept.
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((character(&rest[1..end])?, end)));
+        match reference {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn character(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/// Nests the tokens, closing elements left open when an enclosing one closes.
+fn tree(tokens: &[Token]) -> Vec<Node> {
+    // The elements still open, with their children so far
+    let mut open = vec![(String::new(), Vec::new(), Vec::new())];
+    for token in tokens {
+        match token {
+            Token::Open { name, attributes } if VOID_ELEMENTS.contains(&name.as_str()) => {
+                open.last_mut().unwrap().2.push(Node::Element {
+                    name: name.clone(),
+                    attributes: attributes.clone(),
+                    children: Vec::new(),
+                });
+            }
+            Token::Open { name, attributes } => {
+                open.push((name.clone(), attributes.clone(), Vec::new()));
+            }
+            Token::Close(name) => {
+                if open.iter().skip(1).any(|(open_name, ..)| open_name == name) {
+                    while let Some((open_name, attributes, children)) = open.pop() {
+                        let element = Node::Element {
+                            name: open_name.clone(),
+                            attributes,
+                            children,
+                        };
+                        open.last_mut().unwrap().2.push(element);
+                        if &open_name == name {
+                            break;
+                        }
+                    }
+                }
+            }
+            Token::Text(text) => open.last_mut().unwrap().2.push(Node::Text(text.clone())),
+            Token::Skipped => {}
+        }
+    }
+    while open.len() > 1 {
+        let (name, attributes, children) = open.pop().unwrap();
+        open.last_mut().unwrap().2.push(Node::Element {
+            name,
+            attributes,
+            children,
+        });
+    }
+    open.pop().unwrap().2
+}
+
+/// The children of every `<article class="day-desc">`, in page order.
+fn articles(nodes: &[Node]) -> Vec<&[Node]> {
+    nodes
+        .iter()
+        .flat_map(|node| match node {
+            Node::Element {
+                name,
+                attributes,
+                children,
+            } => {
+                let is_description = name == "article"
+                    && attributes.iter().any(|(attribute, value)| {
+                        attribute == "class" && value.split_whitespace().any(|c| c == "day-desc")
+                    });
+                match is_description {
+                    true => vec![children.as_slice()],
+                    false => articles(children),
+                }
+            }
+            Node::Text(_) => Vec::new(),
+        })
+        .collect()
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "pre"
+            | "ul"
+            | "ol"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "div"
+            | "section"
+            | "blockquote"
+            | "hr"
+    )
+}
+
+/// The Markdown blocks for `nodes`, which are separated by blank lines.
+fn blocks(nodes: &[Node], url: &str) -> Vec<String> {
+    let mut markdown = Vec::new();
+    // Text and inline elements between blocks form a paragraph of their own
+    let mut loose = Vec::new();
+    let flush = |loose: &mut Vec<&Node>, markdown: &mut Vec<String>| {
+        let paragraph = inline_all(loose.drain(..), url);
+        if !paragraph.is_empty() {
+            markdown.push(paragraph);
+        }
+    };
+
+    for node in nodes {
+        let Node::Element { name, children, .. } = node else {
+            loose.push(node);
+            continue;
+        };
+        if !is_block(name) {
+            loose.push(node);
+            continue;
+        }
+        flush(&mut loose, &mut markdown);
+
+        match name.as_str() {
+            "p" => markdown.extend(Some(inline_all(children, url)).filter(|p| !p.is_empty())),
+            "pre" => {
+                let code = text_content(children);
+                let fence = "`".repeat(longest_run(&code, '`').max(2) + 1);
+                let newline = if code.ends_with('\n') { "" } else { "\n" };
+                markdown.push(format!("{fence}\n{code}{newline}{fence}"));
+            }
+            "ul" | "ol" => markdown.push(list(name == "ol", children, url, 0)),
+            "hr" => markdown.push("---".to_string()),
+            "blockquote" => {
+                markdown.extend(blocks(children, url).into_iter().map(|block| quote(&block)))
+            }
+            heading if heading.starts_with('h') => {
+                let level = heading[1..].parse().unwrap_or(2);
+                markdown.push(format!(
+                    "{} {}",
+                    "#".repeat(level),
+                    inline_all(children, url)
+                ));
+            }
+            _ => markdown.extend(blocks(children, url)),
+        }
+    }
+    flush(&mut loose, &mut markdown);
+    markdown
+}
+
+fn list(ordered: bool, items: &[Node], url: &str, depth: usize) -> String {
+    let indent = "  ".repeat(depth);
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Node::Element { name, children, .. } if name == "li" => Some(children),
+            _ => None,
+        })
+        .enumerate()
+        .map(|(i, children)| {
+            let marker = match ordered {
+                true => format!("{}. ", i + 1),
+                false => "- ".to_string(),
+            };
+            let (nested, text): (Vec<_>, Vec<_>) = children.iter().partition(
+                |child| matches!(child, Node::Element { name, .. } if name == "ul" || name == "ol"),
+            );
+            let mut item = format!("{indent}{marker}{}", inline_all(text, url));
+            for list_node in nested {
+                if let Node::Element { name, children, .. } = list_node {
+                    item.push('\n');
+                    item.push_str(&list(name == "ol", children, url, depth + 1));
+                }
+            }
+            item
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The inline Markdown for `nodes` as one paragraph, with runs of whitespace collapsed.
+fn inline_all<'a>(nodes: impl IntoIterator<Item = &'a Node>, url: &str) -> String {
+    let text = nodes
+        .into_iter()
+        .map(|node| inline(node, url))
+        .collect::<String>();
+    collapse(&text)
+}
+
+fn inline(node: &Node, url: &str) -> String {
+    let (name, attributes, children) = match node {
+        Node::Text(text) => return escape(text),
+        Node::Element {
+            name,
+            attributes,
+            children,
+        } => (name.as_str(), attributes, children),
+    };
+    match name {
+        "code" => {
+            let code = collapse(&text_content(children));
+            let span = code_span(code.trim());
+            // `<code><em>11</em></code>` is how the pages highlight an answer
+            match children.as_slice() {
+                [Node::Element { name, .. }] if name == "em" => format!("**{span}**"),
+                _ => span,
+            }
+        }
+        "em" | "strong" | "b" => wrap(&collapse(&inline_children(children, url)), "**"),
+        "i" => wrap(&collapse(&inline_children(children, url)), "*"),
+        "a" => {
+            let text = collapse(&inline_children(children, url));
+            match attributes.iter().find(|(attribute, _)| attribute == "href") {
+                Some((_, href)) => format!("[{}]({})", text.trim(), resolve(href, url)),
+                None => text,
+            }
+        }
+        "br" => "\\\n".to_string(),
+        _ => inline_children(children, url),
+    }
+}
+
+fn inline_children(children: &[Node], url: &str) -> String {
+    children.iter().map(|child| inline(child, url)).collect()
+}
+
+/// Puts `marker` around the text, leaving any surrounding whitespace outside, where Markdown
+/// needs it to see the emphasis.
+fn wrap(text: &str, marker: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let before = if text.starts_with(' ') { " " } else { "" };
+    let after = if text.ends_with(' ') { " " } else { "" };
+    format!("{before}{marker}{trimmed}{marker}{after}")
+}
+
+fn code_span(code: &str) -> String {
+    let fence = "`".repeat(longest_run(code, '`') + 1);
+    match code.starts_with('`') || code.ends_with('`') {
+        true => format!("{fence} {code} {fence}"),
+        false => format!("{fence}{code}{fence}"),
+    }
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c)
+        .map(str::len)
+        .max()
+        .unwrap_or(0)
+}
+
+fn text_content(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => text_content(children),
+        })
+        .collect()
+}
+
+/// Collapses runs of whitespace other than line breaks into one space, and trims the ends.
+fn collapse(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() && c != '\n' && c != '\u{a0}' {
+            space = true;
+        } else {
+            if space && !collapsed.is_empty() && !collapsed.ends_with('\n') {
+                collapsed.push(' ');
+            }
+            space = false;
+            collapsed.push(c);
+        }
+    }
+    if space && !collapsed.is_empty() {
+        collapsed.push(' ');
+    }
+    collapsed
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\n' | '\r' | '\t' => escaped.push(' '),
+            '\\' | '*' | '_' | '`' | '[' | ']' | '<' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `href` as an absolute URL, given the page at `url` it is on.
+fn resolve(href: &str, url: &str) -> String {
+    if href.contains("://") || href.starts_with("mailto:") {
+        href.to_string()
+    } else if let Some(path) = href.strip_prefix('/') {
+        format!("{ORIGIN}/{path}")
+    } else if href.starts_with('#') {
+        format!("{}{href}", url.split('#').next().unwrap_or(url))
+    } else {
+        let directory = &url[..url.rfind('/').map_or(url.len(), |slash| slash + 1)];
+        format!("{directory}{href}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://adventofcode.com/2024/day/1";
+
+    #[test]
+    fn test_both_parts() {
+        let html = include_str!("../tests/fixtures/article/both-parts.html");
+        let expected = include_str!("../tests/fixtures/article/both-parts.md");
+        assert_eq!(Some(expected.to_string()), to_markdown(html, URL));
+    }
+
+    #[test]
+    fn test_part_one() {
+        let html = include_str!("../tests/fixtures/article/part-one.html");
+        let expected = include_str!("../tests/fixtures/article/part-one.md");
+        assert_eq!(Some(expected.to_string()), to_markdown(html, URL));
+    }
+
+    #[test]
+    fn test_no_article() {
+        assert_eq!(
+            None,
+            to_markdown("<html><body><p>Please log in.</p></body></html>", URL)
+        );
+    }
+
+    #[test]
+    fn test_inline() {
+        let markdown = |html: &str| {
+            to_markdown(
+                &format!("<article class=\"day-desc\">{html}</article>"),
+                URL,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            "a **`11`** b\n",
+            markdown("<p>a <code><em>11</em></code> b</p>")
+        );
+        assert_eq!("**x** y\n", markdown("<p><em>x </em>y</p>"));
+        assert_eq!(
+            "2 \\* 3 \\< 7 & 1\n",
+            markdown("<p>2 * 3 &lt; 7 &amp; 1</p>")
+        );
+        assert_eq!("``a`b``\n", markdown("<p><code>a`b</code></p>"));
+        assert_eq!(
+            "[input](https://adventofcode.com/2024/day/1/input) [about](https://adventofcode.com/2024/about)\n",
+            markdown("<p><a href=\"1/input\">input</a> <a href=\"/2024/about\">about</a></p>")
+        );
+        assert_eq!("&foo; é 😀\n", markdown("<p>&foo; &#233; &#x1F600;</p>"));
+        assert_eq!("a \\< b\n\nc\n", markdown("a < b<p>c"));
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            "https://adventofcode.com/2024/day/1#part2",
+            resolve("#part2", URL)
+        );
+        assert_eq!("https://example.com/", resolve("https://example.com/", URL));
+    }
+}
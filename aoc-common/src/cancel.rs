@@ -0,0 +1,85 @@
+//! Cooperative cancellation for solvers whose running time depends heavily on the input.
+//!
+//! A solver checks its [`CancelToken`] in its main loop and gives up with [`Cancelled`] once the
+//! token was cancelled or its deadline has passed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("The time budget was exceeded")]
+pub struct Cancelled;
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    deadline: Option<Instant>,
+}
+
+/// Shared flag plus optional deadline. Clones observe the same cancellation.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<Inner>,
+}
+
+impl CancelToken {
+    /// A token that only expires when it is cancelled explicitly.
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+
+    pub fn with_deadline(deadline: Instant) -> Self {
+        CancelToken {
+            inner: Arc::new(Inner {
+                cancelled: AtomicBool::new(false),
+                deadline: Some(deadline),
+            }),
+        }
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Self {
+        CancelToken::with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+            || self
+                .inner
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.is_cancelled() {
+            true => Err(Cancelled),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert_eq!(Ok(()), clone.check());
+
+        token.cancel();
+        assert_eq!(Err(Cancelled), clone.check());
+    }
+
+    #[test]
+    fn test_deadline() {
+        assert!(!CancelToken::with_timeout(Duration::from_secs(3600)).is_cancelled());
+        assert!(CancelToken::with_deadline(Instant::now()).is_cancelled());
+    }
+}
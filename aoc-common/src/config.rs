@@ -0,0 +1,253 @@
+//! Puzzle parameters, read from `aoc.toml` in the workspace root.
+//!
+//! Every day that takes more than its input has a section such as `[day-14]`. Missing sections
+//! and keys fall back to the values for the real puzzle input, so an absent file is the same
+//! as an empty one.
+
+use miette::Diagnostic;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{fs, io};
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum ConfigError {
+    #[error("Could not read {}", path.display())]
+    #[diagnostic(code(aoc::config::io))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::config::parse))]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Invalid `{name}` in [{section}]: {reason}")]
+    #[diagnostic(code(aoc::invalid_parameter))]
+    Invalid {
+        section: &'static str,
+        name: &'static str,
+        reason: &'static str,
+    },
+}
+
+/// Claw contraption prizes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day13 {
+    /// Added to both prize coordinates in part 2
+    pub prize_offset: u64,
+}
+
+impl Default for Day13 {
+    fn default() -> Self {
+        Day13 {
+            prize_offset: 10000000000000,
+        }
+    }
+}
+
+/// Restroom robots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day14 {
+    pub height: u32,
+    pub width: u32,
+    /// Seconds to simulate in part 1
+    pub steps: usize,
+    /// Seconds after which part 2 gives up looking for the tree
+    pub max_steps: usize,
+}
+
+impl Default for Day14 {
+    fn default() -> Self {
+        Day14 {
+            height: 103,
+            width: 101,
+            steps: 100,
+            max_steps: 100000,
+        }
+    }
+}
+
+/// RAM run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day18 {
+    /// Side length of the memory space
+    pub size: usize,
+    /// Number of fallen bytes in part 1
+    pub num_bytes: usize,
+}
+
+impl Default for Day18 {
+    fn default() -> Self {
+        Day18 {
+            size: 71,
+            num_bytes: 1024,
+        }
+    }
+}
+
+/// Race condition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day20 {
+    /// Picoseconds a cheat has to save at least to be counted
+    pub minimum_saving: usize,
+}
+
+impl Default for Day20 {
+    fn default() -> Self {
+        Day20 {
+            minimum_saving: 100,
+        }
+    }
+}
+
+/// Keypad conundrum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day21 {
+    /// Directional keypad robots in part 2
+    pub levels: u8,
+}
+
+impl Default for Day21 {
+    fn default() -> Self {
+        Day21 { levels: 26 }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(rename = "day-13")]
+    pub day13: Day13,
+    #[serde(rename = "day-14")]
+    pub day14: Day14,
+    #[serde(rename = "day-18")]
+    pub day18: Day18,
+    #[serde(rename = "day-20")]
+    pub day20: Day20,
+    #[serde(rename = "day-21")]
+    pub day21: Day21,
+}
+
+impl FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let config: Config = toml::from_str(s)?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl Config {
+    /// `aoc.toml` in the workspace root.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("aoc.toml")
+    }
+
+    /// Reads and validates the file at `path`. A missing file gives the defaults.
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents.parse(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(source) => Err(ConfigError::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    /// Rejects values the solvers cannot work with, such as an empty grid.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let positive = |section, name, value: usize| match value {
+            0 => Err(ConfigError::Invalid {
+                section,
+                name,
+                reason: "must be at least 1",
+            }),
+            _ => Ok(()),
+        };
+
+        positive("day-14", "height", self.day14.height as usize)?;
+        positive("day-14", "width", self.day14.width as usize)?;
+        positive("day-18", "size", self.day18.size)?;
+        Ok(())
+    }
+
+    /// The parameter section of `day` in TOML form, or `None` if the day takes none.
+    pub fn section(&self, day: u8) -> Option<String> {
+        let section = match day {
+            13 => toml::to_string(&self.day13),
+            14 => toml::to_string(&self.day14),
+            18 => toml::to_string(&self.day18),
+            20 => toml::to_string(&self.day20),
+            21 => toml::to_string(&self.day21),
+            _ => return None,
+        };
+        Some(section.expect("sections should serialize"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), ConfigError> {
+        let config: Config = "[day-14]
+height = 7
+width = 11
+
+[day-21]
+levels = 3
+"
+        .parse()?;
+
+        assert_eq!(7, config.day14.height);
+        assert_eq!(100, config.day14.steps);
+        assert_eq!(3, config.day21.levels);
+        assert_eq!(Day18::default(), config.day18);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            "[day-14]\nheigth = 7".parse::<Config>(),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            "[day-14]\nheight = -1".parse::<Config>(),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            "[day-18]\nsize = 0".parse::<Config>(),
+            Err(ConfigError::Invalid { name: "size", .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_missing_file() -> Result<(), ConfigError> {
+        assert_eq!(
+            Config::default(),
+            Config::load(Path::new("does-not-exist.toml"))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_workspace_config_is_valid() -> Result<(), ConfigError> {
+        Config::load(&Config::default_path())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+//! Finding where a deterministic sequence of states starts repeating, to skip ahead to states
+//! far in the future.
+//!
+//! A sequence is given by its initial state and a `next` function in the style of
+//! [`std::iter::successors`], returning `None` once the sequence ends. `next` has to give the
+//! same successor for equal states every time.
+//!
+//! [`trace`] hashes every state it sees and keeps them, which takes the fewest steps. [`brent`],
+//! [`floyd`] and [`fast_forward`] only keep a couple of states at a time, for sequences whose
+//! states are too large or too many to remember.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence repeats: the state after `start + period` steps is the state after `start`
+/// steps again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The steps before the first state of the cycle
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The first step that reaches the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// The states of a sequence up to where it ended or started repeating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<T> {
+    /// Every distinct state, the initial one first
+    pub states: Vec<T>,
+    pub cycle: Option<Cycle>,
+}
+
+impl<T> Trace<T> {
+    /// The state after `n` steps, or `None` if the sequence ended before.
+    pub fn nth(&self, n: usize) -> Option<&T> {
+        match self.cycle {
+            Some(cycle) => self.states.get(cycle.reduce(n)),
+            None => self.states.get(n),
+        }
+    }
+}
+
+/// Follows the sequence until it ends or comes back to a state it was in before.
+pub fn trace<T: Hash + Eq + Clone>(initial: T, mut next: impl FnMut(&T) -> Option<T>) -> Trace<T> {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = Some(initial);
+
+    while let Some(current) = state {
+        if let Some(&start) = seen.get(&current) {
+            let period = states.len() - start;
+            return Trace {
+                states,
+                cycle: Some(Cycle { start, period }),
+            };
+        }
+        seen.insert(current.clone(), states.len());
+        state = next(&current);
+        states.push(current);
+    }
+
+    Trace {
+        states,
+        cycle: None,
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, or `None` if the sequence ends.
+pub fn brent<T: PartialEq + Clone>(
+    initial: T,
+    mut next: impl FnMut(&T) -> Option<T>,
+) -> Option<Cycle> {
+    match search(&initial, &mut next, usize::MAX) {
+        Search::Found(cycle) => Some(cycle),
+        Search::Ended | Search::Reached(_) => None,
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare, or `None` if the sequence ends. Usually takes
+/// more steps than [`brent`].
+pub fn floyd<T: PartialEq + Clone>(
+    initial: T,
+    mut next: impl FnMut(&T) -> Option<T>,
+) -> Option<Cycle> {
+    // The hare runs twice as fast, so they meet somewhere in the cycle
+    let mut tortoise = next(&initial)?;
+    let mut hare = next(&tortoise)?;
+    while tortoise != hare {
+        tortoise = next(&tortoise)?;
+        let ahead = next(&hare)?;
+        hare = next(&ahead)?;
+    }
+
+    // The meeting point is as far from the start of the cycle as the initial state
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = next(&tortoise)?;
+        hare = next(&hare)?;
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = next(&tortoise)?;
+    while tortoise != hare {
+        hare = next(&hare)?;
+        period += 1;
+    }
+
+    Some(Cycle { start, period })
+}
+
+/// The state after `n` steps, or `None` if the sequence ends before. Stops looking for a cycle
+/// once step `n` is reached, so this is never slower than stepping `n` times by much.
+pub fn fast_forward<T: PartialEq + Clone>(
+    initial: T,
+    mut next: impl FnMut(&T) -> Option<T>,
+    n: usize,
+) -> Option<T> {
+    match search(&initial, &mut next, n) {
+        Search::Ended => None,
+        Search::Reached(state) => Some(state),
+        Search::Found(cycle) => (0..cycle.reduce(n)).try_fold(initial, |state, _| next(&state)),
+    }
+}
+
+/// How far [`search`] got.
+enum Search<T> {
+    Ended,
+    /// The state after the given number of steps, found before any cycle
+    Reached(T),
+    Found(Cycle),
+}
+
+/// Brent's algorithm, giving up after `limit` steps.
+fn search<T: PartialEq + Clone>(
+    initial: &T,
+    next: &mut impl FnMut(&T) -> Option<T>,
+    limit: usize,
+) -> Search<T> {
+    if limit == 0 {
+        return Search::Reached(initial.clone());
+    }
+
+    // The tortoise waits at every power of two for the hare to come around
+    let mut power = 1;
+    let mut period = 1;
+    let mut steps = 1;
+    let mut tortoise = initial.clone();
+    let Some(mut hare) = next(initial) else {
+        return Search::Ended;
+    };
+    while tortoise != hare {
+        if steps == limit {
+            return Search::Reached(hare);
+        }
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        let Some(state) = next(&hare) else {
+            return Search::Ended;
+        };
+        hare = state;
+        period += 1;
+        steps += 1;
+    }
+
+    // With the hare a period ahead, both reach the start of the cycle at the same time
+    const CYCLES: &str = "a cycling sequence does not end";
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..period {
+        hare = next(&hare).expect(CYCLES);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise).expect(CYCLES);
+        hare = next(&hare).expect(CYCLES);
+        start += 1;
+    }
+
+    Search::Found(Cycle { start, period })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3, 4, 5, 6 over and over.
+    fn rho(&n: &u32) -> Option<u32> {
+        Some(if n == 6 { 3 } else { n + 1 })
+    }
+
+    /// 0 to 4, then ends.
+    fn line(&n: &u32) -> Option<u32> {
+        (n < 4).then_some(n + 1)
+    }
+
+    #[test]
+    fn test_detection() {
+        let cycle = Some(Cycle {
+            start: 3,
+            period: 4,
+        });
+        assert_eq!(cycle, brent(0, rho));
+        assert_eq!(cycle, floyd(0, rho));
+        assert_eq!(cycle, trace(0, rho).cycle);
+        assert_eq!(None, brent(0, line));
+        assert_eq!(None, floyd(0, line));
+        assert_eq!(
+            Trace {
+                states: vec![0, 1, 2, 3, 4],
+                cycle: None
+            },
+            trace(0, line)
+        );
+
+        let fixed_point = Some(Cycle {
+            start: 0,
+            period: 1,
+        });
+        assert_eq!(fixed_point, brent(7, |&n| Some(n)));
+        assert_eq!(fixed_point, floyd(7, |&n| Some(n)));
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let traced = trace(0, rho);
+        for n in [0, 2, 3, 6, 7, 1_000_000_000_001] {
+            let expected = std::iter::successors(Some(0), rho).nth(
+                Cycle {
+                    start: 3,
+                    period: 4,
+                }
+                .reduce(n),
+            );
+            assert_eq!(expected, fast_forward(0, rho, n), "step {n}");
+            assert_eq!(expected.as_ref(), traced.nth(n), "step {n}");
+        }
+        assert_eq!(Some(5), fast_forward(0, rho, 1_000_000_000_001));
+
+        assert_eq!(Some(4), fast_forward(0, line, 4));
+        assert_eq!(None, fast_forward(0, line, 5));
+        assert_eq!(None, trace(0, line).nth(5));
+    }
+}
@@ -0,0 +1,168 @@
+//! Comparing text grids cell by cell, for tests whose failures would otherwise be two
+//! unreadable blocks of dots.
+
+use std::fmt::Write;
+
+/// A cell in which two grids differ. A cell one of the grids does not have is `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellDiff {
+    pub x: usize,
+    pub y: usize,
+    pub expected: Option<char>,
+    pub actual: Option<char>,
+}
+
+/// The differing cells of two grids in reading order, lines being rows.
+pub fn diff(expected: &str, actual: &str) -> Vec<CellDiff> {
+    let expected = rows(expected);
+    let actual = rows(actual);
+
+    (0..expected.len().max(actual.len()))
+        .flat_map(|y| {
+            let expected = expected.get(y).map_or(&[][..], Vec::as_slice);
+            let actual = actual.get(y).map_or(&[][..], Vec::as_slice);
+            (0..expected.len().max(actual.len())).filter_map(move |x| {
+                let cells = (expected.get(x).copied(), actual.get(x).copied());
+                (cells.0 != cells.1).then_some(CellDiff {
+                    x,
+                    y,
+                    expected: cells.0,
+                    actual: cells.1,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Both grids next to each other under their titles, with the rows listed in `marked` flagged
+/// by a `*`.
+pub fn side_by_side(
+    (left_title, left): (&str, &str),
+    (right_title, right): (&str, &str),
+    marked: &[usize],
+) -> String {
+    let left = left.lines().collect::<Vec<_>>();
+    let right = right.lines().collect::<Vec<_>>();
+    let width = left
+        .iter()
+        .map(|row| row.chars().count())
+        .chain([left_title.chars().count()])
+        .max()
+        .unwrap_or_default();
+
+    let mut view = format!("    {left_title:width$}   {right_title}\n");
+    for y in 0..left.len().max(right.len()) {
+        let mark = if marked.contains(&y) { '*' } else { ' ' };
+        let left = left.get(y).copied().unwrap_or_default();
+        let right = right.get(y).copied().unwrap_or_default();
+        writeln!(view, "{mark}{y:>2} {left:width$}   {right}").unwrap();
+    }
+    view
+}
+
+/// Describes how `actual` differs from `expected`, or `None` if they are the same grid.
+pub fn report(expected: &str, actual: &str) -> Option<String> {
+    let cells = diff(expected, actual);
+    if cells.is_empty() {
+        return None;
+    }
+
+    let show = |cell: Option<char>| cell.map_or("nothing".to_string(), |c| format!("{c:?}"));
+    let mut report = match cells.len() {
+        1 => "1 cell differs:\n".to_string(),
+        n => format!("{n} cells differ:\n"),
+    };
+    for cell in &cells {
+        writeln!(
+            report,
+            "  ({}, {}): expected {}, found {}",
+            cell.x,
+            cell.y,
+            show(cell.expected),
+            show(cell.actual)
+        )
+        .unwrap();
+    }
+
+    let mut marked = cells.iter().map(|cell| cell.y).collect::<Vec<_>>();
+    marked.dedup();
+    report.push_str(&side_by_side(
+        ("expected", expected),
+        ("actual", actual),
+        &marked,
+    ));
+    Some(report)
+}
+
+/// Asserts that two grids are equal, listing the differing cells by coordinate and showing
+/// both grids side by side otherwise.
+#[macro_export]
+macro_rules! assert_grid_eq {
+    ($expected:expr, $actual:expr $(,)?) => {
+        if let Some(report) = $crate::grid::report(&$expected, &$actual) {
+            panic!("grids differ, {report}");
+        }
+    };
+}
+
+fn rows(grid: &str) -> Vec<Vec<char>> {
+    grid.lines().map(|row| row.chars().collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let expected = "#..\n.O.\n";
+        let actual = "#..\n..O\n#";
+
+        assert_eq!(
+            vec![
+                CellDiff {
+                    x: 1,
+                    y: 1,
+                    expected: Some('O'),
+                    actual: Some('.'),
+                },
+                CellDiff {
+                    x: 2,
+                    y: 1,
+                    expected: Some('.'),
+                    actual: Some('O'),
+                },
+                CellDiff {
+                    x: 0,
+                    y: 2,
+                    expected: None,
+                    actual: Some('#'),
+                },
+            ],
+            diff(expected, actual)
+        );
+        assert_eq!(None, report(expected, expected));
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            Some(
+                "1 cell differs:
+  (1, 1): expected 'O', found '.'
+    expected   actual
+  0 #..        #..
+* 1 .O.        ...
+"
+                .to_string()
+            ),
+            report("#..\n.O.", "#..\n...")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "(2, 0): expected '.', found '@'")]
+    fn test_assert_grid_eq() {
+        assert_grid_eq!("#..", "#.@");
+    }
+}
@@ -0,0 +1,148 @@
+//! Loading puzzle inputs and normalising them before they reach a parser.
+
+use crate::sealed::{self, InputKey, KeyError};
+use miette::Diagnostic;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use thiserror::Error;
+
+/// The name of a day's sealed input, shared by both parts.
+pub const SEALED_INPUT: &str = "input.txt.enc";
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum InputError {
+    #[error("No puzzle input in {}", dir.display())]
+    #[diagnostic(
+        code(aoc::input::not_found),
+        help("fetch it with `just get-input day-XX`, which stores it as {SEALED_INPUT}")
+    )]
+    NotFound { dir: PathBuf },
+
+    #[error("Could not read {}", path.display())]
+    #[diagnostic(code(aoc::input::io))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Key(#[from] KeyError),
+
+    #[error("Could not decrypt {}", path.display())]
+    #[diagnostic(
+        code(aoc::input::wrong_key),
+        help("the input key is not the one it was encrypted with, or the file is damaged")
+    )]
+    Undecryptable { path: PathBuf },
+}
+
+/// The input for `part` of the day whose crate is `day_dir`: its `input{part}.txt` if there is
+/// one, so a plain local copy always wins, or else its sealed [`SEALED_INPUT`]. The input is
+/// [`normalize`]d, so the `day-XX` binaries and benchmarks see what the `aoc` runner sees.
+pub fn load(day_dir: impl AsRef<Path>, part: u8) -> Result<String, InputError> {
+    let day_dir = day_dir.as_ref();
+    [
+        day_dir.join(format!("input{part}.txt")),
+        day_dir.join(SEALED_INPUT),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+    .ok_or_else(|| InputError::NotFound {
+        dir: day_dir.to_path_buf(),
+    })
+    .and_then(|path| read(&path))
+    .map(|input| match normalize(&input) {
+        Cow::Borrowed(_) => input,
+        Cow::Owned(normalized) => normalized,
+    })
+}
+
+/// [`load`] for benchmarks, which have no way to report an error. Panics with the diagnostic if
+/// the input cannot be loaded.
+pub fn bench_input(day_dir: &str, part: u8) -> String {
+    load(day_dir, part).unwrap_or_else(|err| panic!("{:?}", miette::Report::new(err)))
+}
+
+/// Reads the input at `path`, decrypting it with the key from the environment if it is sealed.
+pub fn read(path: &Path) -> Result<String, InputError> {
+    let io_error = |source| InputError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let bytes = fs::read(path).map_err(io_error)?;
+    if !sealed::is_sealed(&bytes) {
+        return String::from_utf8(bytes)
+            .map_err(|err| io_error(io::Error::new(io::ErrorKind::InvalidData, err)));
+    }
+
+    let key = InputKey::from_env()?;
+    sealed::open(&key, &bytes).ok_or_else(|| InputError::Undecryptable {
+        path: path.to_path_buf(),
+    })
+}
+
+/// Brings `input` into the shape of a freshly downloaded puzzle input: no UTF-8 byte order
+/// mark, `\n` line endings, no trailing whitespace on any line and exactly one newline after
+/// the last non-blank line. Blank lines between sections are kept.
+///
+/// Returns the input unchanged if it already has that shape.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let mut lines = input
+        .strip_prefix('\u{feff}')
+        .unwrap_or(input)
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    let mut normalized = lines.join("\n");
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+
+    match normalized == input {
+        true => Cow::Borrowed(input),
+        false => Cow::Owned(normalized),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("1 2\n\n3 4\n"), Cow::Borrowed(_)));
+        assert_eq!(
+            "1 2\n\n3 4\n",
+            normalize("\u{feff}1 2\r\n \r\n3 4  \r\n\r\n\n")
+        );
+        assert_eq!("1 2\n3 4\n", normalize("1 2\n3 4"));
+        assert_eq!("  1\n", normalize("  1\t"));
+        assert_eq!("", normalize("\n \n"));
+    }
+
+    #[test]
+    fn test_load() -> Result<(), InputError> {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(load(&dir, 1), Err(InputError::NotFound { .. })));
+
+        let sealed = sealed::seal(&InputKey::generate(), "3   4\n");
+        fs::write(dir.path().join(SEALED_INPUT), sealed).unwrap();
+        assert!(matches!(
+            load(&dir, 1),
+            Err(InputError::Key(_) | InputError::Undecryptable { .. })
+        ));
+
+        fs::write(dir.path().join("input2.txt"), "1   2\n").unwrap();
+        assert_eq!("1   2\n", load(&dir, 2)?);
+        fs::write(dir.path().join("input1.txt"), "\u{feff}1   2\r\n\r\n").unwrap();
+        assert_eq!("1   2\n", load(&dir, 1)?);
+        Ok(())
+    }
+}
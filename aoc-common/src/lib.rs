@@ -0,0 +1,12 @@
+//! Building blocks shared by the daily solutions.
+
+pub mod article;
+pub mod cancel;
+pub mod config;
+pub mod cycle;
+pub mod grid;
+pub mod input;
+pub mod memo;
+pub mod sealed;
+pub mod simulation;
+pub mod stream;
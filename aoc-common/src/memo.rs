@@ -0,0 +1,133 @@
+//! Memoisation owned by a single solve.
+//!
+//! A global cache outlives the input it was filled from, so a second input solved in the same
+//! process can get answers that belong to the first. A [`Memo`] is created by the solve that
+//! needs it, is passed down the recursion, and is dropped with the rest of the solve's state.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Values computed during one solve, by the key they were computed for.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    /// The most values remembered at once
+    limit: usize,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            values: HashMap::new(),
+            limit: usize::MAX,
+        }
+    }
+
+    /// A memo that forgets every value before remembering more than `limit` of them. Values
+    /// are computed again after that, but the memory for `limit` values is taken up front and
+    /// never grows, however many keys the solve meets.
+    pub fn bounded(limit: usize) -> Self {
+        Memo {
+            values: HashMap::with_capacity(limit),
+            limit,
+        }
+    }
+
+    /// The value for `key`, computed by `compute` unless it was before. `compute` gets the memo
+    /// to look up the values it depends on.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            return value.clone();
+        }
+        let value = compute(self);
+        self.remember(key, value.clone());
+        value
+    }
+
+    /// [`Memo::get_or_compute`] for a `compute` that can fail. Failures are not remembered.
+    pub fn try_get_or_compute<E>(
+        &mut self,
+        key: K,
+        compute: impl FnOnce(&mut Self) -> Result<V, E>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.values.get(&key) {
+            return Ok(value.clone());
+        }
+        let value = compute(self)?;
+        self.remember(key, value.clone());
+        Ok(value)
+    }
+
+    fn remember(&mut self, key: K, value: V) {
+        if self.values.len() >= self.limit {
+            self.values.clear();
+        }
+        self.values.insert(key, value);
+    }
+
+    /// The number of values remembered.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Forgets every value, keeping the memory they took for the next ones.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>, calls: &mut usize) -> u64 {
+        memo.get_or_compute(n, |memo| {
+            *calls += 1;
+            match n {
+                0 | 1 => n,
+                _ => fibonacci(n - 1, memo, calls) + fibonacci(n - 2, memo, calls),
+            }
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        assert_eq!(12586269025, fibonacci(50, &mut memo, &mut calls));
+        assert_eq!(51, calls);
+        assert_eq!(51, memo.len());
+
+        assert_eq!(55, fibonacci(10, &mut memo, &mut calls));
+        assert_eq!(51, calls);
+    }
+
+    #[test]
+    fn test_try_get_or_compute() {
+        let mut memo = Memo::new();
+        assert_eq!(Err("odd"), memo.try_get_or_compute(1, |_| Err("odd")));
+        assert!(memo.is_empty());
+        assert_eq!(Ok::<_, &str>(4), memo.try_get_or_compute(2, |_| Ok(4)));
+        assert_eq!(Ok(4), memo.try_get_or_compute(2, |_| Err("computed again")));
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded(10);
+        let mut calls = 0;
+        assert_eq!(12586269025, fibonacci(50, &mut memo, &mut calls));
+        // Some values were forgotten and computed again
+        assert!(calls > 51);
+        assert!(memo.len() <= 10);
+    }
+}
@@ -0,0 +1,211 @@
+//! Puzzle inputs encrypted at rest, so they can be committed without publishing them.
+//!
+//! A sealed input is [`MAGIC`], a random 24-byte nonce and the input encrypted with
+//! XChaCha20-Poly1305. The key is 32 bytes written as 64 hex digits, shared by the team out of
+//! band. It is taken from the `AOC_INPUT_KEY` environment variable, or else from the file that
+//! `AOC_INPUT_KEY_FILE` names, which defaults to `.aoc-input-key` in the workspace root.
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use miette::Diagnostic;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+use thiserror::Error;
+
+/// The environment variable holding the key in hex.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+/// The environment variable naming the file holding the key in hex.
+pub const KEY_FILE_VAR: &str = "AOC_INPUT_KEY_FILE";
+/// What every sealed input starts with.
+pub const MAGIC: &[u8] = b"aoc-sealed-v1\n";
+
+const NONCE_LEN: usize = 24;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum KeyError {
+    #[error("There is no key to decrypt the puzzle inputs with")]
+    #[diagnostic(
+        code(aoc::input::missing_key),
+        help(
+            "set AOC_INPUT_KEY to the team's input key, or put the key in {}",
+            key_file.display()
+        )
+    )]
+    Missing { key_file: PathBuf },
+
+    #[error("The input key in {origin} is not 64 hex digits")]
+    #[diagnostic(
+        code(aoc::input::invalid_key),
+        help("a new key can be made with `aoc input key`")
+    )]
+    Invalid { origin: String },
+
+    #[error("Could not read the input key from {}", path.display())]
+    #[diagnostic(code(aoc::input::io))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// The key inputs are sealed with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InputKey(Key);
+
+impl InputKey {
+    /// A new random key.
+    pub fn generate() -> Self {
+        InputKey(XChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    /// The key written as 64 hex digits, or `None` if it is not.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return None;
+        }
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(InputKey(*Key::from_slice(&bytes)))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// `.aoc-input-key` in the workspace root.
+    pub fn default_file() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(".aoc-input-key")
+    }
+
+    /// The key from `AOC_INPUT_KEY`, or else from the key file.
+    pub fn from_env() -> Result<Self, KeyError> {
+        let key_file = env::var_os(KEY_FILE_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(InputKey::default_file);
+        InputKey::find(env::var(KEY_VAR).ok(), &key_file)
+    }
+
+    fn find(var: Option<String>, key_file: &Path) -> Result<Self, KeyError> {
+        let (hex, origin) = match var {
+            Some(hex) => (hex, KEY_VAR.to_string()),
+            None => match fs::read_to_string(key_file) {
+                Ok(hex) => (hex, key_file.display().to_string()),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    return Err(KeyError::Missing {
+                        key_file: key_file.to_path_buf(),
+                    })
+                }
+                Err(source) => {
+                    return Err(KeyError::Io {
+                        path: key_file.to_path_buf(),
+                        source,
+                    })
+                }
+            },
+        };
+        InputKey::from_hex(&hex).ok_or(KeyError::Invalid { origin })
+    }
+}
+
+// Keeps the key out of logs and panic messages
+impl fmt::Debug for InputKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("InputKey(..)")
+    }
+}
+
+/// Whether `bytes` are a sealed input rather than a plain one.
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Encrypts `input` with a fresh nonce, so sealing the same input twice gives different bytes.
+pub fn seal(key: &InputKey, input: &str) -> Vec<u8> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = XChaCha20Poly1305::new(&key.0)
+        .encrypt(&nonce, input.as_bytes())
+        .expect("should encrypt an input of any length that fits in memory");
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Decrypts a sealed input, or `None` if it was sealed with a different key, is damaged or is
+/// not a sealed input at all.
+pub fn open(key: &InputKey, sealed: &[u8]) -> Option<String> {
+    let rest = sealed.strip_prefix(MAGIC)?;
+    if rest.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let input = XChaCha20Poly1305::new(&key.0)
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .ok()?;
+    String::from_utf8(input).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal() {
+        let key = InputKey::generate();
+        let sealed = seal(&key, "3   4\n4   3\n");
+
+        assert!(is_sealed(&sealed));
+        assert_ne!(sealed, seal(&key, "3   4\n4   3\n"));
+        assert_eq!(Some("3   4\n4   3\n".to_string()), open(&key, &sealed));
+        assert_eq!(None, open(&InputKey::generate(), &sealed));
+
+        let mut damaged = sealed.clone();
+        *damaged.last_mut().unwrap() ^= 1;
+        assert_eq!(None, open(&key, &damaged));
+        assert_eq!(None, open(&key, &sealed[..MAGIC.len() + 10]));
+        assert_eq!(None, open(&key, b"3   4\n"));
+    }
+
+    #[test]
+    fn test_key() {
+        let key = InputKey::generate();
+        assert_eq!(Some(key.clone()), InputKey::from_hex(&key.to_hex()));
+        assert_eq!(
+            Some(key.clone()),
+            InputKey::from_hex(&format!("{}\n", key.to_hex().to_uppercase()))
+        );
+        assert_eq!(None, InputKey::from_hex("abc"));
+        assert_eq!(None, InputKey::from_hex(&"g".repeat(64)));
+        assert_eq!(None, InputKey::from_hex(&"é".repeat(32)));
+        assert_eq!("InputKey(..)", format!("{key:?}"));
+    }
+
+    #[test]
+    fn test_find_key() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let key_file = dir.path().join(".aoc-input-key");
+        let key = InputKey::generate();
+
+        assert!(matches!(
+            InputKey::find(None, &key_file),
+            Err(KeyError::Missing { .. })
+        ));
+        fs::write(&key_file, format!("{}\n", key.to_hex()))?;
+        assert_eq!(key, InputKey::find(None, &key_file).unwrap());
+
+        let other = InputKey::generate();
+        assert_eq!(
+            other,
+            InputKey::find(Some(other.to_hex()), &key_file).unwrap()
+        );
+        assert!(matches!(
+            InputKey::find(Some("beef".to_string()), &key_file),
+            Err(KeyError::Invalid { origin }) if origin == KEY_VAR
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+//! Step-by-step simulations that can be inspected, snapshotted and rewound.
+//!
+//! A day implements [`Simulation`] for its state machine and drives it with
+//! [`Simulation::step`] or [`Simulation::run_until`]. Wrapping it in a [`History`] records the
+//! states it went through, so it can be rewound to see what happened at an earlier step.
+
+use std::collections::VecDeque;
+
+/// A state machine advanced one step at a time.
+pub trait Simulation {
+    /// Everything needed to put the simulation back to where it was.
+    type Snapshot: Clone;
+    /// What a single step did.
+    type Event;
+
+    /// Advances one step and tells what happened, or returns `None` without changing anything
+    /// once the simulation has finished.
+    fn step(&mut self) -> Option<Self::Event>;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    fn restore(&mut self, snapshot: Self::Snapshot);
+
+    /// Steps until `predicate` holds for the simulation and returns the number of steps that
+    /// took, or `None` if the simulation finished first. Holding already takes no steps.
+    fn run_until(&mut self, mut predicate: impl FnMut(&Self) -> bool) -> Option<usize>
+    where
+        Self: Sized,
+    {
+        let mut steps = 0;
+        while !predicate(self) {
+            self.step()?;
+            steps += 1;
+        }
+        Some(steps)
+    }
+}
+
+/// Called with the step number and the event of every step.
+type EventCallback<'a, E> = Box<dyn FnMut(usize, &E) + 'a>;
+
+/// A simulation that remembers its last `capacity` snapshots, and counts its steps.
+pub struct History<'a, S: Simulation> {
+    simulation: S,
+    step: usize,
+    capacity: usize,
+    /// The snapshots before each of the most recent steps, the latest last
+    snapshots: VecDeque<S::Snapshot>,
+    on_event: Option<EventCallback<'a, S::Event>>,
+}
+
+impl<'a, S: Simulation> History<'a, S> {
+    pub fn new(simulation: S, capacity: usize) -> Self {
+        History {
+            simulation,
+            step: 0,
+            capacity,
+            snapshots: VecDeque::with_capacity(capacity),
+            on_event: None,
+        }
+    }
+
+    /// Calls `on_event` with the step number, counted from 1, and the event of every step.
+    pub fn on_event(mut self, on_event: impl FnMut(usize, &S::Event) + 'a) -> Self {
+        self.on_event = Some(Box::new(on_event));
+        self
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.simulation
+    }
+
+    pub fn into_simulation(self) -> S {
+        self.simulation
+    }
+
+    /// The number of steps taken so far, minus the rewound ones.
+    pub fn steps(&self) -> usize {
+        self.step
+    }
+
+    /// Like [`Simulation::step`], but remembers the state before the step.
+    pub fn step(&mut self) -> Option<S::Event> {
+        let snapshot = self.simulation.snapshot();
+        let event = self.simulation.step()?;
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        if self.capacity > 0 {
+            self.snapshots.push_back(snapshot);
+        }
+        self.step += 1;
+
+        if let Some(on_event) = self.on_event.as_mut() {
+            on_event(self.step, &event);
+        }
+        Some(event)
+    }
+
+    /// Like [`Simulation::run_until`], but remembers the states along the way.
+    pub fn run_until(&mut self, mut predicate: impl FnMut(&S) -> bool) -> Option<usize> {
+        let mut steps = 0;
+        while !predicate(&self.simulation) {
+            self.step()?;
+            steps += 1;
+        }
+        Some(steps)
+    }
+
+    /// Goes back up to `steps` steps, as far as the history reaches, and returns how many
+    /// steps it went back.
+    pub fn rewind(&mut self, steps: usize) -> usize {
+        let steps = steps.min(self.snapshots.len());
+        if steps == 0 {
+            return 0;
+        }
+
+        self.snapshots.truncate(self.snapshots.len() - steps + 1);
+        let snapshot = self
+            .snapshots
+            .pop_back()
+            .expect("at least one step to rewind");
+        self.simulation.restore(snapshot);
+        self.step -= steps;
+        steps
+    }
+
+    /// The snapshot of the simulation as it was after step `step`, if the history still
+    /// reaches back that far.
+    pub fn snapshot_at(&self, step: usize) -> Option<S::Snapshot> {
+        if step == self.step {
+            return Some(self.simulation.snapshot());
+        }
+        // The snapshot taken before step `step + 1`
+        let back = self.step.checked_sub(step)?;
+        let index = self.snapshots.len().checked_sub(back)?;
+        self.snapshots.get(index).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Counts up to a limit.
+    struct Counter {
+        value: u32,
+        limit: u32,
+    }
+
+    impl Simulation for Counter {
+        type Snapshot = u32;
+        type Event = u32;
+
+        fn step(&mut self) -> Option<u32> {
+            (self.value < self.limit).then(|| {
+                self.value += 1;
+                self.value
+            })
+        }
+
+        fn snapshot(&self) -> u32 {
+            self.value
+        }
+
+        fn restore(&mut self, snapshot: u32) {
+            self.value = snapshot;
+        }
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut counter = Counter { value: 0, limit: 5 };
+        assert_eq!(Some(3), counter.run_until(|counter| counter.value == 3));
+        assert_eq!(Some(0), counter.run_until(|counter| counter.value == 3));
+        assert_eq!(None, counter.run_until(|counter| counter.value == 9));
+        assert_eq!(5, counter.value);
+        assert_eq!(None, counter.step());
+    }
+
+    #[test]
+    fn test_history() {
+        let events = RefCell::new(Vec::new());
+        let mut history = History::new(
+            Counter {
+                value: 0,
+                limit: 10,
+            },
+            3,
+        )
+        .on_event(|step, event| events.borrow_mut().push((step, *event)));
+
+        assert_eq!(Some(6), history.run_until(|counter| counter.value == 6));
+        assert_eq!(6, history.steps());
+        assert_eq!(Some(4), history.snapshot_at(4));
+        assert_eq!(Some(6), history.snapshot_at(6));
+        assert_eq!(None, history.snapshot_at(2));
+
+        assert_eq!(2, history.rewind(2));
+        assert_eq!(4, history.simulation().value);
+        assert_eq!(4, history.steps());
+        assert_eq!(1, history.rewind(5));
+        assert_eq!(3, history.simulation().value);
+        assert_eq!(0, history.rewind(1));
+
+        assert_eq!(Some(4), history.step());
+        drop(history);
+        assert_eq!(
+            vec![(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (4, 4)],
+            events.into_inner()
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Reading an input piece by piece, for solvers whose memory must not grow with the input.
+//!
+//! The pieces are the ones [`normalize`](crate::input::normalize) would leave: no byte order
+//! mark, no line endings or trailing whitespace, and no blank lines at the end. Only the current
+//! line, or word, is held in memory.
+
+use std::io::{self, BufRead};
+
+const BOM: &str = "\u{feff}";
+
+/// Calls `f` with every line of `reader`, normalised. Blank lines are held back until the next
+/// line that is not blank, so trailing ones are dropped.
+pub fn for_each_line<E: From<io::Error>>(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut line = String::new();
+    let mut blank_lines = 0;
+    let mut first = true;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let mut trimmed = line.trim_end();
+        if first {
+            trimmed = trimmed.strip_prefix(BOM).unwrap_or(trimmed);
+            first = false;
+        }
+
+        if trimmed.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        for _ in 0..std::mem::take(&mut blank_lines) {
+            f("")?;
+        }
+        f(trimmed)?;
+    }
+}
+
+/// Calls `f` with every word of `reader`, split on ASCII whitespace. Lines are not held in
+/// memory, so this works for inputs that are a single line too long to read at once.
+pub fn for_each_word<E: From<io::Error>>(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut word = Vec::new();
+    let mut first = true;
+    let mut emit = |word: &mut Vec<u8>, first: &mut bool| -> Result<(), E> {
+        if word.is_empty() {
+            return Ok(());
+        }
+        let text = std::str::from_utf8(word)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let text = match std::mem::take(first) {
+            true => text.strip_prefix(BOM).unwrap_or(text),
+            false => text,
+        };
+        if !text.is_empty() {
+            f(text)?;
+        }
+        word.clear();
+        Ok(())
+    };
+
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return emit(&mut word, &mut first);
+        }
+        let length = buffer.len();
+        for &byte in buffer {
+            if byte.is_ascii_whitespace() {
+                emit(&mut word, &mut first)?;
+            } else {
+                word.push(byte);
+            }
+        }
+        reader.consume(length);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::normalize;
+
+    fn lines(input: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        for_each_line(input.as_bytes(), |line| -> io::Result<()> {
+            lines.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        lines
+    }
+
+    fn words(input: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        // A tiny buffer splits words across reads
+        let reader = io::BufReader::with_capacity(3, input.as_bytes());
+        for_each_word(reader, |word| -> io::Result<()> {
+            words.push(word.to_string());
+            Ok(())
+        })
+        .unwrap();
+        words
+    }
+
+    #[test]
+    fn test_for_each_line() {
+        for input in [
+            "1 2\n\n3 4\n",
+            "\u{feff}1 2\r\n \r\n3 4  \r\n\r\n\n",
+            "1 2\n3 4",
+            "  1\t",
+            "\n \n",
+            "",
+        ] {
+            let normalized = normalize(input);
+            assert_eq!(
+                normalized.lines().collect::<Vec<_>>(),
+                lines(input),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_for_each_word() {
+        assert_eq!(vec!["125", "17"], words("\u{feff}125 17\r\n"));
+        assert_eq!(vec!["12345", "6", "789"], words("  12345\t6\n\n789"));
+        assert!(words(" \n").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let result = for_each_line(&b"1\n\xff\n"[..], |_| -> io::Result<()> { Ok(()) });
+        assert_eq!(io::ErrorKind::InvalidData, result.unwrap_err().kind());
+        let result = for_each_word(&b"1 \xff"[..], |_| -> io::Result<()> { Ok(()) });
+        assert_eq!(io::ErrorKind::InvalidData, result.unwrap_err().kind());
+    }
+}
@@ -0,0 +1,159 @@
+//! The error every solver returns, telling apart the ways a solve can fail.
+//!
+//! Each kind of failure has a diagnostic code and an exit status that stay the same across
+//! releases, so scripts around the `aoc` binary can react to them:
+//!
+//! | Variant                        | Code                      | Exit status |
+//! |--------------------------------|---------------------------|------------:|
+//! | [`AocError::MalformedInput`]   | `aoc::malformed_input`    | 3           |
+//! | [`AocError::NoSolution`]       | `aoc::no_solution`        | 4           |
+//! | [`AocError::InvalidParameter`] | `aoc::invalid_parameter`  | 5           |
+//! | [`AocError::Timeout`]          | `aoc::timeout`            | 6           |
+//! | [`AocError::Internal`]         | `aoc::internal`           | 7           |
+//! | [`AocError::IoError`]          | `aoc::io_error`           | 8           |
+//!
+//! Any other failure exits with 1, and command line mistakes with 2.
+
+use aoc_common::cancel::Cancelled;
+use miette::{Diagnostic, Report};
+use thiserror::Error;
+
+/// The exit status of a failure that is not an [`AocError`].
+pub const OTHER_FAILURE: u8 = 1;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    /// The input does not have the shape the puzzle describes
+    #[error("{0}")]
+    #[diagnostic(
+        code(aoc::malformed_input),
+        help("check that this is the day's puzzle input, complete and unedited")
+    )]
+    MalformedInput(String),
+
+    /// The input is well-formed, but has no answer
+    #[error("{0}")]
+    #[diagnostic(
+        code(aoc::no_solution),
+        help("the puzzle has no answer for this input, check the puzzle parameters")
+    )]
+    NoSolution(String),
+
+    /// A puzzle parameter is out of the range the solver works with
+    #[error("{0}")]
+    #[diagnostic(
+        code(aoc::invalid_parameter),
+        help("pass a different value for the parameter, see `aoc solve --help`")
+    )]
+    InvalidParameter(String),
+
+    #[error(transparent)]
+    #[diagnostic(
+        code(aoc::timeout),
+        help("the solver gave up before finishing, raise the time budget or check the input")
+    )]
+    Timeout(#[from] Cancelled),
+
+    /// Something the solver relies on did not hold, which is a bug in the solver
+    #[error("{0}")]
+    #[diagnostic(
+        code(aoc::internal),
+        help("this is a bug in the solver, please report it with the input that triggered it")
+    )]
+    Internal(String),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+}
+
+impl AocError {
+    pub fn malformed(message: impl Into<String>) -> Self {
+        AocError::MalformedInput(message.into())
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        AocError::NoSolution(message.into())
+    }
+
+    pub fn invalid_parameter(message: impl Into<String>) -> Self {
+        AocError::InvalidParameter(message.into())
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        AocError::Internal(message.into())
+    }
+
+    /// The process exit status for this kind of failure.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            AocError::MalformedInput(_) => 3,
+            AocError::NoSolution(_) => 4,
+            AocError::InvalidParameter(_) => 5,
+            AocError::Timeout(_) => 6,
+            AocError::Internal(_) => 7,
+            AocError::IoError(_) => 8,
+        }
+    }
+}
+
+/// The exit status for a failed run, from the first [`AocError`] among the report's causes, or
+/// [`OTHER_FAILURE`] if there is none.
+pub fn exit_code(report: &Report) -> u8 {
+    report
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<AocError>())
+        .map_or(OTHER_FAILURE, AocError::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::WrapErr;
+
+    #[test]
+    fn test_codes() {
+        let errors = [
+            AocError::malformed("Rows differ in length"),
+            AocError::no_solution("No path found"),
+            AocError::invalid_parameter("The width must not be 0"),
+            AocError::from(Cancelled),
+            AocError::internal("The robot left the warehouse"),
+            AocError::from(std::io::Error::other("Disk on fire")),
+        ];
+        let codes = errors
+            .iter()
+            .map(|error| (error.code().unwrap().to_string(), error.exit_code()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                ("aoc::malformed_input".to_string(), 3),
+                ("aoc::no_solution".to_string(), 4),
+                ("aoc::invalid_parameter".to_string(), 5),
+                ("aoc::timeout".to_string(), 6),
+                ("aoc::internal".to_string(), 7),
+                ("aoc::io_error".to_string(), 8),
+            ],
+            codes
+        );
+        assert_eq!("No path found", errors[1].to_string());
+        assert!(errors.iter().take(5).all(|error| error.help().is_some()));
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let report = Report::new(AocError::no_solution("No path found"));
+        assert_eq!(4, exit_code(&report));
+
+        let wrapped = Err::<(), _>(AocError::malformed("Empty map"))
+            .wrap_err("part 1")
+            .unwrap_err();
+        assert_eq!(3, exit_code(&wrapped));
+
+        assert_eq!(
+            OTHER_FAILURE,
+            exit_code(&miette::miette!("no solution for day 26"))
+        );
+    }
+}
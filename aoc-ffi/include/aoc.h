@@ -0,0 +1,78 @@
+#ifndef AOC_FFI_H
+#define AOC_FFI_H
+
+/* Generated by cbindgen from aoc-ffi/src/lib.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of `aoc_solve`.
+typedef enum AocStatus {
+  // The output holds the answer
+  AOC_STATUS_OK = 0,
+  // There is no solution for the requested day and part
+  AOC_STATUS_UNKNOWN_SOLUTION = 1,
+  // The input buffer is not valid UTF-8
+  AOC_STATUS_INVALID_INPUT = 2,
+  // The parameters are not a valid JSON object of known parameters with valid values, or the
+  // solver rejected one of them
+  AOC_STATUS_INVALID_PARAMS = 3,
+  // The solver returned an error, the output holds its message
+  AOC_STATUS_FAILED = 4,
+  // The solver panicked
+  AOC_STATUS_PANICKED = 5,
+  // A required pointer argument was null
+  AOC_STATUS_NULL_POINTER = 6,
+} AocStatus;
+
+// A day and part for which a solution is available.
+typedef struct AocSolutionId {
+  uint8_t day;
+  uint8_t part;
+} AocSolutionId;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns all available solutions and writes their number to `len`.
+//
+// The returned array is owned by the library and lives until the process exits.
+//
+// # Safety
+//
+// `len` must be null or a valid pointer.
+const struct AocSolutionId *aoc_solutions(size_t *len);
+
+// Solves `day` and `part` for the `input_len` bytes at `input`.
+//
+// `params_json` is an optional NUL-terminated JSON object such as `{"height": 7, "width": 11}`
+// and may be null. On return `*output` holds either the answer or an error message, which
+// must be released with `aoc_string_free`. It is set to null if there is nothing to report.
+//
+// # Safety
+//
+// `input` must point to `input_len` readable bytes, `params_json` must be null or a valid
+// C string and `output` must be a valid pointer.
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         const char *params_json,
+                         char **output);
+
+// Releases a string returned by `aoc_solve`. Passing null is a no-op.
+//
+// # Safety
+//
+// `string` must be null or a pointer obtained from `aoc_solve` that was not freed yet.
+void aoc_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_FFI_H */
@@ -0,0 +1,262 @@
+//! C interface to the solution registry.
+//!
+//! The header `include/aoc.h` is generated from this file by cbindgen on every build.
+
+use aoc::cancel::CancelToken;
+use aoc::config::Config;
+use aoc::params::Params;
+use aoc::registry;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::sync::OnceLock;
+
+/// Outcome of `aoc_solve`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    /// The output holds the answer
+    Ok = 0,
+    /// There is no solution for the requested day and part
+    UnknownSolution = 1,
+    /// The input buffer is not valid UTF-8
+    InvalidInput = 2,
+    /// The parameters are not a valid JSON object of known parameters with valid values, or the
+    /// solver rejected one of them
+    InvalidParams = 3,
+    /// The solver returned an error, the output holds its message
+    Failed = 4,
+    /// The solver panicked
+    Panicked = 5,
+    /// A required pointer argument was null
+    NullPointer = 6,
+}
+
+/// A day and part for which a solution is available.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AocSolutionId {
+    pub day: u8,
+    pub part: u8,
+}
+
+/// Returns all available solutions and writes their number to `len`.
+///
+/// The returned array is owned by the library and lives until the process exits.
+///
+/// # Safety
+///
+/// `len` must be null or a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solutions(len: *mut usize) -> *const AocSolutionId {
+    static IDS: OnceLock<Vec<AocSolutionId>> = OnceLock::new();
+
+    let ids = IDS.get_or_init(|| {
+        registry::solutions()
+            .iter()
+            .map(|solution| AocSolutionId {
+                day: solution.day,
+                part: solution.part,
+            })
+            .collect()
+    });
+
+    if !len.is_null() {
+        *len = ids.len();
+    }
+    ids.as_ptr()
+}
+
+/// Solves `day` and `part` for the `input_len` bytes at `input`.
+///
+/// `params_json` is an optional NUL-terminated JSON object such as `{"height": 7, "width": 11}`
+/// and may be null. On return `*output` holds either the answer or an error message, which
+/// must be released with `aoc_string_free`. It is set to null if there is nothing to report.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, `params_json` must be null or a valid
+/// C string and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    params_json: *const c_char,
+    output: *mut *mut c_char,
+) -> AocStatus {
+    if output.is_null() {
+        return AocStatus::NullPointer;
+    }
+    *output = ptr::null_mut();
+
+    if input.is_null() && input_len > 0 {
+        return AocStatus::NullPointer;
+    }
+    let input = if input_len == 0 {
+        &[][..]
+    } else {
+        std::slice::from_raw_parts(input, input_len)
+    };
+
+    let (status, message) = solve(day, part, input, params_json);
+    if let Some(message) = message {
+        *output = into_c_string(message);
+    }
+    status
+}
+
+/// Releases a string returned by `aoc_solve`. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `string` must be null or a pointer obtained from `aoc_solve` that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+unsafe fn solve(
+    day: u8,
+    part: u8,
+    input: &[u8],
+    params_json: *const c_char,
+) -> (AocStatus, Option<String>) {
+    let Some(solution) = registry::find(day, part) else {
+        return (
+            AocStatus::UnknownSolution,
+            Some(format!("No solution for day {day} part {part}")),
+        );
+    };
+
+    let input = match std::str::from_utf8(input) {
+        Ok(input) => input,
+        Err(err) => return (AocStatus::InvalidInput, Some(err.to_string())),
+    };
+
+    let params = if params_json.is_null() {
+        Ok(Params::default())
+    } else {
+        CStr::from_ptr(params_json)
+            .to_str()
+            .map_err(|err| err.to_string())
+            .and_then(|json| serde_json::from_str::<Params>(json).map_err(|err| err.to_string()))
+    };
+    let config = params.and_then(|params| {
+        params
+            .resolve(&Config::default())
+            .map_err(|err| err.to_string())
+    });
+    let config = match config {
+        Ok(config) => config,
+        Err(err) => return (AocStatus::InvalidParams, Some(err)),
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| {
+        solution.solve_cancellable(input, &config, &CancelToken::new())
+    })) {
+        Ok(Ok(answer)) => (AocStatus::Ok, Some(answer)),
+        Ok(Err(report)) => {
+            let status = match report.code().map(|code| code.to_string()).as_deref() {
+                Some("aoc::invalid_parameter") => AocStatus::InvalidParams,
+                _ => AocStatus::Failed,
+            };
+            (status, Some(format!("{report}")))
+        }
+        Err(_) => (AocStatus::Panicked, Some("Solver panicked".to_string())),
+    }
+}
+
+fn into_c_string(message: String) -> *mut c_char {
+    // Interior NUL bytes cannot be represented, so they are dropped instead of losing the text.
+    let message = message.replace('\0', "");
+    CString::new(message)
+        .expect("should not contain NUL bytes")
+        .into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(day: u8, part: u8, input: &str, params: Option<&CStr>) -> (AocStatus, Option<String>) {
+        let mut output = ptr::null_mut();
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                params.map_or(ptr::null(), |params| params.as_ptr()),
+                &mut output,
+            )
+        };
+        let message = (!output.is_null()).then(|| unsafe {
+            let message = CStr::from_ptr(output).to_string_lossy().into_owned();
+            aoc_string_free(output);
+            message
+        });
+        (status, message)
+    }
+
+    #[test]
+    fn test_solutions() {
+        let mut len = 0;
+        let ids = unsafe { std::slice::from_raw_parts(aoc_solutions(&mut len), len) };
+
+        assert_eq!(registry::solutions().len(), len);
+        assert!(ids.contains(&AocSolutionId { day: 25, part: 1 }));
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+        assert_eq!(
+            (AocStatus::Ok, Some("31".to_string())),
+            call(1, 2, input, None)
+        );
+    }
+
+    #[test]
+    fn test_solve_with_params() {
+        let input = "029A
+980A
+179A
+456A
+379A
+";
+        assert_eq!(
+            (AocStatus::Ok, Some("126384".to_string())),
+            call(21, 2, input, Some(c"{\"levels\": 3}"))
+        );
+        assert_eq!(
+            AocStatus::InvalidParams,
+            call(21, 2, input, Some(c"{\"robots\": 3}")).0
+        );
+        assert_eq!(
+            AocStatus::InvalidParams,
+            call(14, 1, "", Some(c"{\"height\": 0}")).0
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(AocStatus::UnknownSolution, call(25, 2, "", None).0);
+        assert_eq!(
+            (AocStatus::Failed, Some("No starting position".to_string())),
+            call(16, 1, "#####\n#...#\n#####", None)
+        );
+        assert_eq!(AocStatus::NullPointer, unsafe {
+            aoc_solve(1, 1, ptr::null(), 0, ptr::null(), ptr::null_mut())
+        });
+    }
+}
@@ -0,0 +1,54 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int expect_answer(uint8_t day, uint8_t part, const char *input, const char *params,
+                         const char *expected) {
+    char *output = NULL;
+    AocStatus status =
+        aoc_solve(day, part, (const uint8_t *)input, strlen(input), params, &output);
+
+    int failed = status != AOC_STATUS_OK || output == NULL || strcmp(output, expected) != 0;
+    if (failed) {
+        fprintf(stderr, "day %d part %d: status %d, output %s, expected %s\n", day, part,
+                (int)status, output ? output : "(null)", expected);
+    }
+    aoc_string_free(output);
+    return failed;
+}
+
+int main(void) {
+    int failures = 0;
+
+    size_t len = 0;
+    const AocSolutionId *solutions = aoc_solutions(&len);
+    if (len == 0 || solutions[0].day != 1 || solutions[0].part != 1) {
+        fprintf(stderr, "unexpected solution list of length %zu\n", len);
+        failures++;
+    }
+
+    failures += expect_answer(1, 1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", NULL, "11");
+    failures += expect_answer(18, 1,
+                              "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n"
+                              "1,2\n5,5\n2,5\n6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0",
+                              "{\"size\": 7, \"num_bytes\": 12}", "22");
+
+    char *output = NULL;
+    AocStatus status = aoc_solve(14, 1, (const uint8_t *)"", 0, "{\"height\": 0}", &output);
+    if (status != AOC_STATUS_INVALID_PARAMS || output == NULL) {
+        fprintf(stderr, "expected invalid params, got status %d\n", (int)status);
+        failures++;
+    }
+    aoc_string_free(output);
+
+    output = NULL;
+    status = aoc_solve(25, 2, (const uint8_t *)"", 0, NULL, &output);
+    if (status != AOC_STATUS_UNKNOWN_SOLUTION || output == NULL) {
+        fprintf(stderr, "expected unknown solution, got status %d\n", (int)status);
+        failures++;
+    }
+    aoc_string_free(output);
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,52 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const GENERATED_HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/aoc.h"));
+
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Directory holding the `cdylib` built alongside this test binary.
+fn library_dir() -> PathBuf {
+    env::current_exe().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_header_up_to_date() {
+    let committed = std::fs::read_to_string(manifest_dir().join("include/aoc.h")).unwrap();
+    assert!(
+        committed == GENERATED_HEADER,
+        "include/aoc.h is stale, copy the header generated into OUT_DIR over it"
+    );
+}
+
+#[test]
+fn test_c_program() {
+    let out_dir = PathBuf::from(env!("OUT_DIR"));
+    let executable = out_dir.join("smoke");
+    let library_dir = library_dir();
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(compiler)
+        .arg(manifest_dir().join("tests/c/smoke.c"))
+        .arg("-I")
+        .arg(manifest_dir().join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-laoc_ffi")
+        .arg("-o")
+        .arg(&executable)
+        .status()
+        .expect("should be able to run the C compiler");
+    assert!(status.success(), "compiling the C test program failed");
+
+    let output = Command::new(&executable).output().unwrap();
+    assert!(
+        output.status.success(),
+        "C test program failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,235 @@
+//! Divisibility and congruences.
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+///
+/// Returned as `u128`, as `gcd(i128::MIN, 0)` does not fit an `i128`.
+pub fn gcd(a: i128, b: i128) -> u128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, which is never negative, or `None` if it does not fit. The least
+/// common multiple with 0 is 0.
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    let gcd = i128::try_from(gcd(a, b)).ok()?;
+    (a / gcd).checked_mul(b)?.checked_abs()
+}
+
+/// Bézout coefficients: `a * x + b * y == gcd`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtendedGcd {
+    /// Never negative
+    pub gcd: i128,
+    pub x: i128,
+    pub y: i128,
+}
+
+/// The greatest common divisor of `a` and `b` together with its Bézout coefficients, or
+/// `None` if they do not fit.
+pub fn extended_gcd(a: i128, b: i128) -> Option<ExtendedGcd> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let quotient = old_r.checked_div(r)?;
+        (old_r, r) = (r, old_r.checked_sub(quotient.checked_mul(r)?)?);
+        (old_x, x) = (x, old_x.checked_sub(quotient.checked_mul(x)?)?);
+        (old_y, y) = (y, old_y.checked_sub(quotient.checked_mul(y)?)?);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (
+            old_r.checked_neg()?,
+            old_x.checked_neg()?,
+            old_y.checked_neg()?,
+        );
+    }
+    Some(ExtendedGcd {
+        gcd: old_r,
+        x: old_x,
+        y: old_y,
+    })
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, or `None` if `a` has no inverse,
+/// which it only has for a positive modulus it shares no factor with.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+    let bezout = extended_gcd(a.rem_euclid(modulus), modulus)?;
+    (bezout.gcd == 1).then(|| bezout.x.rem_euclid(modulus))
+}
+
+/// The numbers `x` with `x ≡ residue (mod modulus)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Congruence {
+    pub residue: i128,
+    pub modulus: i128,
+}
+
+impl Congruence {
+    /// The congruence with its residue brought into `0..modulus`, for a positive modulus.
+    pub fn new(residue: i128, modulus: i128) -> Option<Self> {
+        (modulus > 0).then(|| Congruence {
+            residue: residue.rem_euclid(modulus),
+            modulus,
+        })
+    }
+
+    /// Whether `x` satisfies the congruence.
+    pub fn contains(&self, x: i128) -> bool {
+        x.rem_euclid(self.modulus) == self.residue
+    }
+}
+
+/// Combines congruences with the Chinese remainder theorem into the one that holds for exactly
+/// the numbers satisfying all of them. The moduli need not be coprime. Returns `None` if the
+/// congruences contradict each other, a modulus is not positive, or the combined modulus does
+/// not fit. No congruences at all combine to `x ≡ 0 (mod 1)`.
+pub fn crt(congruences: impl IntoIterator<Item = Congruence>) -> Option<Congruence> {
+    congruences
+        .into_iter()
+        .try_fold(Congruence::new(0, 1)?, |combined, congruence| {
+            let congruence = Congruence::new(congruence.residue, congruence.modulus)?;
+
+            // combined.residue + combined.modulus * k ≡ congruence.residue (mod congruence.modulus)
+            let bezout = extended_gcd(combined.modulus, congruence.modulus)?;
+            let difference = congruence.residue.checked_sub(combined.residue)?;
+            if difference % bezout.gcd != 0 {
+                return None;
+            }
+            let step = congruence.modulus / bezout.gcd;
+            let k = mul_mod(difference / bezout.gcd, bezout.x, step)?;
+
+            let modulus = combined.modulus.checked_mul(step)?;
+            let residue = combined
+                .residue
+                .checked_add(mul_mod(combined.modulus, k, modulus)?)?;
+            Congruence::new(residue, modulus)
+        })
+}
+
+/// `a * b` modulo a positive `modulus`, in `0..modulus`.
+fn mul_mod(a: i128, b: i128, modulus: i128) -> Option<i128> {
+    let (a, b) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+    match a.checked_mul(b) {
+        Some(product) => Some(product % modulus),
+        // Double and add, which only needs room for twice the modulus
+        None => {
+            let (mut result, mut a, mut b) = (0i128, a, b);
+            while b > 0 {
+                if b & 1 == 1 {
+                    result = result.checked_add(a)? % modulus;
+                }
+                a = a.checked_add(a)? % modulus;
+                b >>= 1;
+            }
+            Some(result)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(-12, 18));
+        assert_eq!(0, gcd(0, 0));
+        assert_eq!(1 << 127, gcd(i128::MIN, 0));
+        assert_eq!(Some(36), lcm(-12, 18));
+        assert_eq!(None, lcm(i128::MAX, i128::MAX - 1));
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(None, mod_inverse(4, 12));
+    }
+
+    #[test]
+    fn test_crt() {
+        let crt_of = |congruences: &[(i128, i128)]| {
+            crt(congruences
+                .iter()
+                .map(|&(residue, modulus)| Congruence { residue, modulus }))
+        };
+        assert_eq!(Congruence::new(23, 105), crt_of(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Congruence::new(10, 12), crt_of(&[(4, 6), (2, 4)]));
+        assert_eq!(None, crt_of(&[(1, 6), (2, 4)]));
+        assert_eq!(Congruence::new(0, 1), crt_of(&[]));
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_divides_both(a in any::<i64>(), b in any::<i64>()) {
+            let (a, b) = (a as i128, b as i128);
+            let bezout = extended_gcd(a, b).unwrap();
+            prop_assert_eq!(gcd(a, b), bezout.gcd as u128);
+            prop_assert_eq!(bezout.gcd, a * bezout.x + b * bezout.y);
+            if bezout.gcd != 0 {
+                prop_assert_eq!(0, a % bezout.gcd);
+                prop_assert_eq!(0, b % bezout.gcd);
+            }
+        }
+
+        #[test]
+        fn lcm_is_a_common_multiple(a in 1..1_000_000i128, b in 1..1_000_000i128) {
+            let lcm = lcm(a, b).unwrap();
+            prop_assert_eq!(0, lcm % a);
+            prop_assert_eq!(0, lcm % b);
+            prop_assert_eq!(a * b, lcm * gcd(a, b) as i128);
+        }
+
+        #[test]
+        fn mod_inverse_inverts(a in any::<i64>(), modulus in 1..i64::MAX) {
+            let (a, modulus) = (a as i128, modulus as i128);
+            match mod_inverse(a, modulus) {
+                Some(inverse) => {
+                    prop_assert!((0..modulus).contains(&inverse));
+                    prop_assert_eq!(1 % modulus, (a * inverse).rem_euclid(modulus));
+                }
+                None => prop_assert_ne!(1, gcd(a, modulus)),
+            }
+        }
+
+        #[test]
+        fn crt_solves_every_congruence(
+            x in 0..1_000_000_000i128,
+            moduli in prop::collection::vec(1..1000i128, 0..5),
+        ) {
+            let congruences = moduli
+                .iter()
+                .map(|&modulus| Congruence::new(x, modulus).unwrap())
+                .collect::<Vec<_>>();
+            let combined = crt(congruences.iter().copied()).unwrap();
+
+            let lcm = moduli.iter().try_fold(1, |lcm_so_far, &modulus| lcm(lcm_so_far, modulus));
+            prop_assert_eq!(lcm, Some(combined.modulus));
+            prop_assert!(combined.contains(x));
+            for congruence in congruences {
+                prop_assert!(congruence.contains(combined.residue));
+            }
+        }
+
+        #[test]
+        fn crt_detects_contradictions(
+            residues in (0..100i128, 0..100i128),
+            moduli in (1..100i128, 1..100i128),
+        ) {
+            let combined = crt([
+                Congruence::new(residues.0, moduli.0).unwrap(),
+                Congruence::new(residues.1, moduli.1).unwrap(),
+            ]);
+            let exists = (0..moduli.0 * moduli.1)
+                .any(|x| x % moduli.0 == residues.0 % moduli.0 && x % moduli.1 == residues.1 % moduli.1);
+            prop_assert_eq!(exists, combined.is_some());
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Exact number theory for the daily solutions.
+//!
+//! Everything works on `i128` and checks for overflow, returning `None` where the result
+//! does not fit instead of wrapping or panicking.
+
+pub mod integer;
+pub mod linear;
+pub mod rational;
+
+pub use integer::{crt, extended_gcd, gcd, lcm, mod_inverse, Congruence, ExtendedGcd};
+pub use linear::{solve_2x2, solve_linear, LinearSolutions};
+pub use rational::Rational;
@@ -0,0 +1,174 @@
+//! Linear equations over the rationals and the integers.
+
+use crate::integer::extended_gcd;
+use crate::rational::Rational;
+use std::ops::RangeInclusive;
+
+/// The exact solution `[x, y]` of
+///
+/// ```text
+/// matrix[0][0] * x + matrix[0][1] * y == rhs[0]
+/// matrix[1][0] * x + matrix[1][1] * y == rhs[1]
+/// ```
+///
+/// by Cramer's rule, or `None` if the matrix is singular or the arithmetic overflows.
+pub fn solve_2x2(matrix: [[i128; 2]; 2], rhs: [i128; 2]) -> Option<[Rational; 2]> {
+    let [[a, b], [c, d]] = matrix;
+    let cross =
+        |p: i128, q: i128, r: i128, s: i128| p.checked_mul(q)?.checked_sub(r.checked_mul(s)?);
+
+    let determinant = cross(a, d, b, c)?;
+    if determinant == 0 {
+        return None;
+    }
+    Some([
+        Rational::new(cross(rhs[0], d, b, rhs[1])?, determinant)?,
+        Rational::new(cross(a, rhs[1], rhs[0], c)?, determinant)?,
+    ])
+}
+
+/// Every integer solution of `a * x + b * y == c`, which is `(x + k * step_x, y - k * step_y)`
+/// for any integer `k`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinearSolutions {
+    pub x: i128,
+    pub y: i128,
+    pub step_x: i128,
+    pub step_y: i128,
+}
+
+impl LinearSolutions {
+    /// The solution for `k`, or `None` if it does not fit.
+    pub fn solution(&self, k: i128) -> Option<(i128, i128)> {
+        Some((
+            self.x.checked_add(k.checked_mul(self.step_x)?)?,
+            self.y.checked_sub(k.checked_mul(self.step_y)?)?,
+        ))
+    }
+
+    /// The `k` of the solutions with neither `x` nor `y` negative, which may be none. Only
+    /// equations with positive `a` and `b` have finitely many such solutions, so any other
+    /// gives `None`.
+    pub fn non_negative(&self) -> Option<RangeInclusive<i128>> {
+        if self.step_x <= 0 || self.step_y <= 0 {
+            return None;
+        }
+        let lowest = self.x.div_euclid(self.step_x).checked_neg()?;
+        let highest = self.y.div_euclid(self.step_y);
+        Some(lowest..=highest)
+    }
+
+    /// The solutions with neither `x` nor `y` negative, by increasing `x`. Empty unless `a` and
+    /// `b` are positive.
+    pub fn non_negative_solutions(&self) -> impl Iterator<Item = (i128, i128)> + '_ {
+        self.non_negative()
+            .into_iter()
+            .flatten()
+            .filter_map(|k| self.solution(k))
+    }
+}
+
+/// The integer solutions of the linear Diophantine equation `a * x + b * y == c`, or `None` if
+/// it has none, if `a` and `b` are both 0, or if the arithmetic overflows.
+pub fn solve_linear(a: i128, b: i128, c: i128) -> Option<LinearSolutions> {
+    if a == 0 && b == 0 {
+        return None;
+    }
+    let bezout = extended_gcd(a, b)?;
+    if c % bezout.gcd != 0 {
+        return None;
+    }
+    let scale = c / bezout.gcd;
+
+    Some(LinearSolutions {
+        x: bezout.x.checked_mul(scale)?,
+        y: bezout.y.checked_mul(scale)?,
+        step_x: b / bezout.gcd,
+        step_y: a / bezout.gcd,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integer::gcd;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_solve_2x2() {
+        assert_eq!(
+            Some([Rational::from(80), Rational::from(40)]),
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400])
+        );
+        assert_eq!(
+            Some([Rational::new(1, 2).unwrap(), Rational::ZERO]),
+            solve_2x2([[2, 0], [0, 1]], [1, 0])
+        );
+        assert_eq!(None, solve_2x2([[1, 2], [2, 4]], [3, 6]));
+    }
+
+    #[test]
+    fn test_solve_linear() {
+        let solutions = solve_linear(3, 5, 22).unwrap();
+        assert_eq!(
+            vec![(4, 2)],
+            solutions.non_negative_solutions().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 6), (5, 3), (10, 0)],
+            solve_linear(3, 5, 30)
+                .unwrap()
+                .non_negative_solutions()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, solve_linear(4, 6, 3));
+        assert_eq!(None, solve_linear(0, 0, 0));
+        assert_eq!(None, solve_linear(-3, 5, 1).unwrap().non_negative());
+    }
+
+    proptest! {
+        #[test]
+        fn solve_2x2_satisfies_both_rows(
+            matrix in prop::array::uniform2(prop::array::uniform2(-1000..1000i128)),
+            rhs in prop::array::uniform2(-1_000_000..1_000_000i128),
+        ) {
+            let determinant = matrix[0][0] * matrix[1][1] - matrix[0][1] * matrix[1][0];
+            match solve_2x2(matrix, rhs) {
+                None => prop_assert_eq!(0, determinant),
+                Some([x, y]) => {
+                    for (row, rhs) in matrix.iter().zip(rhs) {
+                        let lhs = Rational::from(row[0])
+                            .checked_mul(x)
+                            .unwrap()
+                            .checked_add(Rational::from(row[1]).checked_mul(y).unwrap());
+                        prop_assert_eq!(Some(Rational::from(rhs)), lhs);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn solve_linear_finds_every_solution(
+            a in 1..50i128,
+            b in 1..50i128,
+            c in 0..2000i128,
+        ) {
+            let expected = (0..=c / a)
+                .filter_map(|x| ((c - a * x) % b == 0).then_some((x, (c - a * x) / b)))
+                .collect::<Vec<_>>();
+            match solve_linear(a, b, c) {
+                None => {
+                    prop_assert!(expected.is_empty());
+                    prop_assert_ne!(0, c % gcd(a, b) as i128);
+                }
+                Some(solutions) => {
+                    for k in -5..5 {
+                        let (x, y) = solutions.solution(k).unwrap();
+                        prop_assert_eq!(c, a * x + b * y);
+                    }
+                    prop_assert_eq!(expected, solutions.non_negative_solutions().collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Fractions that never round.
+
+use crate::integer::gcd;
+use std::fmt;
+
+/// A fraction in lowest terms with a positive denominator, so equal values compare equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    /// `numerator / denominator`, or `None` for a zero denominator or one that does not fit
+    /// once it is made positive.
+    pub fn new(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = i128::try_from(gcd(numerator, denominator)).ok()?;
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+
+        Some(if denominator < 0 {
+            Rational {
+                numerator: numerator.checked_neg()?,
+                denominator: denominator.checked_neg()?,
+            }
+        } else {
+            Rational {
+                numerator,
+                denominator,
+            }
+        })
+    }
+
+    pub fn from_integer(value: i128) -> Self {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    /// The value if it is a whole number.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        Rational::new(numerator, self.denominator.checked_mul(other.denominator)?)
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        // Cancelling crosswise first keeps the products small
+        let left = i128::try_from(gcd(self.numerator, other.denominator)).ok()?;
+        let right = i128::try_from(gcd(other.numerator, self.denominator)).ok()?;
+        Rational::new(
+            (self.numerator / left).checked_mul(other.numerator / right)?,
+            (self.denominator / right).checked_mul(other.denominator / left)?,
+        )
+    }
+
+    /// `None` when dividing by zero, too.
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        self.checked_mul(Rational::new(other.denominator, other.numerator)?)
+    }
+
+    pub fn checked_neg(self) -> Option<Rational> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational::from_integer(value)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            denominator => write!(f, "{}/{denominator}", self.numerator),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn rational() -> impl Strategy<Value = Rational> {
+        (
+            any::<i32>(),
+            any::<i32>().prop_filter("nonzero", |&d| d != 0),
+        )
+            .prop_map(|(n, d)| Rational::new(n as i128, d as i128).unwrap())
+    }
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(-3, -6).unwrap();
+        assert_eq!((1, 2), (half.numerator(), half.denominator()));
+        assert_eq!("-1/2", Rational::new(2, -4).unwrap().to_string());
+        assert_eq!("3", Rational::from(3).to_string());
+        assert_eq!(Some(3), Rational::new(6, 2).unwrap().to_integer());
+        assert_eq!(None, half.to_integer());
+        assert_eq!(None, Rational::new(1, 0));
+        assert_eq!(None, half.checked_div(Rational::ZERO));
+        assert_eq!(
+            None,
+            Rational::from(i128::MAX).checked_add(Rational::from(1))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn lowest_terms(n in any::<i64>(), d in any::<i64>(), factor in 1..1000i128) {
+            prop_assume!(d != 0);
+            let (n, d) = (n as i128, d as i128);
+            let value = Rational::new(n, d).unwrap();
+            prop_assert!(value.denominator() > 0);
+            prop_assert_eq!(1, gcd(value.numerator(), value.denominator()));
+            prop_assert_eq!(Some(value), Rational::new(n * factor, d * factor));
+            prop_assert_eq!(n * value.denominator(), d * value.numerator());
+        }
+
+        #[test]
+        fn field_laws(a in rational(), b in rational(), c in rational()) {
+            let sum = a.checked_add(b).unwrap();
+            prop_assert_eq!(Some(a), sum.checked_sub(b));
+            prop_assert_eq!(
+                a.checked_mul(b.checked_add(c).unwrap()),
+                a.checked_mul(b).unwrap().checked_add(a.checked_mul(c).unwrap())
+            );
+            if b != Rational::ZERO {
+                prop_assert_eq!(Some(a), a.checked_div(b).unwrap().checked_mul(b));
+            }
+        }
+    }
+}
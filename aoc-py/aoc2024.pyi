@@ -0,0 +1,20 @@
+from typing import Literal
+
+class AocError(Exception):
+    code: str | None
+    help: str | None
+
+class MalformedInputError(AocError): ...
+class NoSolutionError(AocError): ...
+class SolveTimeoutError(AocError): ...
+class InternalError(AocError): ...
+class IoError(AocError): ...
+class UnknownSolutionError(AocError): ...
+class InvalidParamsError(AocError): ...
+
+def solve(day: int, part: int, input: str, **params: int) -> str: ...
+def solutions() -> list[tuple[int, int]]: ...
+def lan_graph(input: str) -> dict[str, list[str] | list[tuple[str, str]]]: ...
+def gate_graph(
+    input: str,
+) -> dict[str, dict[str, bool] | dict[str, tuple[str, Literal["AND", "OR", "XOR"], str]]]: ...
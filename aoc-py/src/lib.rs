@@ -0,0 +1,183 @@
+//! Python bindings for the solution registry, built with maturin as the `aoc2024` module.
+
+use aoc::cancel::CancelToken;
+use aoc::config::Config;
+use aoc::params::Params;
+use aoc::registry;
+use miette::Report;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+create_exception!(
+    aoc2024,
+    AocError,
+    PyException,
+    "Base class of every error raised by a solver."
+);
+create_exception!(
+    aoc2024,
+    MalformedInputError,
+    AocError,
+    "Mirrors `AocError::MalformedInput`, the input does not have the shape the puzzle describes."
+);
+create_exception!(
+    aoc2024,
+    NoSolutionError,
+    AocError,
+    "Mirrors `AocError::NoSolution`, the input is well-formed but has no answer."
+);
+create_exception!(
+    aoc2024,
+    SolveTimeoutError,
+    AocError,
+    "Mirrors `AocError::Timeout`, the solver gave up before finishing."
+);
+create_exception!(
+    aoc2024,
+    InternalError,
+    AocError,
+    "Mirrors `AocError::Internal`, a bug in the solver."
+);
+create_exception!(
+    aoc2024,
+    IoError,
+    AocError,
+    "Mirrors `AocError::IoError`, the input could not be read."
+);
+create_exception!(
+    aoc2024,
+    UnknownSolutionError,
+    AocError,
+    "There is no solution for the requested day and part."
+);
+create_exception!(
+    aoc2024,
+    InvalidParamsError,
+    AocError,
+    "A keyword parameter is unknown, has the wrong type or an invalid value."
+);
+
+/// Converts a solver diagnostic into the matching exception, keeping its code and help text.
+fn aoc_error(py: Python<'_>, report: &Report) -> PyErr {
+    let code = report.code().map(|code| code.to_string());
+    let help = report.help().map(|help| help.to_string());
+
+    let err = match code.as_deref() {
+        Some("aoc::malformed_input") => MalformedInputError::new_err(report.to_string()),
+        Some("aoc::no_solution") => NoSolutionError::new_err(report.to_string()),
+        Some("aoc::invalid_parameter") => InvalidParamsError::new_err(report.to_string()),
+        Some("aoc::timeout") => SolveTimeoutError::new_err(report.to_string()),
+        Some("aoc::internal") => InternalError::new_err(report.to_string()),
+        Some("aoc::io_error") => IoError::new_err(report.to_string()),
+        _ => AocError::new_err(report.to_string()),
+    };
+
+    let value = err.value(py);
+    if value.setattr("code", code).is_err() || value.setattr("help", help).is_err() {
+        return AocError::new_err(report.to_string());
+    }
+    err
+}
+
+/// Solves `day` and `part` for `input`. Puzzle parameters such as `height` and `width` for
+/// day 14 are passed as keyword arguments.
+#[pyfunction]
+#[pyo3(signature = (day, part, input, **params))]
+fn solve(
+    py: Python<'_>,
+    day: u8,
+    part: u8,
+    input: &str,
+    params: Option<&Bound<'_, PyDict>>,
+) -> PyResult<String> {
+    let solution = registry::find(day, part).ok_or_else(|| {
+        UnknownSolutionError::new_err(format!("No solution for day {day} part {part}"))
+    })?;
+
+    let params: Params = match params {
+        Some(params) => pythonize::depythonize(params.as_any())
+            .map_err(|err| InvalidParamsError::new_err(err.to_string()))?,
+        None => Params::default(),
+    };
+    let config = params
+        .resolve(&Config::default())
+        .map_err(|err| aoc_error(py, &Report::new(err)))?;
+
+    py.allow_threads(|| solution.solve_cancellable(input, &config, &CancelToken::new()))
+        .map_err(|report| aoc_error(py, &report))
+}
+
+/// Lists the available `(day, part)` pairs.
+#[pyfunction]
+fn solutions() -> Vec<(u8, u8)> {
+    registry::solutions()
+        .iter()
+        .map(|solution| (solution.day, solution.part))
+        .collect()
+}
+
+/// Parses a day 23 network map into `{"nodes": [...], "edges": [(a, b), ...]}`.
+#[pyfunction]
+fn lan_graph<'py>(py: Python<'py>, input: &str) -> PyResult<Bound<'py, PyDict>> {
+    let network = day_23::model::parse(input).map_err(|err| aoc_error(py, &err.into()))?;
+    let graph = network.to_graph();
+
+    let nodes = graph.node_weights().copied().collect::<Vec<_>>();
+    let edges = graph
+        .raw_edges()
+        .iter()
+        .map(|edge| (graph[edge.source()], graph[edge.target()]))
+        .collect::<Vec<_>>();
+
+    let dict = PyDict::new(py);
+    dict.set_item("nodes", nodes)?;
+    dict.set_item("edges", edges)?;
+    Ok(dict)
+}
+
+/// Parses a day 24 gate configuration into
+/// `{"inputs": {wire: bool}, "gates": {output: (input1, "AND" | "OR" | "XOR", input2)}}`.
+#[pyfunction]
+fn gate_graph<'py>(py: Python<'py>, input: &str) -> PyResult<Bound<'py, PyDict>> {
+    let device = day_24::model::parse(input).map_err(|err| aoc_error(py, &err.into()))?;
+    let configuration = day_24::circuit::GateConfiguration::from(&device);
+
+    let gates = PyDict::new(py);
+    for (output, ((input1, input2), gate)) in configuration.gates.iter() {
+        let gate = match gate {
+            day_24::model::GateType::And => "AND",
+            day_24::model::GateType::Or => "OR",
+            day_24::model::GateType::Xor => "XOR",
+        };
+        gates.set_item(output, (input1, gate, input2))?;
+    }
+
+    let dict = PyDict::new(py);
+    dict.set_item("inputs", configuration.input)?;
+    dict.set_item("gates", gates)?;
+    Ok(dict)
+}
+
+#[pymodule]
+fn aoc2024(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(solutions, m)?)?;
+    m.add_function(wrap_pyfunction!(lan_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(gate_graph, m)?)?;
+
+    let py = m.py();
+    m.add("AocError", py.get_type::<AocError>())?;
+    m.add("MalformedInputError", py.get_type::<MalformedInputError>())?;
+    m.add("NoSolutionError", py.get_type::<NoSolutionError>())?;
+    m.add("SolveTimeoutError", py.get_type::<SolveTimeoutError>())?;
+    m.add("InternalError", py.get_type::<InternalError>())?;
+    m.add("IoError", py.get_type::<IoError>())?;
+    m.add(
+        "UnknownSolutionError",
+        py.get_type::<UnknownSolutionError>(),
+    )?;
+    m.add("InvalidParamsError", py.get_type::<InvalidParamsError>())?;
+    Ok(())
+}
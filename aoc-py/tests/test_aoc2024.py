@@ -0,0 +1,113 @@
+import pytest
+
+import aoc2024
+
+ROBOTS = """p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3"""
+
+NETWORK = """kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn"""
+
+GATES = """x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02"""
+
+
+def test_solve():
+    assert aoc2024.solve(23, 2, NETWORK) == "co,de,ka,ta"
+
+
+def test_solve_with_params():
+    assert aoc2024.solve(14, 1, ROBOTS, height=7, width=11) == "12"
+
+
+def test_solutions():
+    solutions = aoc2024.solutions()
+    assert (1, 1) in solutions
+    assert (25, 2) not in solutions
+
+
+def test_unknown_solution():
+    with pytest.raises(aoc2024.UnknownSolutionError):
+        aoc2024.solve(25, 2, "")
+
+
+def test_invalid_params():
+    with pytest.raises(aoc2024.InvalidParamsError):
+        aoc2024.solve(14, 1, ROBOTS, robots=5)
+    with pytest.raises(aoc2024.InvalidParamsError) as excinfo:
+        aoc2024.solve(14, 1, ROBOTS, height=0)
+
+    assert excinfo.value.code == "aoc::invalid_parameter"
+
+
+def test_solver_error_mirrors_aoc_error():
+    with pytest.raises(aoc2024.MalformedInputError) as excinfo:
+        aoc2024.solve(16, 1, "#####\n#...#\n#####")
+
+    assert isinstance(excinfo.value, aoc2024.AocError)
+    assert excinfo.value.code == "aoc::malformed_input"
+    assert str(excinfo.value) == "No starting position"
+
+
+def test_lan_graph():
+    graph = aoc2024.lan_graph(NETWORK)
+
+    assert len(graph["nodes"]) == 16
+    assert len(graph["edges"]) == 32
+    assert ("kh", "tc") in graph["edges"]
+
+
+def test_gate_graph():
+    graph = aoc2024.gate_graph(GATES)
+
+    assert graph["inputs"]["x00"] is True
+    assert graph["inputs"]["y02"] is False
+    assert graph["gates"]["z01"] == ("x01", "XOR", "y01")
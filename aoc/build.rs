@@ -0,0 +1,100 @@
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Workspace crates the day solvers depend on, which count as part of every day's sources.
+const SHARED_CRATES: &[&str] = &["aoc-common", "aoc-error", "aoc-math"];
+
+/// Sources of this crate that decide how a day is solved: which function a part runs, and how
+/// the configuration and parameters become its arguments.
+const WIRING: &[&str] = &["src/registry.rs", "src/params.rs"];
+
+/// Collects every file below `dir`, sorted so the hash does not depend on directory order.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Hashes the sources a day's solver is built from, so cached answers are invalidated
+/// whenever one of them changes.
+fn source_hash(workspace: &Path, manifest_dir: &Path, day_dir: &Path) -> String {
+    let mut files = WIRING
+        .iter()
+        .map(|file| manifest_dir.join(file))
+        .collect::<Vec<_>>();
+    for dir in SHARED_CRATES
+        .iter()
+        .map(|name| workspace.join(name))
+        .chain([day_dir.to_path_buf()])
+    {
+        files.push(dir.join("Cargo.toml"));
+        collect_files(&dir.join("src"), &mut files);
+    }
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file.strip_prefix(workspace).unwrap_or(&file);
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update(fs::read(&file).unwrap_or_default());
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            write!(hex, "{byte:02x}").unwrap();
+            hex
+        })
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let workspace = manifest_dir.parent().unwrap();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    for file in WIRING {
+        println!(
+            "cargo:rerun-if-changed={}",
+            manifest_dir.join(file).display()
+        );
+    }
+    for name in SHARED_CRATES {
+        let dir = workspace.join(name);
+        println!("cargo:rerun-if-changed={}", dir.join("src").display());
+        println!(
+            "cargo:rerun-if-changed={}",
+            dir.join("Cargo.toml").display()
+        );
+    }
+
+    let mut generated = String::from("static SOURCE_HASHES: [&str; 25] = [\n");
+    for day in 1..=25 {
+        let day_dir = workspace.join(format!("day-{day:02}"));
+        println!("cargo:rerun-if-changed={}", day_dir.join("src").display());
+        println!(
+            "cargo:rerun-if-changed={}",
+            day_dir.join("Cargo.toml").display()
+        );
+        writeln!(
+            generated,
+            "    \"{}\",",
+            source_hash(workspace, &manifest_dir, &day_dir)
+        )
+        .unwrap();
+    }
+    generated.push_str("];\n");
+
+    fs::write(out_dir.join("source_hashes.rs"), generated).unwrap();
+}
@@ -0,0 +1,132 @@
+//! Allocation counting, so the runner can record allocation stats alongside each answer.
+//!
+//! Binaries opt in by installing [`CountingAllocator`] as their global allocator. Without it,
+//! [`measure`] reports nothing.
+//!
+//! Every thread counts its own allocations, so a solve is measured on its own even while other
+//! threads, such as the dashboard redrawing, allocate at the same time.
+
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// The allocations of one thread. Memory freed by another thread than the one that allocated
+/// it counts there, so the bytes live on a thread can drop below zero.
+struct Counters {
+    allocations: Cell<u64>,
+    allocated_bytes: Cell<u64>,
+    current_bytes: Cell<i64>,
+    peak_bytes: Cell<i64>,
+}
+
+thread_local! {
+    // Const-initialised without a destructor, so the allocator can use it at any time
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            allocated_bytes: Cell::new(0),
+            current_bytes: Cell::new(0),
+            peak_bytes: Cell::new(0),
+        }
+    };
+}
+
+/// The system allocator, counting every allocation it hands out.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let _ = COUNTERS.try_with(|counters| {
+            counters.allocations.set(counters.allocations.get() + 1);
+            counters
+                .allocated_bytes
+                .set(counters.allocated_bytes.get() + size as u64);
+            let current = counters.current_bytes.get() + size as i64;
+            counters.current_bytes.set(current);
+            counters
+                .peak_bytes
+                .set(counters.peak_bytes.get().max(current));
+        });
+    }
+
+    fn record_dealloc(size: usize) {
+        let _ = COUNTERS.try_with(|counters| {
+            counters
+                .current_bytes
+                .set(counters.current_bytes.get() - size as i64);
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// Number of allocations, counting every reallocation as one
+    pub allocations: u64,
+    /// Bytes allocated in total
+    pub bytes: u64,
+    /// Most bytes live at the same time, on top of what was live before
+    pub peak_bytes: u64,
+}
+
+/// Runs `f` and counts its allocations, or returns `None` as stats if [`CountingAllocator`] is
+/// not installed. Only the allocations of the calling thread are counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+
+    let (allocations, bytes, current) = COUNTERS.with(|counters| {
+        let current = counters.current_bytes.get();
+        counters.peak_bytes.set(current);
+        (
+            counters.allocations.get(),
+            counters.allocated_bytes.get(),
+            current,
+        )
+    });
+
+    let result = f();
+
+    let stats = COUNTERS.with(|counters| AllocStats {
+        allocations: counters.allocations.get() - allocations,
+        bytes: counters.allocated_bytes.get() - bytes,
+        peak_bytes: (counters.peak_bytes.get() - current).max(0) as u64,
+    });
+    (result, Some(stats))
+}
@@ -0,0 +1,47 @@
+use aoc::server::{router, ServerConfig};
+use clap::Parser;
+use miette::{Context, IntoDiagnostic};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// Serve `POST /solve/{day}/{part}` for every registered solution
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// address to listen on
+    #[clap(long, default_value = "127.0.0.1:3000")]
+    addr: SocketAddr,
+    /// largest accepted puzzle input in bytes
+    #[clap(long, default_value_t = 1024 * 1024)]
+    max_input_bytes: usize,
+    /// per-request solve timeout in seconds, only solvers that check it stop when it is spent
+    #[clap(long, default_value_t = 30)]
+    timeout_secs: u64,
+    /// most solves running at once, defaults to the number of CPUs
+    #[clap(long)]
+    max_solves: Option<usize>,
+}
+
+#[tokio::main]
+async fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let args = Args::parse();
+    let defaults = ServerConfig::default();
+    let config = ServerConfig {
+        max_input_bytes: args.max_input_bytes,
+        timeout: Duration::from_secs(args.timeout_secs),
+        max_solves: args.max_solves.unwrap_or(defaults.max_solves),
+    };
+
+    let listener = tokio::net::TcpListener::bind(args.addr)
+        .await
+        .into_diagnostic()
+        .context("bind listener")?;
+    tracing::info!("listening on {}", args.addr);
+
+    axum::serve(listener, router(config))
+        .await
+        .into_diagnostic()
+        .context("serve")
+}
@@ -0,0 +1,115 @@
+use aoc::alloc::CountingAllocator;
+use aoc::cache::Cache;
+use aoc::calendar;
+use aoc::config::Config;
+use aoc::runner::{self, Runner};
+use aoc::tui::{self, Action, App, Job, LogBuffer, Update};
+use clap::Parser;
+use miette::{Context, IntoDiagnostic};
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::{Duration, Instant};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+/// How often the dashboard redraws when nothing else happens, to keep the log tailed.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Browse, run and profile all days in a terminal dashboard
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// directory of the result cache, defaults to `target/aoc-cache`
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+    /// puzzle parameters, defaults to `aoc.toml` in the workspace
+    #[clap(long)]
+    config: Option<PathBuf>,
+    /// give up on cancellable solvers after this many seconds
+    #[clap(long)]
+    timeout_secs: Option<u64>,
+    /// always solve, neither reading nor writing the cache
+    #[clap(long)]
+    no_cache: bool,
+}
+
+fn main() -> miette::Result<()> {
+    let args = Args::parse();
+
+    // The solvers' spans are logged as they close, with the time spent in them
+    let log = LogBuffer::default();
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(log.clone())
+        .init();
+
+    let config_path = args.config.unwrap_or_else(Config::default_path);
+    let config =
+        Config::load(&config_path).with_context(|| format!("load {}", config_path.display()))?;
+    let cache = Cache::new(args.cache_dir.unwrap_or_else(Cache::default_dir));
+    let days = calendar::scan(&runner::workspace_dir(), &cache, &config);
+
+    let runner = Runner::new((!args.no_cache).then_some(cache));
+    let runner = match args.timeout_secs {
+        Some(secs) => runner.with_timeout(Duration::from_secs(secs)),
+        None => runner,
+    };
+    let (jobs, updates) = tui::spawn_worker(runner, config.clone());
+    let mut app = App::new(days, config, log);
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app, &jobs, &updates);
+    ratatui::restore();
+    result
+}
+
+fn run(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    jobs: &Sender<Job>,
+    updates: &Receiver<Update>,
+) -> miette::Result<()> {
+    let mut redraw = true;
+    let mut last_drawn = Instant::now();
+    loop {
+        if redraw || last_drawn.elapsed() >= REDRAW_INTERVAL {
+            terminal
+                .draw(|frame| tui::draw(frame, app))
+                .into_diagnostic()?;
+            redraw = false;
+            last_drawn = Instant::now();
+        }
+
+        if event::poll(Duration::from_millis(100)).into_diagnostic()? {
+            if let Event::Key(key) = event::read().into_diagnostic()? {
+                if key.kind == KeyEventKind::Press {
+                    match app.handle_key(key) {
+                        Action::Quit => return Ok(()),
+                        Action::Run(new_jobs) => {
+                            for job in new_jobs {
+                                jobs.send(job).into_diagnostic()?;
+                            }
+                        }
+                        Action::None => {}
+                    }
+                }
+            }
+            // Also redraws after resizes
+            redraw = true;
+        }
+
+        for update in updates.try_iter() {
+            app.update(update);
+            redraw = true;
+        }
+    }
+}
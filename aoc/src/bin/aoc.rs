@@ -0,0 +1,626 @@
+use aoc::alloc::{self, CountingAllocator};
+use aoc::cache::Cache;
+use aoc::calendar;
+use aoc::config::{Config, ConfigError};
+use aoc::dump;
+use aoc::explain;
+use aoc::leaderboard::{self, Analysis};
+use aoc::params::Params;
+use aoc::puzzle;
+use aoc::registry::{self, Solution};
+use aoc::repl::{Reply, Session};
+use aoc::runner::{self, Outcome, Runner};
+use aoc_common::input::{self, InputError};
+use aoc_common::sealed::{self, InputKey};
+use aoc_error::AocError;
+use clap::{Parser, Subcommand, ValueEnum};
+use miette::{miette, Context, IntoDiagnostic};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::fs::File;
+use std::io::{BufRead, BufReader, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+/// Run the Advent of Code 2024 solutions
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// directory of the result cache, defaults to `target/aoc-cache`
+    #[clap(long, global = true)]
+    cache_dir: Option<PathBuf>,
+    /// puzzle parameters, defaults to `aoc.toml` in the workspace
+    #[clap(long, global = true)]
+    config: Option<PathBuf>,
+    /// give up on cancellable solvers after this many seconds
+    #[clap(long, global = true)]
+    timeout_secs: Option<u64>,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// solve one day, both parts unless `--part` is given
+    Solve {
+        day: u8,
+        #[clap(long)]
+        part: Option<u8>,
+        /// puzzle input, `-` for stdin, decrypted if it is sealed. Defaults to the day's
+        /// `input{part}.txt`, or else its sealed `input.txt.enc`
+        #[clap(long)]
+        input: Option<PathBuf>,
+        /// puzzle parameter such as `height=7` overriding the config, may be repeated
+        #[clap(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+        /// always solve, neither reading nor writing the cache
+        #[clap(long)]
+        no_cache: bool,
+        /// run an alternative implementation instead of the default one, never cached
+        #[clap(long, value_name = "NAME")]
+        implementation: Option<String>,
+        /// print the parsed model as JSON instead of solving, and check that it prints back to
+        /// the input
+        #[clap(long)]
+        dump_parsed: bool,
+        /// solve while reading `--input`, for inputs too large to hold in memory. Only the
+        /// line-oriented days can, and the answers are never cached
+        #[clap(long, requires = "input", conflicts_with_all = ["implementation", "dump_parsed"])]
+        stream: bool,
+        /// show how both answers were derived, as `text` or `json`. Only some days can
+        #[clap(
+            long,
+            value_name = "FORMAT",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "text",
+            conflicts_with_all = ["part", "implementation", "dump_parsed", "stream"]
+        )]
+        explain: Option<ExplainFormat>,
+    },
+    /// solve every day whose input is present
+    All {
+        /// always solve, neither reading nor writing the cache
+        #[clap(long)]
+        no_cache: bool,
+    },
+    /// explore one day's input interactively, setting parameters and solving again
+    Repl {
+        day: u8,
+        /// puzzle input, decrypted if it is sealed. Defaults to the day's part 1 input
+        #[clap(long)]
+        input: Option<PathBuf>,
+        /// run the commands in this file, one per line, instead of reading them interactively
+        #[clap(long)]
+        script: Option<PathBuf>,
+    },
+    /// show which days and parts are solved, with their recorded runtime and allocations
+    Calendar {
+        /// also write the calendar into README.md between its marker comments
+        #[clap(long)]
+        update_readme: bool,
+    },
+    /// manage the result cache
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommand,
+    },
+    /// statistics on a private leaderboard: standings with recomputed local scores, then every
+    /// day's ranking with part 1 to part 2 times
+    Leaderboard {
+        /// the leaderboard's JSON, `-` for stdin. `just leaderboard ID` fetches it
+        file: PathBuf,
+        /// print every star as CSV instead
+        #[clap(long)]
+        csv: bool,
+        /// show only this day's ranking
+        #[clap(long)]
+        day: Option<u8>,
+        /// show when this member, by name or id, got each star
+        #[clap(long, value_name = "NAME")]
+        member: Option<String>,
+    },
+    /// show a day's puzzle description, saved by `just get-input` as `PUZZLE.md`
+    Show {
+        /// the day, as `day-05` or `5`
+        #[clap(value_parser = parse_day)]
+        day: u8,
+        /// wrap paragraphs at this many columns, defaults to `$COLUMNS` up to 100
+        #[clap(long)]
+        width: Option<usize>,
+    },
+    /// manage the puzzle inputs, which are committed encrypted
+    Input {
+        #[clap(subcommand)]
+        command: InputCommand,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ExplainFormat {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum InputCommand {
+    /// print a new random key for sealing the inputs, to share with the team
+    Key,
+    /// encrypt a day's `input1.txt` into the `input.txt.enc` that can be committed
+    Seal { day: u8 },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// remove entries computed by solvers whose sources have changed since
+    Gc,
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.strip_prefix("day-")
+        .unwrap_or(day)
+        .parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("`{day}` is not a day from `day-01` to `day-25`"))
+}
+
+fn read_input(solution: &Solution, input: Option<&PathBuf>) -> miette::Result<String> {
+    match input {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .into_diagnostic()
+                .context("read stdin")?;
+            Ok(input)
+        }
+        Some(path) => input::read(path).with_context(|| format!("read {}", path.display())),
+        None => runner::read_input(solution.day, solution.part).with_context(|| {
+            format!(
+                "read the day {:02} part {} input",
+                solution.day, solution.part
+            )
+        }),
+    }
+}
+
+fn run(runner: &Runner, solution: &Solution, input: &str, config: &Config) -> miette::Result<()> {
+    let outcome = runner
+        .run(solution, input, config)
+        .with_context(|| format!("day {:02} part {}", solution.day, solution.part))?;
+    print_outcome(solution, &outcome);
+    Ok(())
+}
+
+/// Runs the given parts of one day, both at once if they read the same input.
+fn run_day(
+    runner: &Runner,
+    solutions: &[&Solution],
+    inputs: &[String],
+    config: &Config,
+) -> miette::Result<()> {
+    if let ([part1, part2], [input1, input2]) = (solutions, inputs) {
+        if let Some(both) = registry::find_both(part1.day).filter(|_| input1 == input2) {
+            let outcomes = runner
+                .run_both(both, input1, config)
+                .with_context(|| format!("day {:02}", both.day))?;
+            for (solution, outcome) in [part1, part2].into_iter().zip(&outcomes) {
+                print_outcome(solution, outcome);
+            }
+            return Ok(());
+        }
+    }
+
+    for (solution, input) in solutions.iter().zip(inputs) {
+        run(runner, solution, input, config)?;
+    }
+    Ok(())
+}
+
+/// Solves `day` from the input at `path` as it is read, so the input never has to fit in
+/// memory. Sealed inputs can only be decrypted as a whole, so they are refused.
+fn run_streaming(day: u8, part: Option<u8>, path: &Path) -> miette::Result<()> {
+    let streaming = registry::find_streaming(day).ok_or_else(|| {
+        let days = registry::streaming()
+            .iter()
+            .map(|streaming| format!("{:02}", streaming.day))
+            .collect::<Vec<_>>();
+        miette!(
+            help = format!("days {} can be streamed", days.join(", ")),
+            "no streaming solver for day {day}"
+        )
+    })?;
+    let mut reader: Box<dyn BufRead> = match path.as_os_str() == "-" {
+        true => Box::new(std::io::stdin().lock()),
+        false => Box::new(BufReader::new(
+            File::open(path)
+                .into_diagnostic()
+                .with_context(|| format!("read {}", path.display()))?,
+        )),
+    };
+    if sealed::is_sealed(reader.fill_buf().into_diagnostic()?) {
+        return Err(miette!(
+            help = "sealed inputs are decrypted in memory, solve them without `--stream`",
+            "{} is sealed",
+            path.display()
+        ));
+    }
+
+    let start = Instant::now();
+    let (answers, allocations) = alloc::measure(|| streaming.solve(&mut reader));
+    let elapsed = start.elapsed();
+    let answers = answers.with_context(|| format!("day {day:02}"))?;
+
+    for (number, answer) in [(1, answers.0), (2, answers.1)] {
+        if part.is_none_or(|part| part == number) {
+            println!(
+                "day {day:02} part {number} [stream]: {answer}  ({})",
+                calendar::format_stats(elapsed.as_secs_f64() * 1000.0, allocations.as_ref(), true)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_outcome(solution: &Solution, outcome: &Outcome) {
+    let implementation = if solution.name == registry::DEFAULT_IMPLEMENTATION {
+        String::new()
+    } else {
+        format!(" [{}]", solution.name)
+    };
+    println!(
+        "day {:02} part {}{implementation}: {}  ({}{})",
+        solution.day,
+        solution.part,
+        outcome.answer,
+        calendar::format_stats(
+            outcome.elapsed.as_secs_f64() * 1000.0,
+            outcome.allocations.as_ref(),
+            outcome.shared
+        ),
+        if outcome.cached { ", cached" } else { "" }
+    );
+}
+
+fn repl(mut session: Session, day: u8) -> miette::Result<()> {
+    let mut editor = DefaultEditor::new().into_diagnostic()?;
+    let history = runner::workspace_dir()
+        .join("target")
+        .join("aoc-repl-history");
+    // There is no history before the first session
+    let _ = editor.load_history(&history);
+    println!("day {day:02} loaded, `help` lists the commands");
+
+    loop {
+        let line = match editor.readline(&format!("day {day:02}> ")) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err).into_diagnostic(),
+        };
+        let _ = editor.add_history_entry(line.as_str());
+
+        match session.execute(&line) {
+            Ok(Reply::Output(output)) if output.is_empty() => {}
+            Ok(Reply::Output(output)) => println!("{output}"),
+            Ok(Reply::Quit) => break,
+            Err(report) => eprintln!("{report:?}"),
+        }
+    }
+
+    if let Err(err) = editor.save_history(&history) {
+        tracing::warn!("could not write {}: {err}", history.display());
+    }
+    Ok(())
+}
+
+fn run_script(mut session: Session, script: &Path) -> miette::Result<()> {
+    let commands = std::fs::read_to_string(script)
+        .into_diagnostic()
+        .with_context(|| format!("read {}", script.display()))?;
+
+    for (number, line) in (1..).zip(commands.lines()) {
+        let reply = session
+            .execute(line)
+            .with_context(|| format!("{}:{number}", script.display()))?;
+        match reply {
+            Reply::Output(output) if output.is_empty() => {}
+            Reply::Output(output) => println!("> {}\n{output}", line.trim()),
+            Reply::Quit => break,
+        }
+    }
+    Ok(())
+}
+
+fn explain_day(day: u8, input: &str, config: &Config, format: ExplainFormat) -> miette::Result<()> {
+    let explanation = explain::explain(day, input, config)
+        .ok_or_else(|| {
+            let days = explain::days()
+                .map(|day| format!("{day:02}"))
+                .collect::<Vec<_>>();
+            miette!(
+                help = format!("days {} can explain their answers", days.join(", ")),
+                "no explanation for day {day}"
+            )
+        })?
+        .with_context(|| format!("explain day {day:02}"))?;
+
+    match format {
+        ExplainFormat::Text => {
+            print!("{}", explanation.text);
+            println!("day {day:02} part 1: {}", explanation.answers.0);
+            println!("day {day:02} part 2: {}", explanation.answers.1);
+        }
+        ExplainFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&explanation.to_json()).into_diagnostic()?
+        ),
+    }
+    Ok(())
+}
+
+fn dump_parsed(solution: &Solution, input: &str) -> miette::Result<()> {
+    let dump = dump::dump(solution.day, input)
+        .ok_or_else(|| miette!("no model for day {}", solution.day))?
+        .with_context(|| format!("parse day {:02}", solution.day))?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&dump.json).into_diagnostic()?
+    );
+
+    match dump::first_mismatch(input, &dump.printed) {
+        None => {
+            eprintln!("the parsed model prints back to the input exactly");
+            Ok(())
+        }
+        Some(mismatch) => {
+            let show = |line: Option<String>| line.unwrap_or_else(|| "<end of file>".to_string());
+            eprintln!("input   {}: {}", mismatch.line, show(mismatch.input));
+            eprintln!("printed {}: {}", mismatch.line, show(mismatch.printed));
+            Err(miette!(
+                "the parsed model prints differently from the input from line {} on",
+                mismatch.line
+            ))
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    tracing_subscriber::fmt::init();
+
+    match run_command(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(report) => {
+            eprintln!("{report:?}");
+            ExitCode::from(aoc_error::exit_code(&report))
+        }
+    }
+}
+
+fn run_command(args: Args) -> miette::Result<()> {
+    let config_path = args.config.unwrap_or_else(Config::default_path);
+    let config = Config::load(&config_path)
+        .map_err(|err| match err {
+            ConfigError::Invalid { .. } => AocError::invalid_parameter(err.to_string()).into(),
+            err => miette::Report::new(err),
+        })
+        .with_context(|| format!("load {}", config_path.display()))?;
+    let cache = Cache::new(args.cache_dir.unwrap_or_else(Cache::default_dir));
+    let runner = |no_cache: bool| {
+        let runner = Runner::new((!no_cache).then(|| cache.clone()));
+        match args.timeout_secs {
+            Some(secs) => runner.with_timeout(Duration::from_secs(secs)),
+            None => runner,
+        }
+    };
+
+    match args.command {
+        Command::Solve {
+            day,
+            part,
+            input,
+            params,
+            no_cache,
+            implementation,
+            dump_parsed,
+            stream,
+            explain,
+        } => {
+            if let (true, Some(input)) = (stream, &input) {
+                return run_streaming(day, part, input);
+            }
+            let config = Params::from_assignments(params.iter().map(String::as_str))
+                .and_then(|params| Ok(params.resolve(&config)?))
+                .map_err(|err| {
+                    // Keep the cause, e.g. which field was unknown, not just the context
+                    let causes = err.chain().map(ToString::to_string).collect::<Vec<_>>();
+                    AocError::invalid_parameter(causes.join(": "))
+                })?;
+            let solutions = registry::solutions()
+                .iter()
+                .filter(|solution| solution.day == day && part.is_none_or(|p| solution.part == p))
+                .filter_map(|solution| match &implementation {
+                    Some(name) => registry::find_implementation(day, solution.part, name),
+                    None => Some(solution),
+                })
+                .collect::<Vec<_>>();
+            if solutions.is_empty() {
+                return Err(match implementation {
+                    Some(name) => miette!("no implementation {name} for day {day}"),
+                    None => miette!("no solution for day {day}"),
+                });
+            }
+
+            if dump_parsed {
+                // Both parts share the model, so one input is enough
+                let input = read_input(solutions[0], input.as_ref())?;
+                return self::dump_parsed(solutions[0], &input);
+            }
+            if let Some(format) = explain {
+                let input = read_input(solutions[0], input.as_ref())?;
+                return explain_day(day, &input, &config, format);
+            }
+
+            let inputs = solutions
+                .iter()
+                .map(|solution| read_input(solution, input.as_ref()))
+                .collect::<miette::Result<Vec<_>>>()?;
+            if implementation.is_some() {
+                // The cache holds the default implementations' answers and timings
+                let runner = runner(true);
+                for (solution, input) in solutions.iter().zip(&inputs) {
+                    run(&runner, solution, input, &config)?;
+                }
+            } else {
+                run_day(&runner(no_cache), &solutions, &inputs, &config)?;
+            }
+        }
+        Command::All { no_cache } => {
+            let runner = runner(no_cache);
+            for day in registry::solutions().chunk_by(|a, b| a.day == b.day) {
+                let (solutions, inputs): (Vec<_>, Vec<_>) = day
+                    .iter()
+                    .filter_map(
+                        |solution| match runner::read_input(solution.day, solution.part) {
+                            Ok(input) => Some(Ok((solution, input))),
+                            Err(InputError::NotFound { dir }) => {
+                                tracing::info!("skipping {}, no input", dir.display());
+                                None
+                            }
+                            Err(err) => Some(Err(err)),
+                        },
+                    )
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .unzip();
+                run_day(&runner, &solutions, &inputs, &config)?;
+            }
+        }
+        Command::Repl { day, input, script } => {
+            let input = match input {
+                Some(path) => {
+                    input::read(&path).with_context(|| format!("read {}", path.display()))?
+                }
+                None => runner::read_input(day, 1)
+                    .with_context(|| format!("read the day {day:02} input"))?,
+            };
+            let session = Session::new(day, input, config)?;
+
+            match script {
+                Some(script) => run_script(session, &script)?,
+                None => repl(session, day)?,
+            }
+        }
+        Command::Calendar { update_readme } => {
+            let days = calendar::scan(&runner::workspace_dir(), &cache, &config);
+            print!("{}", calendar::render_terminal(&days));
+
+            if update_readme {
+                let path = runner::workspace_dir().join("README.md");
+                let readme = std::fs::read_to_string(&path)
+                    .into_diagnostic()
+                    .with_context(|| format!("read {}", path.display()))?;
+                let readme = calendar::update_readme(&readme, &calendar::render_markdown(&days));
+                std::fs::write(&path, readme)
+                    .into_diagnostic()
+                    .with_context(|| format!("write {}", path.display()))?;
+            }
+        }
+        Command::Cache {
+            command: CacheCommand::Gc,
+        } => {
+            let stats = cache
+                .gc()
+                .into_diagnostic()
+                .with_context(|| format!("collect {}", cache.dir().display()))?;
+            println!("removed {} entries, kept {}", stats.removed, stats.kept);
+        }
+        Command::Leaderboard {
+            file,
+            csv,
+            day,
+            member,
+        } => {
+            let json = if file.as_os_str() == "-" {
+                let mut json = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut json)
+                    .into_diagnostic()
+                    .context("read stdin")?;
+                json
+            } else {
+                std::fs::read_to_string(&file)
+                    .into_diagnostic()
+                    .with_context(|| format!("read {}", file.display()))?
+            };
+            let analysis = Analysis::new(&leaderboard::parse(&json)?)?;
+
+            if csv {
+                print!("{}", leaderboard::render_csv(&analysis));
+            } else if let Some(name) = member {
+                let member = analysis
+                    .find_member(&name)
+                    .ok_or_else(|| miette!("no member {name} on the leaderboard"))?;
+                print!("{}", leaderboard::render_timeline(&analysis, member));
+            } else if let Some(day) = day {
+                print!("{}", leaderboard::render_day(&analysis, day));
+            } else {
+                print!("{}", leaderboard::render_standings(&analysis));
+                for day in analysis.days() {
+                    print!("\n{}", leaderboard::render_day(&analysis, day));
+                }
+            }
+        }
+        Command::Show { day, width } => {
+            let path = puzzle::path(&runner::day_dir(day));
+            let markdown = match std::fs::read_to_string(&path) {
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(miette!(
+                        help = format!("fetch it with `just get-puzzle day-{day:02}`"),
+                        "no puzzle description at {}",
+                        path.display()
+                    ));
+                }
+                markdown => markdown
+                    .into_diagnostic()
+                    .with_context(|| format!("read {}", path.display()))?,
+            };
+            // Wide terminals are capped so paragraphs stay readable, an explicit width is not
+            let width = width.unwrap_or_else(|| {
+                std::env::var("COLUMNS")
+                    .ok()
+                    .and_then(|columns| columns.parse().ok())
+                    .map_or(80, |columns: usize| columns.min(100))
+            });
+            let styled = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            print!("{}", puzzle::render(&markdown, width, styled));
+        }
+        Command::Input {
+            command: InputCommand::Key,
+        } => {
+            println!("{}", InputKey::generate().to_hex());
+        }
+        Command::Input {
+            command: InputCommand::Seal { day },
+        } => {
+            let key = InputKey::from_env()?;
+            let day_dir = runner::day_dir(day);
+            let plain = day_dir.join("input1.txt");
+            let input = std::fs::read_to_string(&plain)
+                .into_diagnostic()
+                .with_context(|| format!("read {}", plain.display()))?;
+            let path = day_dir.join(input::SEALED_INPUT);
+            std::fs::write(&path, sealed::seal(&key, &input))
+                .into_diagnostic()
+                .with_context(|| format!("write {}", path.display()))?;
+            println!("wrote {}", path.display());
+        }
+    }
+
+    Ok(())
+}
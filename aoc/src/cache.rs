@@ -0,0 +1,232 @@
+//! Content-addressed on-disk cache of computed answers.
+//!
+//! An entry is keyed by the hash of the day, part, the day's parameters, input bytes and the
+//! hash of the day's solver sources, so editing a solver makes its old entries unreachable.
+//! `gc` removes them.
+
+use crate::alloc::AllocStats;
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+include!(concat!(env!("OUT_DIR"), "/source_hashes.rs"));
+
+/// Hash of the sources the solver for `day` is built from, including the registry that wires it
+/// up, computed at build time.
+pub fn source_hash(day: u8) -> Option<&'static str> {
+    SOURCE_HASHES.get(usize::from(day).checked_sub(1)?).copied()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{byte:02x}").unwrap();
+        hex
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub day: u8,
+    pub part: u8,
+    /// Source hash of the solver that computed the answer
+    pub source_hash: String,
+    pub answer: String,
+    /// Time the original solve took
+    pub elapsed_ms: f64,
+    /// Allocations of the original solve, if they were counted
+    #[serde(default)]
+    pub allocations: Option<AllocStats>,
+    /// Whether the time and allocations are of both parts solved together, and so the same as
+    /// in the other part's entry
+    #[serde(default)]
+    pub shared: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GcStats {
+    pub kept: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    /// `target/aoc-cache` in the workspace, so `cargo clean` also clears the cache.
+    pub fn default_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("target")
+            .join("aoc-cache")
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Computes the cache key of a solve, or `None` if there is no solver for `day`.
+    pub fn key(day: u8, part: u8, config: &Config, input: &str) -> Option<String> {
+        let source_hash = source_hash(day)?;
+        // Only the day's own section, so changing another day's parameters keeps the entry
+        let params = config.section(day).unwrap_or_default();
+
+        let mut hasher = Sha256::new();
+        hasher.update([day, part]);
+        hasher.update(source_hash.as_bytes());
+        // Length prefixes keep the variable-length fields from running into each other
+        hasher.update((params.len() as u64).to_le_bytes());
+        hasher.update(params.as_bytes());
+        hasher.update((input.len() as u64).to_le_bytes());
+        hasher.update(input.as_bytes());
+        Some(to_hex(&hasher.finalize()))
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    /// Looks up an entry. Unreadable or stale entries are treated as missing.
+    pub fn get(&self, key: &str) -> Option<CacheEntry> {
+        let entry: CacheEntry = serde_json::from_slice(&fs::read(self.path(key)).ok()?).ok()?;
+        (source_hash(entry.day) == Some(entry.source_hash.as_str())).then_some(entry)
+    }
+
+    pub fn put(&self, key: &str, entry: &CacheEntry) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        // Write to a temporary file first so concurrent readers never see a partial entry
+        let path = self.path(key);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(entry)?)?;
+        fs::rename(tmp, path)
+    }
+
+    /// Removes every entry that was computed by a solver whose sources have changed since, as
+    /// well as anything in the cache directory that is not a readable entry.
+    pub fn gc(&self) -> io::Result<GcStats> {
+        let mut stats = GcStats::default();
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(stats),
+            Err(err) => return Err(err),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            let current = path.extension().is_some_and(|ext| ext == "json")
+                && fs::read(&path)
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice::<CacheEntry>(&bytes).ok())
+                    .is_some_and(|entry| {
+                        source_hash(entry.day) == Some(entry.source_hash.as_str())
+                    });
+
+            if current {
+                stats.kept += 1;
+            } else if path.is_file() {
+                fs::remove_file(&path)?;
+                stats.removed += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, source_hash: &str) -> CacheEntry {
+        CacheEntry {
+            day,
+            part: 1,
+            source_hash: source_hash.to_string(),
+            answer: "42".to_string(),
+            elapsed_ms: 1.0,
+            allocations: None,
+            shared: false,
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        let config = Config::default();
+        let key = Cache::key(14, 1, &config, "p=0,4 v=3,-3").unwrap();
+
+        assert_eq!(64, key.len());
+        assert_eq!(
+            Some(&key),
+            Cache::key(14, 1, &config, "p=0,4 v=3,-3").as_ref()
+        );
+        assert_ne!(
+            Some(&key),
+            Cache::key(14, 2, &config, "p=0,4 v=3,-3").as_ref()
+        );
+        assert_ne!(
+            Some(&key),
+            Cache::key(14, 1, &config, "p=0,4 v=3,-2").as_ref()
+        );
+
+        let mut other_day = config.clone();
+        other_day.day21.levels = 3;
+        assert_eq!(
+            Some(&key),
+            Cache::key(14, 1, &other_day, "p=0,4 v=3,-3").as_ref()
+        );
+
+        let mut same_day = config.clone();
+        same_day.day14.height = 7;
+        assert_ne!(
+            Some(&key),
+            Cache::key(14, 1, &same_day, "p=0,4 v=3,-3").as_ref()
+        );
+        assert_eq!(None, Cache::key(26, 1, &config, "p=0,4 v=3,-3"));
+    }
+
+    #[test]
+    fn test_get_put() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path());
+        let key = Cache::key(1, 1, &Config::default(), "1   2").unwrap();
+        assert_eq!(None, cache.get(&key));
+
+        let current = entry(1, source_hash(1).unwrap());
+        cache.put(&key, &current)?;
+        assert_eq!(Some(current), cache.get(&key));
+
+        cache.put(&key, &entry(1, "outdated"))?;
+        assert_eq!(None, cache.get(&key));
+        Ok(())
+    }
+
+    #[test]
+    fn test_gc() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path());
+        cache.put("current", &entry(2, source_hash(2).unwrap()))?;
+        cache.put("stale", &entry(2, "outdated"))?;
+        fs::write(dir.path().join("garbage.json"), "{")?;
+
+        assert_eq!(
+            GcStats {
+                kept: 1,
+                removed: 2
+            },
+            cache.gc()?
+        );
+        assert!(cache.get("current").is_some());
+        assert!(!dir.path().join("stale.json").exists());
+        Ok(())
+    }
+}
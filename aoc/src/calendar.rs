@@ -0,0 +1,295 @@
+//! Overview of the whole calendar: which days have a crate, which parts are implemented and
+//! which have an answer recorded for the puzzle input.
+//!
+//! Answers are recorded by the runner in the result cache, so a part counts as solved once it
+//! was run on its puzzle input with the current sources and parameters.
+
+use crate::alloc::AllocStats;
+use crate::cache::{Cache, CacheEntry};
+use crate::config::Config;
+use crate::registry;
+use aoc_common::input::{self, normalize};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+pub const README_START: &str = "<!-- calendar:start -->";
+pub const README_END: &str = "<!-- calendar:end -->";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartStatus {
+    /// The day has no crate yet
+    NoCrate,
+    /// The crate has no `part{N}.rs`, like day 25 part 2
+    Missing,
+    /// `process` is still the template's `todo!()`
+    Todo,
+    /// Implemented but not in the registry, such as a part solved by hand
+    Unregistered,
+    /// Not run on the puzzle input since the sources or parameters last changed
+    NotRun,
+    /// Solved on the puzzle input
+    Solved(CacheEntry),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayStatus {
+    pub day: u8,
+    pub parts: [PartStatus; 2],
+}
+
+impl DayStatus {
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|part| matches!(part, PartStatus::Solved(_)))
+            .count()
+    }
+}
+
+fn part_status(workspace: &Path, cache: &Cache, config: &Config, day: u8, part: u8) -> PartStatus {
+    let day_dir = workspace.join(format!("day-{day:02}"));
+    if !day_dir.join("Cargo.toml").exists() {
+        return PartStatus::NoCrate;
+    }
+
+    let Ok(source) = fs::read_to_string(day_dir.join("src").join(format!("part{part}.rs"))) else {
+        return PartStatus::Missing;
+    };
+    // The template's test module also starts with `todo!()`, only `process` matters
+    let code = source.split("#[cfg(test)]").next().unwrap_or_default();
+    if code.contains("todo!(") {
+        return PartStatus::Todo;
+    }
+
+    if registry::find(day, part).is_none() {
+        return PartStatus::Unregistered;
+    }
+
+    input::load(&day_dir, part)
+        .ok()
+        .and_then(|input| Cache::key(day, part, config, &normalize(&input)))
+        .and_then(|key| cache.get(&key))
+        .map_or(PartStatus::NotRun, PartStatus::Solved)
+}
+
+/// Inspects the days in `workspace` and looks up their recorded answers in `cache`.
+pub fn scan(workspace: &Path, cache: &Cache, config: &Config) -> Vec<DayStatus> {
+    (1..=25)
+        .map(|day| DayStatus {
+            day,
+            parts: [1, 2].map(|part| part_status(workspace, cache, config, day, part)),
+        })
+        .collect()
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1048576 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1048576.0),
+    }
+}
+
+/// The time and allocations of a solve, marked as being for both parts if they were `shared`
+/// by a combined solve.
+pub fn format_stats(elapsed_ms: f64, allocations: Option<&AllocStats>, shared: bool) -> String {
+    let both = if shared { " for both parts" } else { "" };
+    match allocations {
+        Some(stats) => format!(
+            "{elapsed_ms:.3} ms, {} allocations, peak {}{both}",
+            stats.allocations,
+            format_bytes(stats.peak_bytes)
+        ),
+        None => format!("{elapsed_ms:.3} ms{both}"),
+    }
+}
+
+fn describe(status: &PartStatus) -> String {
+    match status {
+        PartStatus::NoCrate => "-".to_string(),
+        PartStatus::Missing => "missing".to_string(),
+        PartStatus::Todo => "todo".to_string(),
+        PartStatus::Unregistered => "not in registry".to_string(),
+        PartStatus::NotRun => "not run".to_string(),
+        PartStatus::Solved(entry) => {
+            format_stats(entry.elapsed_ms, entry.allocations.as_ref(), entry.shared)
+        }
+    }
+}
+
+fn total_stars(days: &[DayStatus]) -> usize {
+    days.iter().map(DayStatus::stars).sum()
+}
+
+pub fn render_terminal(days: &[DayStatus]) -> String {
+    let mut out = format!(
+        "{:<4} {:<5} {:<45} {}\n",
+        "day", "stars", "part 1", "part 2"
+    );
+    for day in days {
+        writeln!(
+            out,
+            "{:<4} {:<5} {:<45} {}",
+            format!("{:02}", day.day),
+            "*".repeat(day.stars()),
+            describe(&day.parts[0]),
+            describe(&day.parts[1])
+        )
+        .unwrap();
+    }
+    writeln!(out, "\ntotal stars: {}", total_stars(days)).unwrap();
+    out
+}
+
+pub fn render_markdown(days: &[DayStatus]) -> String {
+    let mut out = String::from("| Day | Stars | Part 1 | Part 2 |\n|---:|:---:|---|---|\n");
+    for day in days {
+        let name = match day.parts[0] {
+            PartStatus::NoCrate => format!("{:02}", day.day),
+            _ => format!("[{:02}](day-{:02})", day.day, day.day),
+        };
+        writeln!(
+            out,
+            "| {name} | {} | {} | {} |",
+            "⭐".repeat(day.stars()),
+            describe(&day.parts[0]),
+            describe(&day.parts[1])
+        )
+        .unwrap();
+    }
+    writeln!(out, "\nTotal stars: **{}**", total_stars(days)).unwrap();
+    out
+}
+
+/// Replaces everything between the calendar markers in `readme` with `table`, appending the
+/// markers if the README has none yet.
+pub fn update_readme(readme: &str, table: &str) -> String {
+    let section = format!("{README_START}\n{table}{README_END}");
+
+    match (readme.find(README_START), readme.find(README_END)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{section}{}",
+            &readme[..start],
+            &readme[end + README_END.len()..]
+        ),
+        _ => format!("{}\n\n{section}\n", readme.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_scan() {
+        let workspace = tempfile::tempdir().unwrap();
+        let cache = Cache::new(workspace.path().join("cache"));
+        let config = Config::default();
+        let root = workspace.path();
+
+        write(&root.join("day-01/Cargo.toml"), "");
+        write(&root.join("day-01/src/part1.rs"), "pub fn process() {}");
+        write(&root.join("day-01/input1.txt"), "3   4\n");
+        write(
+            &root.join("day-01/src/part2.rs"),
+            "pub fn process() {\n    todo!(\"day 01 - part 2\");\n}",
+        );
+        write(&root.join("day-24/Cargo.toml"), "");
+        write(&root.join("day-24/src/part2.rs"), "pub fn process() {}");
+        write(&root.join("day-25/Cargo.toml"), "");
+        write(
+            &root.join("day-25/src/part1.rs"),
+            "pub fn process() {}\n#[cfg(test)]\nfn test() { todo!() }",
+        );
+
+        let key = Cache::key(1, 1, &config, "3   4\n").unwrap();
+        let entry = CacheEntry {
+            day: 1,
+            part: 1,
+            source_hash: crate::cache::source_hash(1).unwrap().to_string(),
+            answer: "1".to_string(),
+            elapsed_ms: 0.5,
+            allocations: None,
+            shared: false,
+        };
+        cache.put(&key, &entry).unwrap();
+
+        let days = scan(root, &cache, &config);
+        assert_eq!(25, days.len());
+        assert_eq!([PartStatus::Solved(entry), PartStatus::Todo], days[0].parts);
+        assert_eq!([PartStatus::NoCrate, PartStatus::NoCrate], days[1].parts);
+        assert_eq!(PartStatus::Unregistered, days[23].parts[1]);
+        assert_eq!([PartStatus::NotRun, PartStatus::Missing], days[24].parts);
+        assert_eq!(1, total_stars(&days));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let days = [DayStatus {
+            day: 25,
+            parts: [
+                PartStatus::Solved(CacheEntry {
+                    day: 25,
+                    part: 1,
+                    source_hash: String::new(),
+                    answer: "3".to_string(),
+                    elapsed_ms: 1.5,
+                    allocations: Some(AllocStats {
+                        allocations: 10,
+                        bytes: 4096,
+                        peak_bytes: 2048,
+                    }),
+                    shared: false,
+                }),
+                PartStatus::Missing,
+            ],
+        }];
+
+        assert_eq!(
+            "| Day | Stars | Part 1 | Part 2 |
+|---:|:---:|---|---|
+| [25](day-25) | ⭐ | 1.500 ms, 10 allocations, peak 2.0 KiB | missing |
+
+Total stars: **1**
+",
+            render_markdown(&days)
+        );
+    }
+
+    #[test]
+    fn test_format_stats() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 100,
+            peak_bytes: 64,
+        };
+        assert_eq!(
+            "2.000 ms, 3 allocations, peak 64 B",
+            format_stats(2.0, Some(&stats), false)
+        );
+        assert_eq!("2.000 ms for both parts", format_stats(2.0, None, true));
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let table = "| Day |\n";
+        let added = update_readme("# Advent of Code 2024", table);
+        assert_eq!(
+            "# Advent of Code 2024\n\n<!-- calendar:start -->\n| Day |\n<!-- calendar:end -->\n",
+            added
+        );
+
+        let replaced = update_readme(&added, "| Day | Stars |\n");
+        assert!(
+            replaced.contains("<!-- calendar:start -->\n| Day | Stars |\n<!-- calendar:end -->")
+        );
+        assert!(!replaced.contains("| Day |\n"));
+        assert_eq!(replaced, update_readme(&replaced, "| Day | Stars |\n"));
+    }
+}
@@ -0,0 +1,143 @@
+//! Parsed puzzle models, for debugging a parser against a real input.
+//!
+//! Every day's model prints back to the puzzle text, so comparing the printed model with the
+//! input shows the first place where the parser lost or misread something.
+
+use aoc_common::input::normalize;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// A day's parsed model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dump {
+    pub json: serde_json::Value,
+    /// The model printed in the puzzle input format
+    pub printed: String,
+}
+
+/// The first line where a printed model differs from its input, numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub line: usize,
+    /// The input's line, `None` past its end
+    pub input: Option<String>,
+    /// The printed model's line, `None` past its end
+    pub printed: Option<String>,
+}
+
+type DumpFn = fn(&str) -> miette::Result<Dump>;
+
+fn dump_model(model: &(impl Serialize + Display)) -> miette::Result<Dump> {
+    Ok(Dump {
+        json: serde_json::to_value(model).map_err(|err| miette::miette!("{err}"))?,
+        printed: model.to_string(),
+    })
+}
+
+macro_rules! model {
+    ($day:literal, $krate:ident) => {
+        ($day, |input| dump_model(&$krate::model::parse(input)?))
+    };
+}
+
+static MODELS: &[(u8, DumpFn)] = &[
+    model!(1, day_01),
+    model!(2, day_02),
+    model!(3, day_03),
+    model!(4, day_04),
+    model!(5, day_05),
+    model!(6, day_06),
+    model!(7, day_07),
+    model!(8, day_08),
+    model!(9, day_09),
+    model!(10, day_10),
+    model!(11, day_11),
+    model!(12, day_12),
+    model!(13, day_13),
+    model!(14, day_14),
+    model!(15, day_15),
+    model!(16, day_16),
+    model!(17, day_17),
+    model!(18, day_18),
+    model!(19, day_19),
+    model!(20, day_20),
+    model!(21, day_21),
+    model!(22, day_22),
+    model!(23, day_23),
+    model!(24, day_24),
+    model!(25, day_25),
+];
+
+/// Parses the normalised `input` into the model of `day`, or returns `None` if there is no
+/// model for `day`.
+pub fn dump(day: u8, input: &str) -> Option<miette::Result<Dump>> {
+    MODELS
+        .iter()
+        .find(|(model_day, _)| *model_day == day)
+        .map(|(_, dump)| dump(&normalize(input)))
+}
+
+/// Compares a printed model with the input it was parsed from, after normalising the input.
+pub fn first_mismatch(input: &str, printed: &str) -> Option<Mismatch> {
+    let input = normalize(input);
+    let mut input_lines = input.lines();
+    let mut printed_lines = printed.lines();
+
+    let mut line = 0;
+    loop {
+        line += 1;
+        match (input_lines.next(), printed_lines.next()) {
+            (None, None) => return None,
+            (input, printed) if input == printed => {}
+            (input, printed) => {
+                return Some(Mismatch {
+                    line,
+                    input: input.map(str::to_string),
+                    printed: printed.map(str::to_string),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump() -> miette::Result<()> {
+        let dump = dump(1, "3   4\r\n4   3\r\n").unwrap()?;
+        assert_eq!(
+            serde_json::json!({ "left": [3, 4], "right": [4, 3] }),
+            dump.json
+        );
+        assert_eq!("3   4\n4   3\n", dump.printed);
+        assert!(super::dump(1, "3 x").unwrap().is_err());
+        assert!(super::dump(26, "").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_mismatch() {
+        assert_eq!(
+            None,
+            first_mismatch("3   4\r\n4   3  \n\n", "3   4\n4   3\n")
+        );
+        assert_eq!(
+            Some(Mismatch {
+                line: 2,
+                input: Some("4  3".to_string()),
+                printed: Some("4   3".to_string()),
+            }),
+            first_mismatch("3   4\n4  3\n", "3   4\n4   3\n")
+        );
+        assert_eq!(
+            Some(Mismatch {
+                line: 2,
+                input: Some("4   3".to_string()),
+                printed: None,
+            }),
+            first_mismatch("3   4\n4   3\n", "3   4\n")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Explanations of how a day's answers were derived, for finding out why one is wrong.
+//!
+//! An explanation lists the decisions behind both answers, such as which reports were safe or
+//! which operators satisfied an equation, and adds the answers up from them. They are the same
+//! answers the solvers give, which the tests check.
+
+use aoc_common::config::Config;
+use aoc_common::input::normalize;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// How a day's answers came about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub day: u8,
+    pub json: serde_json::Value,
+    /// The explanation laid out for reading
+    pub text: String,
+    /// Both parts' answers, added up from the explanation
+    pub answers: (String, String),
+}
+
+impl Explanation {
+    /// The explanation with its day and answers, as printed by `aoc solve --explain=json`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "day": self.day,
+            "part1": self.answers.0,
+            "part2": self.answers.1,
+            "explanation": self.json,
+        })
+    }
+}
+
+type ExplainFn = fn(&str, &Config) -> miette::Result<Explanation>;
+
+fn explanation(
+    day: u8,
+    explanation: &(impl Serialize + Display),
+    answers: (String, String),
+) -> miette::Result<Explanation> {
+    Ok(Explanation {
+        day,
+        json: serde_json::to_value(explanation).map_err(|err| miette::miette!("{err}"))?,
+        text: explanation.to_string(),
+        answers,
+    })
+}
+
+macro_rules! explained {
+    ($day:literal, $krate:ident) => {
+        ($day, |input, _config| {
+            let explained = $krate::explain::explain(&$krate::model::parse(input)?);
+            explanation($day, &explained, explained.answers())
+        })
+    };
+}
+
+static EXPLAINED: &[(u8, ExplainFn)] = &[
+    explained!(2, day_02),
+    explained!(3, day_03),
+    explained!(5, day_05),
+    explained!(7, day_07),
+    (13, |input, config| {
+        let prize_offset = config.day13.prize_offset;
+        day_13::model::check_prize_offset(prize_offset)?;
+        let explained = day_13::explain::explain(&day_13::model::parse(input)?, prize_offset);
+        explanation(13, &explained, explained.answers())
+    }),
+];
+
+/// The days that can explain their answers.
+pub fn days() -> impl Iterator<Item = u8> {
+    EXPLAINED.iter().map(|(day, _)| *day)
+}
+
+/// Explains the answers of `day` for the normalised `input`, or returns `None` if the day
+/// cannot explain them.
+pub fn explain(day: u8, input: &str, config: &Config) -> Option<miette::Result<Explanation>> {
+    EXPLAINED
+        .iter()
+        .find(|(explained_day, _)| *explained_day == day)
+        .map(|(_, explain)| explain(&normalize(input), config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let explanation = explain(2, "7 6 4 2 1\r\n1 3 2 4 5\r\n", &Config::default()).unwrap()?;
+        assert_eq!(("1".to_string(), "2".to_string()), explanation.answers);
+        assert_eq!(
+            serde_json::json!({ "removed": 1 }),
+            explanation.json["reports"][1]["dampened"]
+        );
+        assert_eq!("1", explanation.to_json()["part1"]);
+        assert!(super::explain(2, "7 x", &Config::default())
+            .unwrap()
+            .is_err());
+        assert!(super::explain(1, "", &Config::default()).is_none());
+        Ok(())
+    }
+}
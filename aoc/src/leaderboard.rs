@@ -0,0 +1,496 @@
+//! Statistics on a private leaderboard, from the JSON Advent of Code serves for it at
+//! `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`.
+//!
+//! The local score is recomputed from the star times: for every day and part, the first member
+//! to get the star scores one point per member of the leaderboard, the second one point less,
+//! and so on. Members who got a star at the same second are ordered by when the server recorded
+//! it.
+
+use miette::Diagnostic;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use thiserror::Error;
+
+/// Puzzles unlock at midnight in the US eastern time zone, which is UTC-5 in December.
+const UNLOCK_OFFSET_SECS: i64 = 5 * 3600;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum LeaderboardError {
+    #[error("Not a private leaderboard")]
+    #[diagnostic(
+        code(aoc::leaderboard::parse),
+        help("save the JSON from the leaderboard's API link, or fetch it with `just leaderboard`")
+    )]
+    Parse(#[from] serde_json::Error),
+
+    #[error("The leaderboard's event `{0}` is not a year")]
+    #[diagnostic(code(aoc::leaderboard::parse))]
+    Event(String),
+
+    #[error("Member {member} has a star for day {day} part {part}, which does not exist")]
+    #[diagnostic(code(aoc::leaderboard::parse))]
+    Star { member: u64, day: u8, part: u8 },
+}
+
+/// The leaderboard as served, leaving out what the statistics do not need.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<u64, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for members who chose to stay anonymous
+    pub name: Option<String>,
+    /// The local score as Advent of Code computed it
+    pub local_score: u32,
+    /// When the member got each star, by day and part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, StarTime>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct StarTime {
+    /// Unix time
+    pub get_star_ts: i64,
+    /// Order in which the server recorded stars, which breaks ties between equal times
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    /// The name Advent of Code shows for the member.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+/// Reads a leaderboard from its JSON.
+pub fn parse(json: &str) -> Result<Leaderboard, LeaderboardError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// One star one member got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Star {
+    pub member: u64,
+    pub day: u8,
+    pub part: u8,
+    /// Unix time the star was got
+    pub timestamp: i64,
+    /// Seconds from the puzzle unlocking to the star
+    pub elapsed: i64,
+    /// 1 for the first member to get the star
+    pub rank: usize,
+    pub points: u32,
+}
+
+/// A member's totals, with their recomputed local score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub member: u64,
+    pub name: String,
+    pub stars: usize,
+    pub local_score: u32,
+    /// The local score in the JSON, which differs from the recomputed one only for events where
+    /// Advent of Code cancelled the points for a day
+    pub reported_local_score: u32,
+    /// Unix time of the member's latest star
+    pub last_star: Option<i64>,
+}
+
+/// How one member did on one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayResult {
+    pub member: u64,
+    pub name: String,
+    /// Seconds from the unlock to the first star
+    pub part1: Option<i64>,
+    /// Seconds from the unlock to the second star
+    pub part2: Option<i64>,
+    /// Seconds from the first star to the second
+    pub delta: Option<i64>,
+    pub points: u32,
+}
+
+/// One star in a member's timeline, with the totals it brought them to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEntry {
+    pub timestamp: i64,
+    pub day: u8,
+    pub part: u8,
+    pub stars: usize,
+    pub local_score: u32,
+}
+
+/// Everything worked out from one leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    pub year: i32,
+    /// Best first
+    pub standings: Vec<Standing>,
+    /// By day, part and rank
+    pub stars: Vec<Star>,
+}
+
+impl Analysis {
+    pub fn new(leaderboard: &Leaderboard) -> Result<Self, LeaderboardError> {
+        let year = leaderboard
+            .event
+            .parse()
+            .map_err(|_| LeaderboardError::Event(leaderboard.event.clone()))?;
+
+        let mut by_star = BTreeMap::<(u8, u8), Vec<(StarTime, u64)>>::new();
+        for member in leaderboard.members.values() {
+            for (&day, parts) in &member.completion_day_level {
+                for (&part, &time) in parts {
+                    if !(1..=25).contains(&day) || !(1..=2).contains(&part) {
+                        return Err(LeaderboardError::Star {
+                            member: member.id,
+                            day,
+                            part,
+                        });
+                    }
+                    by_star
+                        .entry((day, part))
+                        .or_default()
+                        .push((time, member.id));
+                }
+            }
+        }
+
+        let num_members = leaderboard.members.len();
+        let mut stars = Vec::new();
+        for ((day, part), mut times) in by_star {
+            times.sort_by_key(|(time, member)| (time.get_star_ts, time.star_index, *member));
+            stars.extend(
+                times
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (time, member))| Star {
+                        member,
+                        day,
+                        part,
+                        timestamp: time.get_star_ts,
+                        elapsed: time.get_star_ts - unlock_time(year, day),
+                        rank: i + 1,
+                        points: (num_members - i) as u32,
+                    }),
+            );
+        }
+
+        let mut standings = leaderboard
+            .members
+            .values()
+            .map(|member| {
+                let own = stars.iter().filter(|star| star.member == member.id);
+                Standing {
+                    member: member.id,
+                    name: member.display_name(),
+                    stars: own.clone().count(),
+                    local_score: own.clone().map(|star| star.points).sum(),
+                    reported_local_score: member.local_score,
+                    last_star: own.map(|star| star.timestamp).max(),
+                }
+            })
+            .collect::<Vec<_>>();
+        // Advent of Code breaks ties by who got there first
+        standings.sort_by_key(|standing| {
+            (
+                std::cmp::Reverse(standing.local_score),
+                standing.last_star.unwrap_or(i64::MAX),
+                standing.member,
+            )
+        });
+
+        Ok(Analysis {
+            year,
+            standings,
+            stars,
+        })
+    }
+
+    fn name(&self, member: u64) -> String {
+        self.standings
+            .iter()
+            .find(|standing| standing.member == member)
+            .map_or_else(String::new, |standing| standing.name.clone())
+    }
+
+    /// The days anyone got a star on.
+    pub fn days(&self) -> Vec<u8> {
+        let mut days = self.stars.iter().map(|star| star.day).collect::<Vec<_>>();
+        days.dedup();
+        days
+    }
+
+    /// The members who got a star on `day`, those with both stars first, each group in the
+    /// order they got their last star.
+    pub fn day_ranking(&self, day: u8) -> Vec<DayResult> {
+        // With the rank of the member's last star
+        let mut results = BTreeMap::<u64, (DayResult, usize)>::new();
+        for star in self.stars.iter().filter(|star| star.day == day) {
+            let (result, rank) = results.entry(star.member).or_insert_with(|| {
+                let result = DayResult {
+                    member: star.member,
+                    name: self.name(star.member),
+                    part1: None,
+                    part2: None,
+                    delta: None,
+                    points: 0,
+                };
+                (result, star.rank)
+            });
+            match star.part {
+                1 => result.part1 = Some(star.elapsed),
+                _ => result.part2 = Some(star.elapsed),
+            }
+            result.points += star.points;
+            *rank = star.rank;
+        }
+
+        let mut results = results.into_values().collect::<Vec<_>>();
+        results.sort_by_key(|(result, rank)| (result.part2.is_none(), *rank));
+        results
+            .into_iter()
+            .map(|(result, _)| DayResult {
+                delta: result.part1.zip(result.part2).map(|(one, two)| two - one),
+                ..result
+            })
+            .collect()
+    }
+
+    /// The stars of `member` in the order they got them, with their running totals.
+    pub fn timeline(&self, member: u64) -> Vec<TimelineEntry> {
+        let mut own = self
+            .stars
+            .iter()
+            .filter(|star| star.member == member)
+            .collect::<Vec<_>>();
+        own.sort_by_key(|star| (star.timestamp, star.day, star.part));
+
+        let mut local_score = 0;
+        own.into_iter()
+            .enumerate()
+            .map(|(i, star)| {
+                local_score += star.points;
+                TimelineEntry {
+                    timestamp: star.timestamp,
+                    day: star.day,
+                    part: star.part,
+                    stars: i + 1,
+                    local_score,
+                }
+            })
+            .collect()
+    }
+
+    /// The member whose name or id is `name`.
+    pub fn find_member(&self, name: &str) -> Option<&Standing> {
+        self.standings
+            .iter()
+            .find(|standing| standing.name == name || standing.member.to_string() == name)
+    }
+}
+
+/// Unix time `day`'s puzzle of `year` unlocks.
+pub fn unlock_time(year: i32, day: u8) -> i64 {
+    days_from_civil(year, 12, u32::from(day)) * 86400 + UNLOCK_OFFSET_SECS
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    // Counts years from March, so the leap day is the last day of the year
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((i64::from(month) + 9) % 12) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The date and time of day of a unix time.
+fn civil_from_unix(timestamp: i64) -> (i64, u32, u32, i64) {
+    let (days, secs) = (timestamp.div_euclid(86400), timestamp.rem_euclid(86400));
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day, secs)
+}
+
+/// A unix time on the puzzles' clock, which is UTC-5, such as `2024-12-03 00:12:40`.
+pub fn format_timestamp(timestamp: i64) -> String {
+    let (year, month, day, secs) = civil_from_unix(timestamp - UNLOCK_OFFSET_SECS);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Seconds as `01:02:03`, with whole days in front once there are any, such as `2d 01:02:03`.
+pub fn format_duration(secs: i64) -> String {
+    let sign = if secs < 0 { "-" } else { "" };
+    let secs = secs.abs();
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    );
+    match secs / 86400 {
+        0 => format!("{sign}{clock}"),
+        days => format!("{sign}{days}d {clock}"),
+    }
+}
+
+fn format_optional(secs: Option<i64>) -> String {
+    secs.map_or_else(|| "-".to_string(), format_duration)
+}
+
+/// The members by recomputed local score.
+pub fn render_standings(analysis: &Analysis) -> String {
+    let mut out = format!(
+        "{:>4}  {:<30} {:>5} {:>6} {:>9}\n",
+        "rank", "member", "stars", "score", "reported"
+    );
+    for (i, standing) in analysis.standings.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>4}  {:<30} {:>5} {:>6} {:>9}",
+            i + 1,
+            standing.name,
+            standing.stars,
+            standing.local_score,
+            standing.reported_local_score
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// How everyone did on `day`, with the times since the puzzle unlocked.
+pub fn render_day(analysis: &Analysis, day: u8) -> String {
+    let mut out = format!(
+        "day {day:02}\n{:>4}  {:<30} {:>12} {:>12} {:>12} {:>6}\n",
+        "rank", "member", "part 1", "part 2", "delta", "points"
+    );
+    for (i, result) in analysis.day_ranking(day).iter().enumerate() {
+        writeln!(
+            out,
+            "{:>4}  {:<30} {:>12} {:>12} {:>12} {:>6}",
+            i + 1,
+            result.name,
+            format_optional(result.part1),
+            format_optional(result.part2),
+            format_optional(result.delta),
+            result.points
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// The stars of `member` in the order they got them.
+pub fn render_timeline(analysis: &Analysis, member: &Standing) -> String {
+    let mut out = format!(
+        "{}\n{:<19}  {:>3} {:>4} {:>5} {:>6}\n",
+        member.name, "time (UTC-5)", "day", "part", "stars", "score"
+    );
+    for entry in analysis.timeline(member.member) {
+        writeln!(
+            out,
+            "{:<19}  {:>3} {:>4} {:>5} {:>6}",
+            format_timestamp(entry.timestamp),
+            entry.day,
+            entry.part,
+            entry.stars,
+            entry.local_score
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Every star as a CSV row, by day, part and rank. Times are unix times and seconds.
+pub fn render_csv(analysis: &Analysis) -> String {
+    let mut out =
+        String::from("member_id,member,day,part,timestamp,elapsed_secs,rank,points,delta_secs\n");
+    for star in &analysis.stars {
+        let delta = match star.part {
+            2 => analysis
+                .stars
+                .iter()
+                .find(|one| one.member == star.member && one.day == star.day && one.part == 1)
+                .map(|one| (star.timestamp - one.timestamp).to_string()),
+            _ => None,
+        };
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            star.member,
+            csv_field(&analysis.name(star.member)),
+            star.day,
+            star.part,
+            star.timestamp,
+            star.elapsed,
+            star.rank,
+            star.points,
+            delta.unwrap_or_default()
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time() {
+        // 2024-12-01 05:00:00 UTC
+        assert_eq!(1733029200, unlock_time(2024, 1));
+        assert_eq!(1733029200 + 24 * 86400, unlock_time(2024, 25));
+        assert_eq!("2024-12-01 00:00:00", format_timestamp(1733029200));
+        assert_eq!("2024-11-30 23:59:59", format_timestamp(1733029199));
+        assert_eq!("2000-02-29 19:00:00", format_timestamp(951868800));
+
+        assert_eq!("00:00:59", format_duration(59));
+        assert_eq!("01:02:03", format_duration(3723));
+        assert_eq!("2d 00:00:01", format_duration(2 * 86400 + 1));
+        assert_eq!("-00:01:00", format_duration(-60));
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!("Ada", csv_field("Ada"));
+        assert_eq!("\"Lovelace, Ada\"", csv_field("Lovelace, Ada"));
+        assert_eq!("\"\"\"Ada\"\"\"", csv_field("\"Ada\""));
+    }
+}
@@ -0,0 +1,19 @@
+pub use aoc_common::{cancel, config};
+
+pub mod alloc;
+pub mod cache;
+pub mod calendar;
+pub mod dump;
+pub mod explain;
+pub mod leaderboard;
+pub mod params;
+pub mod puzzle;
+pub mod registry;
+pub mod repl;
+pub mod runner;
+
+#[cfg(feature = "tui")]
+pub mod tui;
+
+#[cfg(feature = "server")]
+pub mod server;
@@ -0,0 +1,114 @@
+use aoc_common::config::Config;
+use aoc_error::AocError;
+use miette::{miette, Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+/// Overrides for the puzzle parameters in [`Config`], as given on the command line or in a
+/// request.
+///
+/// Every field left out keeps the value of the configuration it is applied to.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Params {
+    /// Prize offset for day 13 part 2
+    pub prize_offset: Option<u64>,
+    /// Grid height for day 14
+    pub height: Option<u32>,
+    /// Grid width for day 14
+    pub width: Option<u32>,
+    /// Simulated seconds for day 14 part 1
+    pub steps: Option<usize>,
+    /// Seconds after which day 14 part 2 gives up
+    pub max_steps: Option<usize>,
+    /// Memory space size for day 18
+    pub size: Option<usize>,
+    /// Number of fallen bytes for day 18 part 1
+    pub num_bytes: Option<usize>,
+    /// Minimum number of picoseconds a cheat has to save for day 20
+    pub minimum_saving: Option<usize>,
+    /// Number of directional keypad robots for day 21 part 2
+    pub levels: Option<u8>,
+}
+
+impl Params {
+    /// Reads overrides given as `NAME=VALUE`, such as `height=7`.
+    pub fn from_assignments<'a>(
+        assignments: impl IntoIterator<Item = &'a str>,
+    ) -> miette::Result<Params> {
+        let mut object = serde_json::Map::new();
+        for assignment in assignments {
+            let (name, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| miette!("expected NAME=VALUE, got `{assignment}`"))?;
+            let value = serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+            object.insert(name.to_string(), value);
+        }
+
+        serde_json::from_value(object.into())
+            .into_diagnostic()
+            .context("invalid parameters")
+    }
+
+    /// Applies the overrides to `base` and validates the result, reporting a value out of range
+    /// as [`AocError::InvalidParameter`].
+    pub fn resolve(&self, base: &Config) -> Result<Config, AocError> {
+        let mut config = base.clone();
+        config.day13.prize_offset = self.prize_offset.unwrap_or(config.day13.prize_offset);
+        config.day14.height = self.height.unwrap_or(config.day14.height);
+        config.day14.width = self.width.unwrap_or(config.day14.width);
+        config.day14.steps = self.steps.unwrap_or(config.day14.steps);
+        config.day14.max_steps = self.max_steps.unwrap_or(config.day14.max_steps);
+        config.day18.size = self.size.unwrap_or(config.day18.size);
+        config.day18.num_bytes = self.num_bytes.unwrap_or(config.day18.num_bytes);
+        config.day20.minimum_saving = self.minimum_saving.unwrap_or(config.day20.minimum_saving);
+        config.day21.levels = self.levels.unwrap_or(config.day21.levels);
+
+        config
+            .validate()
+            .map_err(|err| AocError::invalid_parameter(err.to_string()))?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() -> miette::Result<()> {
+        let base: Config = "[day-14]\nheight = 7\nwidth = 11".parse()?;
+        let params = Params {
+            width: Some(13),
+            levels: Some(3),
+            ..Params::default()
+        };
+
+        let config = params.resolve(&base)?;
+        assert_eq!(7, config.day14.height);
+        assert_eq!(13, config.day14.width);
+        assert_eq!(3, config.day21.levels);
+
+        let params = Params {
+            height: Some(0),
+            ..Params::default()
+        };
+        assert!(matches!(
+            params.resolve(&base),
+            Err(AocError::InvalidParameter(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_assignments() -> miette::Result<()> {
+        let params = Params::from_assignments(["height=7", "levels=3"])?;
+        assert_eq!(Some(7), params.height);
+        assert_eq!(Some(3), params.levels);
+
+        assert!(Params::from_assignments(["height"]).is_err());
+        assert!(Params::from_assignments(["height=tall"]).is_err());
+        assert!(Params::from_assignments(["depth=3"]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,413 @@
+//! A day's puzzle description, saved as Markdown by `just get-input`, laid out for the terminal.
+//!
+//! Only the Markdown that [`aoc_common::article`] writes is understood: headings, paragraphs,
+//! fenced code, lists, `**strong**` and `*emphasis*`, code spans and links. Paragraphs are
+//! wrapped to the width, code blocks are kept as they are. Styled output uses ANSI escapes, the
+//! plain one keeps the emphasis and code markers so answers still stand out. Link targets are
+//! left out, they are mostly the input and other pages that need to be online anyway.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// The description in a day's directory.
+pub fn path(day_dir: &Path) -> PathBuf {
+    day_dir.join(aoc_common::article::PUZZLE_FILE)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    strong: bool,
+    emphasis: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn ansi(self) -> String {
+        let codes = [
+            (self.strong, "1"),
+            (self.emphasis, "3"),
+            (self.link, "4"),
+            (self.code, "36"),
+        ]
+        .into_iter()
+        .filter_map(|(on, code)| on.then_some(code))
+        .collect::<Vec<_>>();
+        match codes.is_empty() {
+            true => String::new(),
+            false => format!("\x1b[{}m", codes.join(";")),
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+/// The description laid out in lines of at most `width` columns, apart from code blocks and
+/// words that are longer.
+pub fn render(markdown: &str, width: usize, styled: bool) -> String {
+    let mut out = String::new();
+    let mut lines = markdown.lines().peekable();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+
+        if let Some(fence) = trimmed.strip_suffix(trimmed.trim_start_matches('`')) {
+            if fence.len() >= 3 {
+                let code = Style {
+                    code: true,
+                    ..Style::default()
+                };
+                for line in lines.by_ref() {
+                    if line.trim_end() == fence {
+                        break;
+                    }
+                    out.push_str("    ");
+                    push_run(&mut out, line, code, styled);
+                    out.push('\n');
+                }
+                continue;
+            }
+        }
+
+        if trimmed.starts_with('#') {
+            let text = trimmed.trim_start_matches('#').trim();
+            let heading = spans(text, styled)
+                .into_iter()
+                .map(|(c, style)| {
+                    (
+                        c,
+                        Style {
+                            strong: styled,
+                            ..style
+                        },
+                    )
+                })
+                .collect::<Vec<_>>();
+            wrap(&mut out, &heading, width, "", "", styled);
+        } else if trimmed == "---" {
+            out.push_str(&"─".repeat(width.min(40)));
+            out.push('\n');
+        } else if list_item(line).is_some() {
+            // Every line of a list is an item of its own
+            let mut next = Some(line);
+            while let Some((indent, marker, item)) = next.and_then(list_item) {
+                let indent = " ".repeat(indent + 2);
+                wrap(
+                    &mut out,
+                    &spans(item, styled),
+                    width,
+                    &format!("{indent}{marker} "),
+                    &format!("{indent}{} ", " ".repeat(marker.chars().count())),
+                    styled,
+                );
+                next = lines.next_if(|line| list_item(line).is_some());
+            }
+        } else {
+            let mut paragraph = trimmed.to_string();
+            while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+                paragraph.push('\n');
+                paragraph.push_str(line.trim());
+            }
+            wrap(&mut out, &spans(&paragraph, styled), width, "", "", styled);
+        }
+    }
+    out
+}
+
+/// The indentation, marker and text of a list item: `•` for a `- ` item, and the number for an
+/// `N. ` one.
+fn list_item(line: &str) -> Option<(usize, &str, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    if let Some(item) = trimmed.strip_prefix("- ") {
+        return Some((indent, "•", item));
+    }
+    let digits = trimmed.len()
+        - trimmed
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    let item = trimmed[digits..]
+        .strip_prefix(". ")
+        .filter(|_| digits > 0)?;
+    Some((indent, &trimmed[..=digits], item))
+}
+
+/// The characters of the inline Markdown with their style. Without styling, the markers for
+/// emphasis and code are kept as text too.
+fn spans(text: &str, styled: bool) -> Vec<(char, Style)> {
+    let spans = inline(text);
+    if styled {
+        return spans;
+    }
+
+    let mut plain = Vec::with_capacity(spans.len());
+    let mut previous = Style::default();
+    let markers = |plain: &mut Vec<_>, previous: Style, style: Style| {
+        let markers = [
+            (previous.code, style.code, "`"),
+            (previous.strong, style.strong, "**"),
+            (previous.emphasis, style.emphasis, "*"),
+        ];
+        // Closing markers go innermost first, opening ones outermost first
+        let closing = markers.iter().filter(|(before, now, _)| *before && !now);
+        let opening = markers
+            .iter()
+            .rev()
+            .filter(|(before, now, _)| !before && *now);
+        for (_, _, marker) in closing.chain(opening) {
+            plain.extend(marker.chars().map(|c| (c, Style::default())));
+        }
+    };
+    for (c, style) in spans {
+        markers(&mut plain, previous, style);
+        plain.push((c, style));
+        previous = style;
+    }
+    markers(&mut plain, previous, Style::default());
+    plain
+}
+
+/// Parses the inline Markdown, with `\n` standing for a hard line break.
+fn inline(text: &str) -> Vec<(char, Style)> {
+    let mut spans = Vec::with_capacity(text.len());
+    let mut style = Style::default();
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if chars.get(i + 1) == Some(&'\n') => {
+                spans.push(('\n', style));
+                i += 2;
+            }
+            '\\' if i + 1 < chars.len() => {
+                spans.push((chars[i + 1], style));
+                i += 2;
+            }
+            '\n' => {
+                spans.push((' ', style));
+                i += 1;
+            }
+            '`' => {
+                let fence = chars[i..].iter().take_while(|&&c| c == '`').count();
+                let start = i + fence;
+                let end = (start..chars.len()).find(|&end| {
+                    chars[end..].iter().take_while(|&&c| c == '`').count() == fence
+                        && chars.get(end.wrapping_sub(1)) != Some(&'`')
+                });
+                let Some(end) = end else {
+                    spans.extend(chars[i..start].iter().map(|&c| (c, style)));
+                    i = start;
+                    continue;
+                };
+                let mut code = &chars[start..end];
+                if code.len() > 1 && code[0] == ' ' && code[code.len() - 1] == ' ' {
+                    code = &code[1..code.len() - 1];
+                }
+                let code_style = Style {
+                    code: true,
+                    ..style
+                };
+                spans.extend(code.iter().map(|&c| (c, code_style)));
+                i = end + fence;
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                style.strong = !style.strong;
+                i += 2;
+            }
+            '*' => {
+                style.emphasis = !style.emphasis;
+                i += 1;
+            }
+            '[' => {
+                let close = chars[i..].iter().position(|&c| c == ']').map(|p| i + p);
+                let target = close
+                    .filter(|&close| chars.get(close + 1) == Some(&'('))
+                    .and_then(|close| {
+                        let end = chars[close..].iter().position(|&c| c == ')')?;
+                        Some((close, close + end))
+                    });
+                match target {
+                    Some((close, end)) => {
+                        let text = chars[i + 1..close].iter().collect::<String>();
+                        spans.extend(inline(&text).into_iter().map(|(c, inner)| {
+                            (
+                                c,
+                                Style {
+                                    link: true,
+                                    ..inner
+                                },
+                            )
+                        }));
+                        i = end + 1;
+                    }
+                    None => {
+                        spans.push(('[', style));
+                        i += 1;
+                    }
+                }
+            }
+            c => {
+                spans.push((c, style));
+                i += 1;
+            }
+        }
+    }
+    spans
+}
+
+/// Fills lines of at most `width` columns with the words of `spans`, starting the first one
+/// with `first` and the others with `rest`.
+fn wrap(
+    out: &mut String,
+    spans: &[(char, Style)],
+    width: usize,
+    first: &str,
+    rest: &str,
+    styled: bool,
+) {
+    let mut line = Vec::new();
+    let mut column = 0;
+    let mut prefix = first;
+    for (i, segment) in spans.split(|(c, _)| *c == '\n').enumerate() {
+        if i > 0 {
+            push_line(out, prefix, &mut line, styled);
+            prefix = rest;
+            column = 0;
+        }
+        // Code spans are kept on one line
+        let words = segment.split(|(c, style)| *c == ' ' && !style.code);
+        for word in words.filter(|word| !word.is_empty()) {
+            let start = match line.is_empty() {
+                true => prefix.chars().count(),
+                false => column + 1,
+            };
+            if !line.is_empty() && start + word.len() > width {
+                push_line(out, prefix, &mut line, styled);
+                prefix = rest;
+                column = prefix.chars().count() + word.len();
+            } else {
+                column = start + word.len();
+            }
+            line.push(word);
+        }
+    }
+    push_line(out, prefix, &mut line, styled);
+}
+
+fn push_line(out: &mut String, prefix: &str, words: &mut Vec<&[(char, Style)]>, styled: bool) {
+    out.push_str(prefix);
+    for (i, word) in words.drain(..).enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        for run in word.chunk_by(|a, b| a.1 == b.1) {
+            let text = run.iter().map(|(c, _)| c).collect::<String>();
+            push_run(out, &text, run[0].1, styled);
+        }
+    }
+    out.push('\n');
+}
+
+fn push_run(out: &mut String, text: &str, style: Style, styled: bool) {
+    let ansi = style.ansi();
+    if styled && !ansi.is_empty() {
+        let _ = write!(out, "{ansi}{text}{RESET}");
+    } else {
+        out.push_str(text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "## --- Day 2: Lantern Queue ---
+
+The lanterns are lined up in a queue\\_of\\_lanterns, each one labelled with a number:
+
+```
+7 6 4 2 1
+1 2 7 8 9
+```
+
+A queue is **bright** if the numbers only go down. Here, **`1`** queue is [bright](https://adventofcode.com/2024/day/2#bright).\\
+Queues never wrap around.
+
+- Queues are short.
+- A `*` marks a lantern that is out.
+
+**Which queues are bright?**
+";
+
+    #[test]
+    fn test_render_plain() {
+        assert_eq!(
+            "--- Day 2: Lantern Queue ---
+
+The lanterns are lined up in a
+queue_of_lanterns, each one labelled
+with a number:
+
+    7 6 4 2 1
+    1 2 7 8 9
+
+A queue is **bright** if the numbers
+only go down. Here, **`1`** queue is
+bright.
+Queues never wrap around.
+
+  • Queues are short.
+  • A `*` marks a lantern that is
+    out.
+
+**Which queues are bright?**
+",
+            render(PUZZLE, 36, false)
+        );
+    }
+
+    #[test]
+    fn test_render_styled() {
+        let styled = render(PUZZLE, 80, true);
+        assert!(styled.starts_with("\x1b[1m---\x1b[0m \x1b[1mDay\x1b[0m"));
+        assert!(styled.contains("    \x1b[36m7 6 4 2 1\x1b[0m\n"));
+        assert!(styled.contains("is \x1b[1mbright\x1b[0m if"));
+        assert!(styled.contains("Here, \x1b[1;36m1\x1b[0m queue is \x1b[4mbright\x1b[0m.\n"));
+        assert!(styled.contains("A \x1b[36m*\x1b[0m marks"));
+    }
+
+    #[test]
+    fn test_wrap_long_word() {
+        assert_eq!(
+            "a\nbbbbbbbbbbbb\nc d\n",
+            render("a bbbbbbbbbbbb c d", 5, false)
+        );
+        assert_eq!("a\n`b c`\nd\n", render("a `b c` d", 4, false));
+        assert_eq!(
+            "  • one two\n    three\n",
+            render("- one two three", 11, false)
+        );
+    }
+
+    #[test]
+    fn test_render_ordered_list() {
+        assert_eq!(
+            "Steps:\n\n  1. Turn the dial.\n  2. Press the\n     button.\n    • Twice.\n",
+            render(
+                "Steps:\n\n1. Turn the dial.\n2. Press the button.\n  - Twice.\n",
+                19,
+                false
+            )
+        );
+        // A number ending a sentence does not start a list
+        assert_eq!(
+            "It took 2. Then 3.\n",
+            render("It took\n2. Then 3.", 40, false)
+        );
+    }
+}
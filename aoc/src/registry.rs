@@ -0,0 +1,475 @@
+use crate::params::Params;
+use aoc_common::cancel::CancelToken;
+use aoc_common::config::Config;
+use aoc_common::input::normalize;
+use std::io::BufRead;
+
+pub type SolveFn = fn(&str, &Config, &CancelToken) -> miette::Result<String>;
+pub type SolveBothFn = fn(&str, &Config, &CancelToken) -> miette::Result<(String, String)>;
+pub type StreamFn = fn(&mut dyn BufRead) -> miette::Result<(String, String)>;
+
+/// A single solved puzzle part that can be run against an arbitrary input.
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    /// [`DEFAULT_IMPLEMENTATION`] or the name of an alternative way to solve the part
+    pub name: &'static str,
+    solve: SolveFn,
+}
+
+/// Name of the implementation [`find`] returns and the runner, service and bindings use.
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+impl Solution {
+    /// Solves with the default configuration, overridden by `params`.
+    pub fn solve(&self, input: &str, params: &Params) -> miette::Result<String> {
+        let config = params.resolve(&Config::default())?;
+        self.solve_cancellable(input, &config, &CancelToken::new())
+    }
+
+    /// Solves within the budget of `cancel`. Only the days with unbounded running time check
+    /// it, the others always run to completion.
+    ///
+    /// The input is normalised first, so line endings and trailing whitespace do not matter.
+    pub fn solve_cancellable(
+        &self,
+        input: &str,
+        config: &Config,
+        cancel: &CancelToken,
+    ) -> miette::Result<String> {
+        (self.solve)(&normalize(input), config, cancel)
+    }
+}
+
+/// Both parts of a day, solved together so the input is parsed only once.
+pub struct BothParts {
+    pub day: u8,
+    solve: SolveBothFn,
+}
+
+impl BothParts {
+    /// Solves with the default configuration, overridden by `params`.
+    pub fn solve(&self, input: &str, params: &Params) -> miette::Result<(String, String)> {
+        let config = params.resolve(&Config::default())?;
+        self.solve_cancellable(input, &config, &CancelToken::new())
+    }
+
+    /// Like [`Solution::solve_cancellable`], for both parts at once.
+    pub fn solve_cancellable(
+        &self,
+        input: &str,
+        config: &Config,
+        cancel: &CancelToken,
+    ) -> miette::Result<(String, String)> {
+        (self.solve)(&normalize(input), config, cancel)
+    }
+}
+
+/// Both parts of a day, solved from a reader without holding the whole input in memory.
+pub struct Streaming {
+    pub day: u8,
+    solve: StreamFn,
+}
+
+impl Streaming {
+    /// Solves the input `reader` gives, normalising it as it is read. Gives the same answers as
+    /// [`BothParts::solve`] on the whole input.
+    pub fn solve(&self, reader: &mut dyn BufRead) -> miette::Result<(String, String)> {
+        (self.solve)(reader)
+    }
+}
+
+macro_rules! solution {
+    ($day:literal, $part:literal, $name:literal, $($solve:tt)+) => {
+        Solution {
+            name: $name,
+            ..solution!($day, $part, $($solve)+)
+        }
+    };
+    ($day:literal, $part:literal, |$input:ident, $config:ident, $cancel:ident| $body:expr) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: DEFAULT_IMPLEMENTATION,
+            solve: |$input, $config, $cancel| Ok($body?),
+        }
+    };
+    ($day:literal, $part:literal, |$input:ident, $config:ident| $body:expr) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: DEFAULT_IMPLEMENTATION,
+            solve: |$input, $config, _cancel| Ok($body?),
+        }
+    };
+    ($day:literal, $part:literal, $process:path) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: DEFAULT_IMPLEMENTATION,
+            solve: |input, _config, _cancel| Ok($process(input)?),
+        }
+    };
+}
+
+// Day 24 part 2 was solved by inspecting the gate graph by hand and day 25 has no second part.
+static SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, day_01::part1::process),
+    solution!(1, 2, day_01::part2::process),
+    solution!(2, 1, day_02::part1::process),
+    solution!(2, 2, day_02::part2::process),
+    solution!(3, 1, day_03::part1::process),
+    solution!(3, 2, day_03::part2::process),
+    solution!(4, 1, day_04::part1::process),
+    solution!(4, 2, day_04::part2::process),
+    solution!(5, 1, day_05::part1::process),
+    solution!(5, 2, day_05::part2::process),
+    solution!(6, 1, day_06::part1::process),
+    solution!(6, 2, day_06::part2::process),
+    solution!(7, 1, day_07::part1::process),
+    solution!(7, 2, day_07::part2::process),
+    solution!(8, 1, day_08::part1::process),
+    solution!(8, 2, day_08::part2::process),
+    solution!(9, 1, day_09::part1::process),
+    solution!(9, 2, day_09::part2::process),
+    solution!(10, 1, |input, _config, cancel| {
+        day_10::part1::process_cancellable(input, cancel)
+    }),
+    solution!(10, 2, |input, _config, cancel| {
+        day_10::part2::process_cancellable(input, cancel)
+    }),
+    solution!(11, 1, day_11::part1::process),
+    solution!(11, 2, day_11::part2::process),
+    solution!(12, 1, day_12::part1::process),
+    solution!(12, 2, day_12::part2::process),
+    solution!(13, 1, day_13::part1::process),
+    solution!(13, 2, |input, config| day_13::part2::process(
+        input,
+        config.day13.prize_offset
+    )),
+    solution!(14, 1, |input, config| day_14::part1::process(
+        input,
+        config.day14.height,
+        config.day14.width,
+        config.day14.steps
+    )),
+    solution!(14, 2, |input, config, cancel| {
+        day_14::part2::process_cancellable(
+            input,
+            config.day14.height,
+            config.day14.width,
+            config.day14.max_steps,
+            cancel,
+        )
+    }),
+    solution!(15, 1, day_15::part1::process),
+    solution!(15, 2, day_15::part2::process),
+    solution!(16, 1, day_16::part1::process),
+    solution!(16, 2, day_16::part2::process),
+    solution!(17, 1, day_17::part1::process),
+    solution!(17, 2, day_17::part2::process),
+    solution!(18, 1, |input, config| day_18::part1::process(
+        input,
+        config.day18.size,
+        config.day18.num_bytes
+    )),
+    solution!(18, 2, |input, config| day_18::part2::process(
+        input,
+        config.day18.size
+    )),
+    solution!(19, 1, day_19::part1::process),
+    solution!(19, 2, day_19::part2::process),
+    solution!(20, 1, |input, config| day_20::part1::process(
+        input,
+        config.day20.minimum_saving
+    )),
+    solution!(20, 2, |input, config| day_20::part2::process(
+        input,
+        config.day20.minimum_saving
+    )),
+    solution!(21, 1, day_21::part1::process),
+    solution!(21, 2, |input, config| day_21::part2::process(
+        input,
+        config.day21.levels
+    )),
+    solution!(22, 1, day_22::part1::process),
+    solution!(22, 2, day_22::part2::process),
+    solution!(23, 1, |input, _config, cancel| {
+        day_23::part1::process_cancellable(input, cancel)
+    }),
+    solution!(23, 2, |input, _config, cancel| {
+        day_23::part2::process_cancellable(input, cancel)
+    }),
+    solution!(24, 1, day_24::part1::process),
+    solution!(25, 1, day_25::part1::process),
+];
+
+// Competing implementations, cross-validated against the default ones by the tests
+static ALTERNATIVES: &[Solution] = &[
+    solution!(13, 1, "cramer-i64", |input, _config| {
+        day_13::model::parse(input)
+            .map(|arcade| day_13::part1::total_tokens_cramer(&arcade).to_string())
+    }),
+    solution!(13, 1, "lu-f64", |input, _config| {
+        day_13::model::parse(input)
+            .map(|arcade| day_13::part1::total_tokens_lu_f64(&arcade).to_string())
+    }),
+    solution!(13, 2, "cramer-i64", |input, config| {
+        let prize_offset = config.day13.prize_offset;
+        day_13::model::check_prize_offset(prize_offset)?;
+        day_13::model::parse(input)
+            .map(|arcade| day_13::part2::total_tokens_cramer(&arcade, prize_offset).to_string())
+    }),
+    solution!(13, 2, "lu-f64", |input, config| {
+        let prize_offset = config.day13.prize_offset;
+        day_13::model::check_prize_offset(prize_offset)?;
+        day_13::model::parse(input)
+            .map(|arcade| day_13::part2::total_tokens_lu_f64(&arcade, prize_offset).to_string())
+    }),
+    solution!(14, 2, "crt", |input, config| {
+        day_14::model::parse(input).and_then(|robots| {
+            day_14::part2::solve_crt(
+                &robots,
+                config.day14.height,
+                config.day14.width,
+                config.day14.max_steps,
+            )
+        })
+    }),
+    solution!(23, 2, "bron-kerbosch", |input, _config, cancel| {
+        day_23::model::parse(input).and_then(|network| {
+            Ok(day_23::part2::largest_clique_bron_kerbosch(&network, cancel)?.join(","))
+        })
+    }),
+];
+
+macro_rules! both {
+    ($day:literal, |$input:ident, $config:ident, $cancel:ident| $body:expr) => {
+        BothParts {
+            day: $day,
+            solve: |$input, $config, $cancel| Ok($body?),
+        }
+    };
+    ($day:literal, |$input:ident, $config:ident| $body:expr) => {
+        BothParts {
+            day: $day,
+            solve: |$input, $config, _cancel| Ok($body?),
+        }
+    };
+    ($day:literal, $solve_both:path) => {
+        BothParts {
+            day: $day,
+            solve: |input, _config, _cancel| Ok($solve_both(input)?),
+        }
+    };
+}
+
+// Days 24 and 25 have a single registered part, so there is nothing to combine.
+static BOTH_PARTS: &[BothParts] = &[
+    both!(1, day_01::solve_both),
+    both!(2, day_02::solve_both),
+    both!(3, day_03::solve_both),
+    both!(4, day_04::solve_both),
+    both!(5, day_05::solve_both),
+    both!(6, day_06::solve_both),
+    both!(7, day_07::solve_both),
+    both!(8, day_08::solve_both),
+    both!(9, day_09::solve_both),
+    both!(10, |input, _config, cancel| {
+        day_10::solve_both_cancellable(input, cancel)
+    }),
+    both!(11, day_11::solve_both),
+    both!(12, day_12::solve_both),
+    both!(13, |input, config| day_13::solve_both(
+        input,
+        config.day13.prize_offset
+    )),
+    both!(14, |input, config, cancel| {
+        day_14::solve_both_cancellable(
+            input,
+            config.day14.height,
+            config.day14.width,
+            config.day14.steps,
+            config.day14.max_steps,
+            cancel,
+        )
+    }),
+    both!(15, day_15::solve_both),
+    both!(16, day_16::solve_both),
+    both!(17, day_17::solve_both),
+    both!(18, |input, config| day_18::solve_both(
+        input,
+        config.day18.size,
+        config.day18.num_bytes
+    )),
+    both!(19, day_19::solve_both),
+    both!(20, |input, config| day_20::solve_both(
+        input,
+        config.day20.minimum_saving
+    )),
+    both!(21, |input, config| day_21::solve_both(
+        input,
+        config.day21.levels
+    )),
+    both!(22, day_22::solve_both),
+    both!(23, |input, _config, cancel| {
+        day_23::solve_both_cancellable(input, cancel)
+    }),
+];
+
+macro_rules! streaming {
+    ($day:literal, $solve_both:path) => {
+        Streaming {
+            day: $day,
+            solve: |reader| Ok($solve_both(reader)?),
+        }
+    };
+}
+
+// The line-oriented days, whose answers can be added up as the input goes by.
+static STREAMING: &[Streaming] = &[
+    streaming!(1, day_01::stream::solve_both),
+    streaming!(2, day_02::stream::solve_both),
+    streaming!(3, day_03::stream::solve_both),
+    streaming!(7, day_07::stream::solve_both),
+    streaming!(11, day_11::stream::solve_both),
+    streaming!(19, day_19::stream::solve_both),
+    streaming!(22, day_22::stream::solve_both),
+];
+
+pub fn solutions() -> &'static [Solution] {
+    SOLUTIONS
+}
+
+pub fn find(day: u8, part: u8) -> Option<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day == day && solution.part == part)
+}
+
+/// Every implementation of a part, the default one first.
+pub fn implementations(day: u8, part: u8) -> impl Iterator<Item = &'static Solution> {
+    SOLUTIONS
+        .iter()
+        .chain(ALTERNATIVES)
+        .filter(move |solution| solution.day == day && solution.part == part)
+}
+
+/// The implementation of a part called `name`.
+pub fn find_implementation(day: u8, part: u8, name: &str) -> Option<&'static Solution> {
+    implementations(day, part).find(|solution| solution.name == name)
+}
+
+/// The combined solver for `day`, if it has both parts.
+pub fn find_both(day: u8) -> Option<&'static BothParts> {
+    BOTH_PARTS.iter().find(|both| both.day == day)
+}
+
+/// The streaming solver for `day`, if it has one.
+pub fn find_streaming(day: u8) -> Option<&'static Streaming> {
+    STREAMING.iter().find(|streaming| streaming.day == day)
+}
+
+/// Every day with a streaming solver.
+pub fn streaming() -> &'static [Streaming] {
+    STREAMING
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert!(find(1, 1).is_some());
+        assert!(find(24, 2).is_none());
+        assert!(find(25, 2).is_none());
+        assert!(find(26, 1).is_none());
+    }
+
+    #[test]
+    fn test_implementations() {
+        let names = |day, part| {
+            implementations(day, part)
+                .map(|solution| solution.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![DEFAULT_IMPLEMENTATION], names(1, 1));
+        assert_eq!(
+            vec![DEFAULT_IMPLEMENTATION, "cramer-i64", "lu-f64"],
+            names(13, 1)
+        );
+        assert!(find_implementation(14, 2, "crt").is_some());
+        assert!(find_implementation(23, 2, "bron-kerbosch").is_some());
+        assert!(find_implementation(23, 2, "lu-f64").is_none());
+    }
+
+    #[test]
+    fn test_find_both() {
+        assert!(find_both(1).is_some());
+        assert!(find_both(24).is_none());
+        assert!(find_both(25).is_none());
+    }
+
+    #[test]
+    fn test_find_streaming() -> miette::Result<()> {
+        assert!(find_streaming(4).is_none());
+        let input = "3   4\r\n4   3\r\n2   5\r\n1   3\r\n3   9\r\n3   3\r\n";
+        assert_eq!(
+            find_both(1).unwrap().solve(input, &Params::default())?,
+            find_streaming(1).unwrap().solve(&mut input.as_bytes())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_both_matches_parts() -> miette::Result<()> {
+        let input = "3   4\r\n4   3\r\n2   5\r\n1   3\r\n3   9\r\n3   3\r\n";
+        let params = Params::default();
+        assert_eq!(
+            (
+                find(1, 1).unwrap().solve(input, &params)?,
+                find(1, 2).unwrap().solve(input, &params)?
+            ),
+            find_both(1).unwrap().solve(input, &params)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_with_params() -> miette::Result<()> {
+        let input = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+        let params = Params {
+            height: Some(7),
+            width: Some(11),
+            ..Params::default()
+        };
+        assert_eq!("12", find(14, 1).unwrap().solve(input, &params)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let report = find(23, 2)
+            .unwrap()
+            .solve_cancellable("kh-tc\nqp-kh", &Config::default(), &cancel)
+            .unwrap_err();
+        assert_eq!(
+            Some("aoc::timeout".to_string()),
+            report.code().map(|code| code.to_string())
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! An interactive session on one day's input, for exploring the puzzle parameters without
+//! rebuilding a binary for every change.
+//!
+//! The input is read and parsed once when it is loaded, so mistakes in it show up right away.
+//! Every `run` solves from the loaded text with the current parameters.
+
+use crate::calendar;
+use crate::config::Config;
+use crate::dump::{self, Dump};
+use crate::params::Params;
+use crate::registry;
+use crate::runner::{self, Runner};
+use aoc_common::input;
+use miette::{miette, Context, IntoDiagnostic};
+use std::fmt::Write;
+use std::path::Path;
+
+pub const HELP: &str = "\
+commands:
+  run [1|2]          solve both parts or one, with timing
+  set NAME=VALUE...  change puzzle parameters, such as `set height=7 width=11`
+  params             show the day's parameters
+  dump               print the parsed model as JSON
+  grid               render the input, or the state the parameters describe, as a grid
+  load [PATH]        read the input again, from PATH or the day's part 1 input
+  help               show this help
+  quit               leave the session";
+
+/// The days `grid` can draw.
+pub const GRID_DAYS: &[u8] = &[4, 6, 8, 10, 12, 14, 15, 16, 18, 20];
+
+/// What the session answers to a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Output(String),
+    Quit,
+}
+
+/// One day's loaded input together with the parameters to solve it with.
+pub struct Session {
+    day: u8,
+    input: String,
+    model: Dump,
+    config: Config,
+    runner: Runner,
+}
+
+impl Session {
+    /// Starts a session on `day` with `input`, which has to parse.
+    pub fn new(day: u8, input: String, config: Config) -> miette::Result<Self> {
+        if registry::solutions()
+            .iter()
+            .all(|solution| solution.day != day)
+        {
+            return Err(miette!("no solution for day {day}"));
+        }
+        let model = parse(day, &input)?;
+
+        Ok(Session {
+            day,
+            input,
+            model,
+            config,
+            runner: Runner::new(None),
+        })
+    }
+
+    /// Runs one command line. Blank lines and `#` comments do nothing.
+    pub fn execute(&mut self, line: &str) -> miette::Result<Reply> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next().filter(|word| !word.starts_with('#')) else {
+            return Ok(Reply::Output(String::new()));
+        };
+        let args = words.collect::<Vec<_>>();
+
+        let output = match (command, args.as_slice()) {
+            ("run", []) => self.run(None)?,
+            ("run", [part]) => {
+                let part = part
+                    .parse()
+                    .map_err(|_| miette!("expected part 1 or 2, got `{part}`"))?;
+                self.run(Some(part))?
+            }
+            ("set", [_, ..]) => {
+                let params = Params::from_assignments(args.iter().copied())?;
+                self.config = params.resolve(&self.config)?;
+                self.params()
+            }
+            ("params", []) => self.params(),
+            ("dump", []) => serde_json::to_string_pretty(&self.model.json).into_diagnostic()?,
+            ("grid", []) => self.grid()?,
+            ("load", []) => self.reload()?,
+            ("load", [path]) => self.load(Path::new(path))?,
+            ("help", []) => HELP.to_string(),
+            ("quit" | "exit", []) => return Ok(Reply::Quit),
+            _ => return Err(miette!("unknown command `{}`, try `help`", line.trim())),
+        };
+        Ok(Reply::Output(output))
+    }
+
+    fn run(&self, part: Option<u8>) -> miette::Result<String> {
+        let solutions = registry::solutions()
+            .iter()
+            .filter(|solution| solution.day == self.day && part.is_none_or(|p| solution.part == p))
+            .collect::<Vec<_>>();
+        if solutions.is_empty() {
+            return Err(miette!(
+                "no part {} for day {}",
+                part.unwrap_or(1),
+                self.day
+            ));
+        }
+
+        let mut output = String::new();
+        for solution in solutions {
+            let outcome = self
+                .runner
+                .run(solution, &self.input, &self.config)
+                .with_context(|| format!("part {}", solution.part))?;
+            writeln!(
+                output,
+                "part {}: {}  ({})",
+                solution.part,
+                outcome.answer,
+                calendar::format_stats(
+                    outcome.elapsed.as_secs_f64() * 1000.0,
+                    outcome.allocations.as_ref(),
+                    outcome.shared
+                )
+            )
+            .unwrap();
+        }
+        Ok(output.trim_end().to_string())
+    }
+
+    fn params(&self) -> String {
+        match self.config.section(self.day) {
+            Some(section) => section.trim_end().to_string(),
+            None => format!("day {} takes no parameters", self.day),
+        }
+    }
+
+    /// The input, or the state the parameters describe, drawn as a grid, for the
+    /// [`GRID_DAYS`].
+    pub fn grid(&self) -> miette::Result<String> {
+        let grid = match self.day {
+            14 => {
+                let day14 = &self.config.day14;
+                let mut robots = day_14::model::parse(&self.input)?;
+                for robot in robots.0.iter_mut() {
+                    robot.position = robot.position_after(day14.steps, day14.height, day14.width);
+                }
+                day_14::part2::draw(&robots, day14.height, day14.width)
+            }
+            18 => day_18::model::parse(&self.input)?
+                .draw(self.config.day18.size, self.config.day18.num_bytes),
+            // The other days' inputs are grids already
+            day if GRID_DAYS.contains(&day) => self.model.printed.trim_end().to_string(),
+            day => return Err(miette!("day {day} has no grid")),
+        };
+        Ok(grid)
+    }
+
+    fn load(&mut self, path: &Path) -> miette::Result<String> {
+        let input = input::read(path).with_context(|| format!("read {}", path.display()))?;
+        self.replace_input(input)?;
+        Ok(format!("loaded {}", path.display()))
+    }
+
+    fn reload(&mut self) -> miette::Result<String> {
+        let input = runner::read_input(self.day, 1)
+            .with_context(|| format!("read the day {} input", self.day))?;
+        self.replace_input(input)?;
+        Ok(format!("loaded the day {} input", self.day))
+    }
+
+    fn replace_input(&mut self, input: String) -> miette::Result<()> {
+        self.model = parse(self.day, &input)?;
+        self.input = input;
+        Ok(())
+    }
+}
+
+fn parse(day: u8, input: &str) -> miette::Result<Dump> {
+    dump::dump(day, input)
+        .ok_or_else(|| miette!("no model for day {day}"))?
+        .with_context(|| format!("parse the day {day} input"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: &str = "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2\n5,5
+2,5\n6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0\n";
+
+    fn output(session: &mut Session, line: &str) -> miette::Result<String> {
+        match session.execute(line)? {
+            Reply::Output(output) => Ok(output),
+            Reply::Quit => panic!("`{line}` should not quit"),
+        }
+    }
+
+    #[test]
+    fn test_session() -> miette::Result<()> {
+        let mut session = Session::new(18, BYTES.to_string(), Config::default())?;
+
+        assert_eq!(
+            "size = 7\nnum_bytes = 12",
+            output(&mut session, "set size=7 num_bytes=12")?
+        );
+        assert!(output(&mut session, "run")?.starts_with("part 1: 22  ("));
+        assert!(output(&mut session, "run 2")?.starts_with("part 2: 6,1  ("));
+        // The path is not unique, so only the bytes are compared
+        assert_eq!(
+            "...#...\n..#..#.\n....#..\n...#..#\n..#..#.\n.#..#..\n#.#....",
+            output(&mut session, "grid")?.replace('O', ".")
+        );
+        let dump: serde_json::Value =
+            serde_json::from_str(&output(&mut session, "dump")?).into_diagnostic()?;
+        assert_eq!(serde_json::json!([5, 4]), dump[0]);
+        assert_eq!("", output(&mut session, "# comment")?);
+        assert_eq!(Reply::Quit, session.execute("quit")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() -> miette::Result<()> {
+        let mut session = Session::new(1, "3   4\n4   3".to_string(), Config::default())?;
+        assert!(session.execute("run 3").is_err());
+        assert!(session.execute("set height").is_err());
+        assert!(session.execute("grid").is_err());
+        assert!(session.execute("fly").is_err());
+        assert_eq!("day 1 takes no parameters", output(&mut session, "params")?);
+
+        assert!(Session::new(1, "3 x".to_string(), Config::default()).is_err());
+        assert!(Session::new(26, String::new(), Config::default()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use crate::alloc::{self, AllocStats};
+use crate::cache::{self, Cache, CacheEntry};
+use crate::cancel::CancelToken;
+use crate::config::Config;
+use crate::registry::{BothParts, Solution};
+use aoc_common::input::{self, normalize, InputError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Root of the workspace the runner was built in.
+pub fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// The crate of `day`, which holds its puzzle input.
+pub fn day_dir(day: u8) -> PathBuf {
+    workspace_dir().join(format!("day-{day:02}"))
+}
+
+/// The puzzle input the day's binary for `part` reads, decrypted if it is sealed.
+pub fn read_input(day: u8, part: u8) -> Result<String, InputError> {
+    input::load(day_dir(day), part)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub answer: String,
+    /// Time the solve took, or took originally if the answer came from the cache
+    pub elapsed: Duration,
+    /// Allocations of the solve, if the binary counts them
+    pub allocations: Option<AllocStats>,
+    /// Whether the time and allocations are of both parts solved together
+    pub shared: bool,
+    pub cached: bool,
+}
+
+/// Runs solutions, answering from the result cache where possible.
+#[derive(Debug, Clone, Default)]
+pub struct Runner {
+    cache: Option<Cache>,
+    timeout: Option<Duration>,
+}
+
+impl Runner {
+    /// Creates a runner that uses `cache`, or always solves if it is `None`.
+    pub fn new(cache: Option<Cache>) -> Self {
+        Runner {
+            cache,
+            timeout: None,
+        }
+    }
+
+    /// Gives every solve a budget of `timeout`, after which cancellable solvers give up. The
+    /// budget is advisory for the others, they always run to completion.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn run(
+        &self,
+        solution: &Solution,
+        input: &str,
+        config: &Config,
+    ) -> miette::Result<Outcome> {
+        // Variants of the same input share a cache entry
+        let input = normalize(input);
+        let input = input.as_ref();
+
+        let key = self.key(solution.day, solution.part, config, input);
+        if let Some(outcome) = self.lookup(key.as_deref()) {
+            return Ok(outcome);
+        }
+
+        let cancel = self.cancel_token();
+        let start = Instant::now();
+        let (answer, allocations) =
+            alloc::measure(|| solution.solve_cancellable(input, config, &cancel));
+        let outcome = Outcome {
+            answer: answer?,
+            elapsed: start.elapsed(),
+            allocations,
+            shared: false,
+            cached: false,
+        };
+
+        self.store(key.as_deref(), solution.day, solution.part, &outcome);
+        Ok(outcome)
+    }
+
+    /// Runs both parts of a day, parsing the input once unless both answers are cached.
+    ///
+    /// A combined solve cannot tell the parts apart, so both outcomes carry the time and
+    /// allocations of the whole run, marked as [`Outcome::shared`] so they are shown once for
+    /// both parts rather than counted for each.
+    pub fn run_both(
+        &self,
+        both: &BothParts,
+        input: &str,
+        config: &Config,
+    ) -> miette::Result<[Outcome; 2]> {
+        let input = normalize(input);
+        let input = input.as_ref();
+
+        let keys = [1, 2].map(|part| self.key(both.day, part, config, input));
+        if let [Some(part1), Some(part2)] = keys.each_ref().map(|key| self.lookup(key.as_deref())) {
+            return Ok([part1, part2]);
+        }
+
+        let cancel = self.cancel_token();
+        let start = Instant::now();
+        let (answers, allocations) =
+            alloc::measure(|| both.solve_cancellable(input, config, &cancel));
+        let elapsed = start.elapsed();
+        let (part1, part2) = answers?;
+
+        let outcomes = [part1, part2].map(|answer| Outcome {
+            answer,
+            elapsed,
+            allocations,
+            shared: true,
+            cached: false,
+        });
+        for (part, (key, outcome)) in (1..).zip(keys.iter().zip(&outcomes)) {
+            self.store(key.as_deref(), both.day, part, outcome);
+        }
+        Ok(outcomes)
+    }
+
+    fn key(&self, day: u8, part: u8, config: &Config, input: &str) -> Option<String> {
+        self.cache.as_ref()?;
+        Cache::key(day, part, config, input)
+    }
+
+    fn lookup(&self, key: Option<&str>) -> Option<Outcome> {
+        let entry = self.cache.as_ref()?.get(key?)?;
+        Some(Outcome {
+            answer: entry.answer,
+            elapsed: Duration::from_secs_f64(entry.elapsed_ms / 1000.0),
+            allocations: entry.allocations,
+            shared: entry.shared,
+            cached: true,
+        })
+    }
+
+    fn store(&self, key: Option<&str>, day: u8, part: u8, outcome: &Outcome) {
+        let (Some(cache), Some(key)) = (self.cache.as_ref(), key) else {
+            return;
+        };
+        let entry = CacheEntry {
+            day,
+            part,
+            source_hash: cache::source_hash(day).unwrap_or_default().to_string(),
+            answer: outcome.answer.clone(),
+            elapsed_ms: outcome.elapsed.as_secs_f64() * 1000.0,
+            allocations: outcome.allocations,
+            shared: outcome.shared,
+        };
+        // A cache that cannot be written only costs time, so it does not fail the run
+        if let Err(err) = cache.put(key, &entry) {
+            tracing::warn!("could not write {}: {err}", cache.dir().display());
+        }
+    }
+
+    fn cancel_token(&self) -> CancelToken {
+        self.timeout
+            .map_or_else(CancelToken::new, CancelToken::with_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_run_cached() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Runner::new(Some(Cache::new(dir.path())));
+        let solution = registry::find(1, 1).unwrap();
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+        let first = runner.run(solution, input, &Config::default())?;
+        assert_eq!("11", first.answer);
+        assert!(!first.cached);
+
+        let second = runner.run(solution, input, &Config::default())?;
+        assert_eq!("11", second.answer);
+        assert!(second.cached);
+
+        let uncached = Runner::new(None).run(solution, input, &Config::default())?;
+        assert!(!uncached.cached);
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_both_cached() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Runner::new(Some(Cache::new(dir.path())));
+        let both = registry::find_both(1).unwrap();
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+        let [part1, part2] = runner.run_both(both, input, &Config::default())?;
+        assert_eq!(("11", "31"), (part1.answer.as_str(), part2.answer.as_str()));
+        assert!(!part1.cached && !part2.cached);
+        assert!(part1.shared && part2.shared);
+
+        // The combined run fills the same entries as the single parts
+        let single = runner.run(registry::find(1, 2).unwrap(), input, &Config::default())?;
+        assert_eq!("31", single.answer);
+        assert!(single.cached && single.shared);
+
+        let [part1, part2] = runner.run_both(both, input, &Config::default())?;
+        assert!(part1.cached && part2.cached);
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeout() {
+        let runner = Runner::new(None).with_timeout(Duration::ZERO);
+        let solution = registry::find(14, 2).unwrap();
+        let mut config = Config::default();
+        config.day14.height = 7;
+        config.day14.width = 11;
+
+        let report = runner.run(solution, "p=0,0 v=1,1", &config).unwrap_err();
+        assert_eq!(
+            Some("aoc::timeout".to_string()),
+            report.code().map(|code| code.to_string())
+        );
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Runner::new(Some(Cache::new(dir.path())));
+        let solution = registry::find(16, 1).unwrap();
+
+        assert!(runner
+            .run(solution, "#####\n#...#\n#####", &Config::default())
+            .is_err());
+        assert_eq!(0, Cache::new(dir.path()).gc().unwrap().kept);
+    }
+}
@@ -0,0 +1,449 @@
+use crate::cancel::CancelToken;
+use crate::config::Config;
+use crate::params::Params;
+use crate::registry;
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use miette::{Diagnostic, NarratableReportHandler};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Largest accepted request body in bytes
+    pub max_input_bytes: usize,
+    /// Wall-clock budget for a single solve. Only the solvers that check their
+    /// [`CancelToken`] stop once it is spent, the others run on after the response.
+    pub timeout: Duration,
+    /// Most solves running at once, counting the ones still running past their budget
+    pub max_solves: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            max_input_bytes: 1024 * 1024,
+            timeout: Duration::from_secs(30),
+            max_solves: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ServerState {
+    config: ServerConfig,
+    /// A permit for every solve that may run
+    solves: Arc<Semaphore>,
+}
+
+/// JSON request body, as an alternative to posting the bare puzzle input.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SolveRequest {
+    pub input: String,
+    #[serde(default)]
+    pub params: Params,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DiagnosticReport {
+    pub code: Option<String>,
+    pub message: String,
+    pub help: Option<String>,
+    pub rendered: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SolveResponse {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed_ms: f64,
+    pub diagnostics: Vec<DiagnosticReport>,
+}
+
+impl DiagnosticReport {
+    fn from_diagnostic(diagnostic: &dyn Diagnostic) -> Self {
+        let mut rendered = String::new();
+        if NarratableReportHandler::new()
+            .render_report(&mut rendered, diagnostic)
+            .is_err()
+        {
+            rendered = diagnostic.to_string();
+        }
+
+        DiagnosticReport {
+            code: diagnostic.code().map(|code| code.to_string()),
+            message: diagnostic.to_string(),
+            help: diagnostic.help().map(|help| help.to_string()),
+            rendered,
+        }
+    }
+
+    fn service(code: &str, message: String) -> Self {
+        DiagnosticReport {
+            code: Some(code.to_string()),
+            rendered: message.clone(),
+            message,
+            help: None,
+        }
+    }
+}
+
+pub fn router(config: ServerConfig) -> Router {
+    Router::new()
+        .route("/solve/{day}/{part}", post(solve))
+        .layer(DefaultBodyLimit::max(config.max_input_bytes))
+        .with_state(ServerState {
+            solves: Arc::new(Semaphore::new(config.max_solves)),
+            config,
+        })
+}
+
+async fn solve(
+    State(ServerState { config, solves }): State<ServerState>,
+    Path((day, part)): Path<(u8, u8)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<SolveResponse>) {
+    let failure = |status, code, message: String| {
+        (
+            status,
+            Json(SolveResponse {
+                day,
+                part,
+                answer: None,
+                elapsed_ms: 0.0,
+                diagnostics: vec![DiagnosticReport::service(code, message)],
+            }),
+        )
+    };
+
+    let Some(solution) = registry::find(day, part) else {
+        return failure(
+            StatusCode::NOT_FOUND,
+            "aoc::unknown_solution",
+            format!("No solution for day {day} part {part}"),
+        );
+    };
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+
+    let request = if is_json {
+        match serde_json::from_slice::<SolveRequest>(&body) {
+            Ok(request) => request,
+            Err(err) => {
+                return failure(
+                    StatusCode::BAD_REQUEST,
+                    "aoc::invalid_request",
+                    err.to_string(),
+                )
+            }
+        }
+    } else {
+        match String::from_utf8(body.to_vec()) {
+            Ok(input) => SolveRequest {
+                input,
+                params: Params::default(),
+            },
+            Err(err) => {
+                return failure(
+                    StatusCode::BAD_REQUEST,
+                    "aoc::invalid_request",
+                    err.to_string(),
+                )
+            }
+        }
+    };
+
+    let puzzle_config = match request.params.resolve(&Config::default()) {
+        Ok(puzzle_config) => puzzle_config,
+        Err(err) => {
+            return failure(
+                StatusCode::BAD_REQUEST,
+                "aoc::invalid_parameter",
+                err.to_string(),
+            )
+        }
+    };
+
+    // The solvers are synchronous, so they run on the blocking pool. Cancellable solvers stop
+    // once the budget is spent. The others are reported as timed out, but their thread keeps
+    // running until `process` returns, and holds on to its permit until then.
+    let Ok(permit) = solves.try_acquire_owned() else {
+        return failure(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "aoc::busy",
+            format!(
+                "All {} solves are taken, try again later",
+                config.max_solves
+            ),
+        );
+    };
+    let start = Instant::now();
+    let cancel = CancelToken::with_timeout(config.timeout);
+    let task = tokio::task::spawn_blocking({
+        let cancel = cancel.clone();
+        move || {
+            let _permit = permit;
+            solution.solve_cancellable(&request.input, &puzzle_config, &cancel)
+        }
+    });
+
+    let result = tokio::time::timeout(config.timeout, task).await;
+    cancel.cancel();
+
+    let (status, answer, diagnostics) = match result {
+        Ok(Ok(Ok(answer))) => (StatusCode::OK, Some(answer), Vec::new()),
+        Ok(Ok(Err(report))) => {
+            let status = match report.code().map(|code| code.to_string()).as_deref() {
+                Some("aoc::timeout") => StatusCode::GATEWAY_TIMEOUT,
+                Some("aoc::invalid_parameter") => StatusCode::BAD_REQUEST,
+                Some("aoc::internal") => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::UNPROCESSABLE_ENTITY,
+            };
+            (
+                status,
+                None,
+                vec![DiagnosticReport::from_diagnostic(report.as_ref())],
+            )
+        }
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            None,
+            vec![DiagnosticReport::service("aoc::panic", err.to_string())],
+        ),
+        Err(_) => (
+            StatusCode::GATEWAY_TIMEOUT,
+            None,
+            vec![DiagnosticReport::service(
+                "aoc::timeout",
+                format!("Solve exceeded {:?}", config.timeout),
+            )],
+        ),
+    };
+
+    (
+        status,
+        Json(SolveResponse {
+            day,
+            part,
+            answer,
+            elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+            diagnostics,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn send(
+        config: ServerConfig,
+        uri: &str,
+        content_type: &str,
+        body: impl Into<Body>,
+    ) -> (StatusCode, Option<SolveResponse>) {
+        let response = router(config)
+            .oneshot(
+                Request::post(uri)
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(body.into())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).ok())
+    }
+
+    #[tokio::test]
+    async fn test_solve_plain_input() {
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+        let (status, response) =
+            send(ServerConfig::default(), "/solve/1/1", "text/plain", input).await;
+
+        assert_eq!(StatusCode::OK, status);
+        let response = response.unwrap();
+        assert_eq!(Some("11".to_string()), response.answer);
+        assert!(response.diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_solve_json_params() {
+        let input = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+        let body = serde_json::json!({ "input": input, "params": { "minimum_saving": 50 } });
+        let (status, response) = send(
+            ServerConfig::default(),
+            "/solve/20/2",
+            "application/json",
+            body.to_string(),
+        )
+        .await;
+
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(Some("285".to_string()), response.unwrap().answer);
+    }
+
+    #[tokio::test]
+    async fn test_solve_renders_diagnostics() {
+        let (status, response) = send(
+            ServerConfig::default(),
+            "/solve/16/1",
+            "text/plain",
+            "#####\n#...#\n#####",
+        )
+        .await;
+
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
+        let diagnostics = response.unwrap().diagnostics;
+        assert_eq!(
+            Some("aoc::malformed_input".to_string()),
+            diagnostics[0].code
+        );
+        assert_eq!("No starting position", diagnostics[0].message);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_solution() {
+        let (status, response) =
+            send(ServerConfig::default(), "/solve/25/2", "text/plain", "").await;
+
+        assert_eq!(StatusCode::NOT_FOUND, status);
+        assert_eq!(
+            Some("aoc::unknown_solution".to_string()),
+            response.unwrap().diagnostics[0].code
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_param() {
+        let body = serde_json::json!({ "input": "", "params": { "robots": 5 } });
+        let (status, _) = send(
+            ServerConfig::default(),
+            "/solve/14/1",
+            "application/json",
+            body.to_string(),
+        )
+        .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, status);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_param() {
+        let body = serde_json::json!({ "input": "", "params": { "height": 0 } });
+        let (status, response) = send(
+            ServerConfig::default(),
+            "/solve/14/1",
+            "application/json",
+            body.to_string(),
+        )
+        .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, status);
+        assert_eq!(
+            Some("aoc::invalid_parameter".to_string()),
+            response.unwrap().diagnostics[0].code
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_solves() {
+        let input = "3   4\n4   3\n";
+        let config = ServerConfig {
+            max_solves: 1,
+            ..ServerConfig::default()
+        };
+        let router = router(config);
+        for _ in 0..2 {
+            let response = router
+                .clone()
+                .oneshot(Request::post("/solve/1/1").body(Body::from(input)).unwrap())
+                .await
+                .unwrap();
+            // The permit of the first solve is back once it returned
+            assert_eq!(StatusCode::OK, response.status());
+        }
+
+        let config = ServerConfig {
+            max_solves: 0,
+            ..ServerConfig::default()
+        };
+        let (status, response) = send(config, "/solve/1/1", "text/plain", input).await;
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status);
+        assert_eq!(
+            Some("aoc::busy".to_string()),
+            response.unwrap().diagnostics[0].code
+        );
+    }
+
+    #[tokio::test]
+    async fn test_input_size_limit() {
+        let config = ServerConfig {
+            max_input_bytes: 16,
+            ..ServerConfig::default()
+        };
+        let (status, _) = send(config, "/solve/1/1", "text/plain", "1   2\n".repeat(10)).await;
+
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, status);
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let config = ServerConfig {
+            timeout: Duration::from_millis(1),
+            ..ServerConfig::default()
+        };
+        // No robot line-up ever appears, so part 2 runs through all of its steps
+        let body = serde_json::json!({
+            "input": "p=0,0 v=1,1",
+            "params": { "height": 7, "width": 11 }
+        });
+        let (status, response) =
+            send(config, "/solve/14/2", "application/json", body.to_string()).await;
+
+        assert_eq!(StatusCode::GATEWAY_TIMEOUT, status);
+        assert_eq!(
+            Some("aoc::timeout".to_string()),
+            response.unwrap().diagnostics[0].code
+        );
+    }
+}
@@ -0,0 +1,653 @@
+//! A dashboard over the whole calendar: every day's status, answers, timings and allocations,
+//! with solves running in the background and the tracing output tailed below.
+//!
+//! [`App`] holds the state and only changes through [`App::handle_key`] and [`App::update`],
+//! and [`draw`] renders it, so both work without a terminal. The `aoc-tui` binary owns the
+//! terminal and the event loop, and hands the solves [`App::handle_key`] asks for to the
+//! thread from [`spawn_worker`].
+//!
+//! The worker solves one part at a time, and allocations are counted per thread, so the
+//! allocation stats of a solve leave out the dashboard's own. The binary redraws on keys,
+//! finished solves and a timer, which keeps the log tailed during long solves.
+
+use crate::calendar::{self, DayStatus, PartStatus};
+use crate::config::Config;
+use crate::registry;
+use crate::repl::{Session, GRID_DAYS};
+use crate::runner::{self, Outcome, Runner};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use std::collections::{BTreeMap, VecDeque};
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// How many lines of tracing output are kept.
+pub const LOG_LINES: usize = 500;
+
+pub const HELP: &str = "↑/↓ select  enter run day  a run all  g grid  q quit";
+const GRID_HELP: &str = "↑/↓/←/→ scroll  esc back";
+
+/// One part to solve on its puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Job {
+    pub day: u8,
+    pub part: u8,
+}
+
+/// A finished solve, reported back by the worker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    pub job: Job,
+    /// The outcome, or the error chain on one line
+    pub result: Result<Outcome, String>,
+}
+
+/// What the event loop does after a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Run(Vec<Job>),
+    Quit,
+}
+
+/// Starts a thread that solves the jobs sent to it one after the other, in order, and sends
+/// back an [`Update`] for each. It stops once the job sender is dropped.
+pub fn spawn_worker(runner: Runner, config: Config) -> (Sender<Job>, Receiver<Update>) {
+    let (jobs, job_receiver) = mpsc::channel::<Job>();
+    let (update_sender, updates) = mpsc::channel();
+    thread::spawn(move || {
+        for job in job_receiver {
+            let result = solve(&runner, &config, job);
+            match &result {
+                Ok(outcome) => tracing::info!(
+                    "day {:02} part {}: {} ({})",
+                    job.day,
+                    job.part,
+                    outcome.answer,
+                    calendar::format_stats(
+                        outcome.elapsed.as_secs_f64() * 1000.0,
+                        outcome.allocations.as_ref(),
+                        outcome.shared
+                    )
+                ),
+                Err(err) => tracing::warn!("day {:02} part {}: {err}", job.day, job.part),
+            }
+            if update_sender.send(Update { job, result }).is_err() {
+                break;
+            }
+        }
+    });
+    (jobs, updates)
+}
+
+fn solve(runner: &Runner, config: &Config, job: Job) -> Result<Outcome, String> {
+    let solution = registry::find(job.day, job.part)
+        .ok_or_else(|| format!("no solution for day {} part {}", job.day, job.part))?;
+    let input = runner::read_input(job.day, job.part).map_err(|err| err.to_string())?;
+    runner.run(solution, &input, config).map_err(|report| {
+        report
+            .chain()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(": ")
+    })
+}
+
+/// The tracing output, as a writer for [`tracing_subscriber::fmt`] that keeps the last
+/// [`LOG_LINES`] lines.
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<String>>>);
+
+impl LogBuffer {
+    pub fn push(&self, line: &str) {
+        let mut lines = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if lines.len() == LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line.to_string());
+    }
+
+    /// The last `count` lines, oldest first.
+    pub fn tail(&self, count: usize) -> Vec<String> {
+        let lines = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        lines
+            .iter()
+            .skip(lines.len().saturating_sub(count))
+            .cloned()
+            .collect()
+    }
+}
+
+impl io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The formatter writes every event at once, so lines are not split between writes
+        for line in String::from_utf8_lossy(buf).lines() {
+            self.push(line);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// A day's grid shown instead of the calendar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridView {
+    pub day: u8,
+    pub grid: String,
+    /// Rows and columns scrolled past
+    pub scroll: (u16, u16),
+}
+
+/// The dashboard's state.
+pub struct App {
+    days: Vec<DayStatus>,
+    config: Config,
+    log: LogBuffer,
+    selected: usize,
+    /// Answers of the solves run since the dashboard started
+    results: BTreeMap<Job, Result<Outcome, String>>,
+    /// The jobs sent to the worker and not finished yet, the first one is running
+    pending: VecDeque<Job>,
+    grid: Option<GridView>,
+    /// Shown above the help until the next key
+    message: Option<String>,
+}
+
+impl App {
+    /// A dashboard over `days`, as [`calendar::scan`] found them.
+    pub fn new(days: Vec<DayStatus>, config: Config, log: LogBuffer) -> Self {
+        App {
+            days,
+            config,
+            log,
+            selected: 0,
+            results: BTreeMap::new(),
+            pending: VecDeque::new(),
+            grid: None,
+            message: None,
+        }
+    }
+
+    pub fn selected_day(&self) -> u8 {
+        self.days[self.selected].day
+    }
+
+    pub fn grid(&self) -> Option<&GridView> {
+        self.grid.as_ref()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+
+        if let Some(grid) = &mut self.grid {
+            let (rows, columns) = &mut grid.scroll;
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.grid = None,
+                KeyCode::Up | KeyCode::Char('k') => *rows = rows.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => *rows = rows.saturating_add(1),
+                KeyCode::PageUp => *rows = rows.saturating_sub(20),
+                KeyCode::PageDown => *rows = rows.saturating_add(20),
+                KeyCode::Left | KeyCode::Char('h') => *columns = columns.saturating_sub(4),
+                KeyCode::Right | KeyCode::Char('l') => *columns = columns.saturating_add(4),
+                _ => {}
+            }
+            return Action::None;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.days.len() - 1)
+            }
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = self.days.len() - 1,
+            KeyCode::Enter | KeyCode::Char('r') => return self.queue(&[self.selected_day()]),
+            KeyCode::Char('a') => {
+                let days = self.days.iter().map(|day| day.day).collect::<Vec<_>>();
+                return self.queue(&days);
+            }
+            KeyCode::Char('g') => self.open_grid(),
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// Queues the registered parts of `days` that are not queued already.
+    fn queue(&mut self, days: &[u8]) -> Action {
+        let registered = days
+            .iter()
+            .flat_map(|&day| [1, 2].map(|part| Job { day, part }))
+            .filter(|job| registry::find(job.day, job.part).is_some())
+            .collect::<Vec<_>>();
+        let jobs = registered
+            .iter()
+            .copied()
+            .filter(|job| !self.pending.contains(job))
+            .collect::<Vec<_>>();
+        if jobs.is_empty() {
+            self.message = Some(match (registered.is_empty(), days) {
+                (true, [day]) => format!("day {day} has no solution"),
+                (true, _) => "no day has a solution".to_string(),
+                (false, [day]) => format!("day {day} is already queued"),
+                (false, _) => "every day is already queued".to_string(),
+            });
+            return Action::None;
+        }
+        self.pending.extend(&jobs);
+        Action::Run(jobs)
+    }
+
+    fn open_grid(&mut self) {
+        let day = self.selected_day();
+        if !GRID_DAYS.contains(&day) {
+            self.message = Some(format!("day {day} has no grid"));
+            return;
+        }
+        match runner::read_input(day, 1) {
+            Ok(input) => self.show_grid(day, input),
+            Err(err) => self.message = Some(err.to_string()),
+        }
+    }
+
+    /// Shows the grid of `day` for `input` with the current parameters.
+    pub fn show_grid(&mut self, day: u8, input: String) {
+        let grid = Session::new(day, input, self.config.clone()).and_then(|session| session.grid());
+        match grid {
+            Ok(grid) => {
+                self.message = None;
+                self.grid = Some(GridView {
+                    day,
+                    grid,
+                    scroll: (0, 0),
+                });
+            }
+            Err(report) => self.message = Some(report.to_string()),
+        }
+    }
+
+    /// Records a finished solve.
+    pub fn update(&mut self, update: Update) {
+        self.pending.retain(|&job| job != update.job);
+        self.results.insert(update.job, update.result);
+    }
+
+    fn is_solved(&self, day: &DayStatus, part: u8) -> bool {
+        match self.results.get(&Job { day: day.day, part }) {
+            Some(result) => result.is_ok(),
+            None => matches!(day.parts[part as usize - 1], PartStatus::Solved(_)),
+        }
+    }
+
+    fn stars(&self) -> usize {
+        self.days
+            .iter()
+            .map(|day| (1..=2).filter(|&part| self.is_solved(day, part)).count())
+            .sum()
+    }
+
+    /// What the calendar shows for a part: the solve's state if there was one since the
+    /// dashboard started, or else the recorded one.
+    fn part_cell(&self, day: &DayStatus, part: u8) -> Span<'static> {
+        let job = Job { day: day.day, part };
+        if let Some(position) = self.pending.iter().position(|&pending| pending == job) {
+            let state = if position == 0 { "running" } else { "queued" };
+            return Span::raw(state).yellow();
+        }
+        match self.results.get(&job) {
+            Some(Ok(outcome)) => Span::raw(outcome.answer.clone()).green(),
+            Some(Err(_)) => Span::raw("failed").red(),
+            None => match &day.parts[part as usize - 1] {
+                PartStatus::NoCrate => Span::raw("-").dark_gray(),
+                PartStatus::Missing => Span::raw("missing").dark_gray(),
+                PartStatus::Todo => Span::raw("todo"),
+                PartStatus::Unregistered => Span::raw("not in registry"),
+                PartStatus::NotRun => Span::raw("not run"),
+                PartStatus::Solved(entry) => Span::raw(entry.answer.clone()).green(),
+            },
+        }
+    }
+
+    /// The answer, time and heap stats of each part of the selected day.
+    fn details(&self) -> Vec<Line<'static>> {
+        let day = &self.days[self.selected];
+        let mut lines = Vec::new();
+        for part in 1..=2 {
+            let job = Job { day: day.day, part };
+            let recorded = match &day.parts[part as usize - 1] {
+                PartStatus::Solved(entry) => Some(Outcome {
+                    answer: entry.answer.clone(),
+                    elapsed: std::time::Duration::from_secs_f64(entry.elapsed_ms / 1000.0),
+                    allocations: entry.allocations,
+                    shared: entry.shared,
+                    cached: true,
+                }),
+                _ => None,
+            };
+            let heading = Span::raw(format!("part {part}  ")).bold();
+            match (self.results.get(&job), recorded.as_ref()) {
+                (Some(Err(err)), _) => {
+                    lines.push(Line::from(vec![heading, Span::raw(err.clone()).red()]))
+                }
+                (Some(Ok(outcome)), _) | (None, Some(outcome)) => {
+                    lines.push(Line::from(vec![
+                        heading,
+                        Span::raw(outcome.answer.clone()).green().bold(),
+                    ]));
+                    let cached = if outcome.cached { " (recorded)" } else { "" };
+                    let both = if outcome.shared {
+                        " for both parts"
+                    } else {
+                        ""
+                    };
+                    lines.push(Line::raw(format!(
+                        "  time         {:.3} ms{both}{cached}",
+                        outcome.elapsed.as_secs_f64() * 1000.0
+                    )));
+                    if let Some(stats) = outcome.allocations {
+                        lines.push(Line::raw(format!(
+                            "  allocations  {} ({})",
+                            stats.allocations,
+                            calendar::format_bytes(stats.bytes)
+                        )));
+                        lines.push(Line::raw(format!(
+                            "  peak heap    {}",
+                            calendar::format_bytes(stats.peak_bytes)
+                        )));
+                    }
+                }
+                (None, None) => lines.push(Line::from(vec![heading, self.part_cell(day, part)])),
+            }
+        }
+        if GRID_DAYS.contains(&day.day) {
+            lines.push(Line::raw(""));
+            lines.push(Line::raw("press g to show the grid").dark_gray());
+        }
+        lines
+    }
+}
+
+/// Draws the dashboard, or the open grid, onto the whole frame.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, log, footer] = Layout::vertical([
+        Constraint::Min(8),
+        Constraint::Length(8),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    match &app.grid {
+        Some(grid) => draw_grid(frame, main, grid),
+        None => {
+            let [calendar, details] =
+                Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .areas(main);
+            draw_calendar(frame, calendar, app);
+            let title = format!(" day {:02} ", app.selected_day());
+            frame.render_widget(
+                Paragraph::new(app.details()).block(Block::bordered().title(title)),
+                details,
+            );
+        }
+    }
+
+    let lines = app.log.tail(log.height.saturating_sub(2) as usize);
+    frame.render_widget(
+        Paragraph::new(lines.into_iter().map(Line::raw).collect::<Vec<_>>())
+            .block(Block::bordered().title(" log ")),
+        log,
+    );
+
+    let help = if app.grid.is_some() { GRID_HELP } else { HELP };
+    let footer_line = match &app.message {
+        Some(message) => Line::raw(message.clone()).red(),
+        None => Line::raw(help).dark_gray(),
+    };
+    frame.render_widget(Paragraph::new(footer_line), footer);
+}
+
+fn draw_calendar(frame: &mut Frame, area: Rect, app: &App) {
+    let rows = app.days.iter().map(|day| {
+        let stars = (1..=2).filter(|&part| app.is_solved(day, part)).count();
+        Row::new(vec![
+            Cell::from(format!("{:02}", day.day)),
+            Cell::from(Span::raw("*".repeat(stars)).yellow()),
+            Cell::from(app.part_cell(day, 1)),
+            Cell::from(app.part_cell(day, 2)),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(5),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(Row::new(vec!["day", "stars", "part 1", "part 2"]).bold())
+    .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+    .block(Block::bordered().title(format!(" Advent of Code 2024, {} stars ", app.stars())));
+
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_grid(frame: &mut Frame, area: Rect, grid: &GridView) {
+    frame.render_widget(
+        Paragraph::new(grid.grid.as_str())
+            .scroll(grid.scroll)
+            .block(Block::bordered().title(format!(" day {:02} grid ", grid.day))),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alloc::AllocStats;
+    use crate::cache::CacheEntry;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use std::time::Duration;
+
+    const WORDS: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn app() -> App {
+        let solved = PartStatus::Solved(CacheEntry {
+            day: 1,
+            part: 1,
+            source_hash: String::new(),
+            answer: "1651298".to_string(),
+            elapsed_ms: 0.25,
+            allocations: Some(AllocStats {
+                allocations: 7,
+                bytes: 32768,
+                peak_bytes: 16384,
+            }),
+            shared: false,
+        });
+        let mut days = (1..=25)
+            .map(|day| DayStatus {
+                day,
+                parts: [PartStatus::NoCrate, PartStatus::NoCrate],
+            })
+            .collect::<Vec<_>>();
+        days[0].parts = [solved, PartStatus::NotRun];
+        days[3].parts = [PartStatus::NotRun, PartStatus::NotRun];
+        App::new(days, Config::default(), LogBuffer::default())
+    }
+
+    /// The frame's text, one line per row, without trailing spaces.
+    fn render(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| {
+                let line = row.iter().map(|cell| cell.symbol()).collect::<String>();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    fn contains(screen: &[String], text: &str) -> bool {
+        screen.iter().any(|line| line.contains(text))
+    }
+
+    #[test]
+    fn test_draw_calendar() {
+        let screen = render(&app());
+
+        assert!(screen[0].starts_with("┌ Advent of Code 2024, 1 stars ─"));
+        assert!(screen[0].contains("┐┌ day 01 ─"));
+        assert!(contains(&screen, "│day stars part 1"));
+        assert!(contains(&screen, "│01  *     1651298"));
+        assert!(contains(&screen, "│04        not run"));
+        assert!(contains(&screen, "│part 1  1651298"));
+        assert!(contains(&screen, "│  time         0.250 ms (recorded)"));
+        assert!(contains(&screen, "│  allocations  7 (32.0 KiB)"));
+        assert!(contains(&screen, "│  peak heap    16.0 KiB"));
+        assert!(contains(&screen, "│part 2  not run"));
+        assert_eq!(HELP, screen[23]);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut app = app();
+        assert_eq!(Action::None, app.handle_key(key(KeyCode::Down)));
+        assert_eq!(Action::None, app.handle_key(key(KeyCode::Up)));
+        assert_eq!(1, app.selected_day());
+
+        let jobs = vec![Job { day: 1, part: 1 }, Job { day: 1, part: 2 }];
+        assert_eq!(
+            Action::Run(jobs.clone()),
+            app.handle_key(key(KeyCode::Enter))
+        );
+        // Already queued jobs are not queued again
+        assert_eq!(Action::None, app.handle_key(key(KeyCode::Char('r'))));
+        assert_eq!("day 1 is already queued", render(&app)[23]);
+        let screen = render(&app);
+        assert!(contains(&screen, "│01  *     running"));
+        assert!(contains(&screen, "queued"));
+
+        app.update(Update {
+            job: jobs[0],
+            result: Ok(Outcome {
+                answer: "11".to_string(),
+                elapsed: Duration::from_millis(2),
+                allocations: None,
+                shared: false,
+                cached: false,
+            }),
+        });
+        app.update(Update {
+            job: jobs[1],
+            result: Err("part 2: Rows differ in length".to_string()),
+        });
+        app.log.push("INFO aoc::tui: day 01 part 1: 11 (2.000 ms)");
+        let screen = render(&app);
+        assert!(contains(&screen, "│01  *     11"));
+        assert!(contains(&screen, "failed"));
+        assert!(contains(&screen, "│part 1  11"));
+        assert!(contains(&screen, "│  time         2.000 ms "));
+        assert!(!contains(&screen, "(recorded)"));
+        assert!(contains(&screen, "│part 2  part 2: Rows differ in length"));
+        assert!(contains(
+            &screen,
+            "│INFO aoc::tui: day 01 part 1: 11 (2.000 ms)"
+        ));
+
+        assert_eq!(Action::Quit, app.handle_key(key(KeyCode::Char('q'))));
+    }
+
+    #[test]
+    fn test_run_all() {
+        let mut app = app();
+        let Action::Run(jobs) = app.handle_key(key(KeyCode::Char('a'))) else {
+            panic!("should run every registered part");
+        };
+        assert_eq!(registry::solutions().len(), jobs.len());
+        assert!(jobs.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert_eq!(Action::None, app.handle_key(key(KeyCode::Char('a'))));
+        assert_eq!("every day is already queued", render(&app)[23]);
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut app = app();
+        app.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(None, app.grid());
+        assert_eq!("day 1 has no grid", render(&app)[23]);
+
+        app.show_grid(4, WORDS.to_string());
+        let screen = render(&app);
+        assert!(screen[0].starts_with("┌ day 04 grid ─"));
+        assert_eq!("│MMMSXXMASM", screen[1].trim_end_matches('│').trim_end());
+        assert_eq!(GRID_HELP, screen[23]);
+
+        app.handle_key(key(KeyCode::Down));
+        app.handle_key(key(KeyCode::Right));
+        assert_eq!((1, 4), app.grid().unwrap().scroll);
+        assert!(render(&app)[1].starts_with("│XMSMSA "));
+
+        assert_eq!(Action::None, app.handle_key(key(KeyCode::Esc)));
+        assert_eq!(None, app.grid());
+    }
+
+    #[test]
+    fn test_log_buffer() {
+        let log = LogBuffer::default();
+        let mut writer = log.make_writer();
+        for i in 0..LOG_LINES {
+            io::Write::write_all(&mut writer, format!("line {i}\n").as_bytes()).unwrap();
+        }
+        io::Write::write_all(&mut writer, b"last\nbut one\n").unwrap();
+
+        assert_eq!(vec!["line 499", "last", "but one"], log.tail(3));
+        assert_eq!(LOG_LINES, log.tail(usize::MAX).len());
+    }
+}
@@ -0,0 +1,59 @@
+//! Allocation counting with [`CountingAllocator`] installed, which only a test binary of its own
+//! can do without changing what the other tests measure.
+
+use aoc::alloc::{measure, AllocStats, CountingAllocator};
+use std::hint::black_box;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_measure() {
+    let (_, stats) = measure(|| {
+        let mut numbers = black_box(Vec::<u64>::with_capacity(16));
+        numbers.reserve_exact(32);
+        drop(black_box(numbers));
+    });
+
+    assert_eq!(
+        Some(AllocStats {
+            allocations: 2,
+            bytes: 128 + 256,
+            peak_bytes: 256,
+        }),
+        stats
+    );
+}
+
+#[test]
+fn test_measure_ignores_other_threads() {
+    let barrier = Arc::new(Barrier::new(2));
+    let other = thread::spawn({
+        let barrier = Arc::clone(&barrier);
+        move || {
+            barrier.wait();
+            let bytes = black_box(vec![0_u8; 1 << 20]);
+            barrier.wait();
+            drop(bytes);
+        }
+    });
+
+    let (_, stats) = measure(|| {
+        barrier.wait();
+        let numbers = black_box(Vec::<u64>::with_capacity(4));
+        barrier.wait();
+        drop(numbers);
+    });
+    other.join().unwrap();
+
+    assert_eq!(
+        Some(AllocStats {
+            allocations: 1,
+            bytes: 32,
+            peak_bytes: 32,
+        }),
+        stats
+    );
+}
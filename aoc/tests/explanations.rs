@@ -0,0 +1,244 @@
+//! Explanations add up to the answers the solvers give, on the examples and on the puzzle input
+//! where it is checked out, and `aoc solve --explain` prints them.
+//!
+//! The decisions in an explanation are checked against the input here rather than with the
+//! predicates the solvers use, so an explanation cannot repeat a solver's mistake unnoticed.
+
+use aoc::cancel::CancelToken;
+use aoc::config::Config;
+use aoc::explain;
+use aoc::params::Params;
+use aoc::registry;
+use aoc::runner::read_input;
+use aoc_common::input::normalize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// The configuration the example of `day` is solved with.
+fn example_config(day: u8) -> miette::Result<Config> {
+    let params: Params = fs::read_to_string(fixtures().join(format!("day-{day:02}/params.json")))
+        .map(|json| serde_json::from_str(&json).unwrap())
+        .unwrap_or_default();
+    Ok(params.resolve(&Config::default())?)
+}
+
+#[test]
+fn test_explanations_match_answers() -> miette::Result<()> {
+    for day in explain::days() {
+        let example_config = example_config(day)?;
+        let mut inputs = Vec::new();
+        for part in [1, 2] {
+            let path = fixtures().join(format!("day-{day:02}/part{part}.txt"));
+            let input = fs::read_to_string(&path).unwrap();
+            inputs.push((path.display().to_string(), input, example_config.clone()));
+        }
+        if let Ok(input) = read_input(day, 1) {
+            inputs.push(("the puzzle input".to_string(), input, Config::default()));
+        }
+
+        for (name, input, config) in inputs {
+            let explanation = explain::explain(day, &input, &config).unwrap()?;
+            let expected = registry::find_both(day).unwrap().solve_cancellable(
+                &input,
+                &config,
+                &CancelToken::new(),
+            )?;
+            assert_eq!(expected, explanation.answers, "day {day} on {name}");
+
+            let json = explanation.to_json();
+            assert_eq!(expected.0, json["part1"], "day {day} on {name}");
+            assert_eq!(expected.1, json["part2"], "day {day} on {name}");
+
+            let check = match day {
+                2 => check_day02,
+                3 => check_day03,
+                5 => check_day05,
+                7 => check_day07,
+                13 => check_day13,
+                _ => panic!("day {day} explains its answers but nothing checks them"),
+            };
+            check(&normalize(&input), &explanation.json);
+        }
+    }
+    Ok(())
+}
+
+fn numbers(value: &Value) -> Vec<i64> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|number| number.as_i64().unwrap())
+        .collect()
+}
+
+/// Removing the level the dampener removed leaves a report that is safe as it is.
+fn check_day02(_input: &str, json: &Value) {
+    for report in json["reports"].as_array().unwrap() {
+        if let Some(removed) = report["dampened"]["removed"].as_u64() {
+            let mut levels = numbers(&report["levels"])
+                .into_iter()
+                .map(|level| level as i32)
+                .collect::<Vec<_>>();
+            levels.remove(removed as usize);
+            assert!(day_02::part1::is_safe(&levels), "{report}");
+            assert_eq!(false, report["safe"], "{report}");
+        }
+    }
+}
+
+/// Every `mul` is where the explanation says, and is enabled exactly when the last `do()` or
+/// `don't()` before it was a `do()`.
+fn check_day03(input: &str, json: &Value) {
+    let lines = input.lines().collect::<Vec<_>>();
+    for mul in json["muls"].as_array().unwrap() {
+        let line = mul["line"].as_u64().unwrap() as usize - 1;
+        let column = mul["column"].as_u64().unwrap() as usize - 1;
+        let [x, y] = numbers(&mul["operands"])[..] else {
+            panic!("{mul}");
+        };
+        assert!(
+            lines[line][column..].starts_with(&format!("mul({x},{y})")),
+            "{mul}"
+        );
+        assert_eq!(x * y, mul["product"].as_i64().unwrap(), "{mul}");
+
+        let before = lines[..line]
+            .iter()
+            .copied()
+            .chain([&lines[line][..column]])
+            .collect::<Vec<_>>()
+            .join("\n");
+        let enabled = before.rfind("don't()") <= before.rfind("do()");
+        assert_eq!(enabled, mul["enabled"], "{mul}");
+    }
+}
+
+/// Every update in the right order, as given or reordered, keeps every rule for its pages.
+fn check_day05(input: &str, json: &Value) {
+    let rules = input
+        .lines()
+        .filter_map(|line| line.split_once('|'))
+        .map(|(before, after)| (before.parse().unwrap(), after.parse().unwrap()))
+        .collect::<Vec<(i64, i64)>>();
+    for update in json["updates"].as_array().unwrap() {
+        let pages = numbers(&update["pages"]);
+        let in_order = match &update["reordered"] {
+            Value::Null => pages.clone(),
+            reordered => numbers(reordered),
+        };
+        let mut sorted = (pages.clone(), in_order.clone());
+        sorted.0.sort_unstable();
+        sorted.1.sort_unstable();
+        assert_eq!(sorted.0, sorted.1, "{update}");
+
+        let position = |page| in_order.iter().position(|&p| p == page);
+        for (before, after) in &rules {
+            if let (Some(before), Some(after)) = (position(*before), position(*after)) {
+                assert!(before < after, "{update} breaks {before}|{after}");
+            }
+        }
+        assert_eq!(
+            in_order[in_order.len() / 2],
+            update["middle_page"],
+            "{update}"
+        );
+        assert_eq!(
+            update["reordered"].is_null(),
+            update["broken_rule"].is_null()
+        );
+    }
+}
+
+/// Applying the operators from left to right gives the equation's result.
+fn check_day07(_input: &str, json: &Value) {
+    for equation in json["equations"].as_array().unwrap() {
+        let result = equation["result"].as_u64().unwrap();
+        let operands = numbers(&equation["operands"]);
+        for part in ["part1", "part2"] {
+            let Some(operators) = equation[part].as_array() else {
+                continue;
+            };
+            assert_eq!(operands.len() - 1, operators.len(), "{equation}");
+            let value = operators.iter().zip(&operands[1..]).fold(
+                operands[0] as u64,
+                |value, (operator, &operand)| {
+                    let operand = operand as u64;
+                    match operator.as_str().unwrap() {
+                        "+" => value + operand,
+                        "*" => value * operand,
+                        "||" if part == "part2" => format!("{value}{operand}").parse().unwrap(),
+                        other => panic!("{other} in {part} of {equation}"),
+                    }
+                },
+            );
+            assert_eq!(result, value, "{part} of {equation}");
+        }
+    }
+}
+
+/// Pressing the buttons as often as the explanation says takes the claw to the prize.
+fn check_day13(_input: &str, json: &Value) {
+    let offset = json["prize_offset"].as_i64().unwrap();
+    for played in json["machines"].as_array().unwrap() {
+        let machine = &played["machine"];
+        let [ax, ay] = numbers(&machine["button_a"])[..] else {
+            panic!("{played}");
+        };
+        let [bx, by] = numbers(&machine["button_b"])[..] else {
+            panic!("{played}");
+        };
+        let [x, y] = numbers(&machine["target"])[..] else {
+            panic!("{played}");
+        };
+        for (part, prize) in [("part1", (x, y)), ("part2", (x + offset, y + offset))] {
+            let win = &played[part];
+            if win.is_null() {
+                continue;
+            }
+            let (a, b) = (
+                win["presses_a"].as_i64().unwrap(),
+                win["presses_b"].as_i64().unwrap(),
+            );
+            assert_eq!(
+                prize,
+                (a * ax + b * bx, a * ay + b * by),
+                "{part} of {played}"
+            );
+            assert_eq!(
+                3 * a + b,
+                win["tokens"].as_i64().unwrap(),
+                "{part} of {played}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_explain_flag() {
+    let input = fixtures().join("day-02/part1.txt");
+    let run = |format: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["solve", "2", format, "--input"])
+            .arg(&input)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let text = run("--explain");
+    assert!(text.contains("   4: 1 [3] 2 4 5  safe without level 2\n"));
+    assert!(text.ends_with("day 02 part 1: 2\nday 02 part 2: 4\n"));
+
+    let json: serde_json::Value = serde_json::from_str(&run("--explain=json")).unwrap();
+    assert_eq!("2", json["part1"]);
+    assert_eq!("4", json["part2"]);
+    assert_eq!(6, json["explanation"]["reports"].as_array().unwrap().len());
+}
@@ -0,0 +1,62 @@
+//! Every alternative implementation of a part agrees with the default one, on the example and
+//! on the puzzle input where it is checked out.
+
+use aoc::cancel::CancelToken;
+use aoc::config::Config;
+use aoc::params::Params;
+use aoc::registry::{self, DEFAULT_IMPLEMENTATION};
+use aoc::runner::read_input;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_implementations_agree() -> miette::Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for day in 1..=25 {
+        for part in [1, 2] {
+            let implementations = registry::implementations(day, part).collect::<Vec<_>>();
+            let [default, alternatives @ ..] = implementations.as_slice() else {
+                continue;
+            };
+            if alternatives.is_empty() {
+                continue;
+            }
+            assert_eq!(DEFAULT_IMPLEMENTATION, default.name);
+
+            // The examples are solved with their own parameters, the puzzle input with the
+            // defaults
+            let day_dir = fixtures.join(format!("day-{day:02}"));
+            let params: Params = fs::read_to_string(day_dir.join("params.json"))
+                .map(|json| serde_json::from_str(&json).unwrap())
+                .unwrap_or_default();
+            let example_config = params.resolve(&Config::default())?;
+            let fixture = day_dir.join(format!("part{part}.txt"));
+            let inputs = [
+                (
+                    fixture.display().to_string(),
+                    fs::read_to_string(&fixture).ok(),
+                    example_config,
+                ),
+                (
+                    "the puzzle input".to_string(),
+                    read_input(day, part).ok(),
+                    Config::default(),
+                ),
+            ];
+            for (name, input, config) in inputs {
+                let Some(input) = input else {
+                    continue;
+                };
+
+                let expected = default.solve_cancellable(&input, &config, &CancelToken::new())?;
+                for alternative in alternatives {
+                    let answer =
+                        alternative.solve_cancellable(&input, &config, &CancelToken::new())?;
+                    assert_eq!(expected, answer, "{} on {name}", alternative.name);
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! The leaderboard statistics on a small hand-written private leaderboard in the format Advent of
+//! Code serves. Its `local_score`s were worked out by hand from the star timestamps.
+
+use aoc::leaderboard::{self, Analysis, LeaderboardError};
+
+const PRIVATE: &str = include_str!("fixtures/leaderboard/private.json");
+
+fn analysis() -> Analysis {
+    Analysis::new(&leaderboard::parse(PRIVATE).unwrap()).unwrap()
+}
+
+#[test]
+fn test_local_score_matches_reported() {
+    let analysis = analysis();
+
+    assert_eq!(
+        vec![
+            ("Ada", 6, 29),
+            ("Grace Hopper", 5, 21),
+            ("(anonymous user #1003)", 3, 9),
+            ("Torvalds, Linus", 1, 2),
+            ("Idle", 0, 0),
+        ],
+        analysis
+            .standings
+            .iter()
+            .map(|standing| (standing.name.as_str(), standing.stars, standing.local_score))
+            .collect::<Vec<_>>()
+    );
+    for standing in &analysis.standings {
+        assert_eq!(standing.reported_local_score, standing.local_score);
+    }
+}
+
+#[test]
+fn test_day_ranking() {
+    let analysis = analysis();
+    assert_eq!(vec![1, 2, 3], analysis.days());
+
+    let day1 = analysis.day_ranking(1);
+    assert_eq!(
+        vec![
+            ("Ada", Some(148), 10),
+            ("Grace Hopper", Some(449), 8),
+            ("(anonymous user #1003)", Some(960), 6),
+            ("Torvalds, Linus", None, 2),
+        ],
+        day1.iter()
+            .map(|result| (result.name.as_str(), result.delta, result.points))
+            .collect::<Vec<_>>()
+    );
+
+    // Both got the second star in the same second, the server recorded Ada's first
+    let day2 = analysis.day_ranking(2);
+    assert_eq!(("Ada", 9), (day2[0].name.as_str(), day2[0].points));
+    assert_eq!(("Grace Hopper", 9), (day2[1].name.as_str(), day2[1].points));
+    assert_eq!(Some(845), day2[0].part2);
+    assert_eq!(Some(483), day2[1].delta);
+
+    let day3 = analysis.day_ranking(3);
+    assert_eq!(Some(3723), day3[0].part2);
+    assert_eq!((Some(765), None), (day3[1].part1, day3[1].part2));
+}
+
+#[test]
+fn test_timeline() {
+    let analysis = analysis();
+    let grace = analysis.find_member("Grace Hopper").unwrap();
+
+    assert_eq!(
+        vec![
+            (1, 1, 1, 4),
+            (1, 2, 2, 8),
+            (2, 1, 3, 13),
+            (2, 2, 4, 17),
+            (3, 1, 5, 21)
+        ],
+        analysis
+            .timeline(grace.member)
+            .iter()
+            .map(|entry| (entry.day, entry.part, entry.stars, entry.local_score))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some(grace), analysis.find_member("1002"));
+    assert_eq!(None, analysis.find_member("Grace"));
+}
+
+#[test]
+fn test_render() {
+    let analysis = analysis();
+
+    assert_eq!(
+        "rank  member                         stars  score  reported
+   1  Ada                                6     29        29
+   2  Grace Hopper                       5     21        21
+   3  (anonymous user #1003)             3      9         9
+   4  Torvalds, Linus                    1      2         2
+   5  Idle                               0      0         0
+",
+        leaderboard::render_standings(&analysis)
+    );
+    assert_eq!(
+        "day 03
+rank  member                               part 1       part 2        delta points
+   1  Ada                                00:10:00     01:02:03     00:52:03     10
+   2  Grace Hopper                       00:12:45            -            -      4
+",
+        leaderboard::render_day(&analysis, 3)
+    );
+    assert_eq!(
+        "Torvalds, Linus
+time (UTC-5)         day part stars  score
+2024-12-03 01:00:00    1    1     1      2
+",
+        leaderboard::render_timeline(&analysis, analysis.find_member("1004").unwrap())
+    );
+}
+
+#[test]
+fn test_csv() {
+    let csv = leaderboard::render_csv(&analysis());
+    let lines = csv.lines().collect::<Vec<_>>();
+
+    assert_eq!(
+        "member_id,member,day,part,timestamp,elapsed_secs,rank,points,delta_secs",
+        lines[0]
+    );
+    assert_eq!("1001,Ada,1,1,1733029392,192,1,5,", lines[1]);
+    assert_eq!("1001,Ada,1,2,1733029540,340,1,5,148", lines[5]);
+    assert!(lines.contains(&"1004,\"Torvalds, Linus\",1,1,1733205600,176400,4,2,"));
+    assert_eq!(1 + 15, lines.len());
+}
+
+#[test]
+fn test_invalid() {
+    assert!(matches!(
+        leaderboard::parse("{\"members\": []}"),
+        Err(LeaderboardError::Parse(_))
+    ));
+
+    let mut board = leaderboard::parse(PRIVATE).unwrap();
+    board.event = "next year".to_string();
+    assert!(matches!(
+        Analysis::new(&board),
+        Err(LeaderboardError::Event(_))
+    ));
+
+    let empty = leaderboard::parse("{\"event\": \"2024\", \"members\": {}}").unwrap();
+    let analysis = Analysis::new(&empty).unwrap();
+    assert!(analysis.standings.is_empty());
+    assert_eq!(
+        "member_id,member,day,part,timestamp,elapsed_secs,rank,points,delta_secs\n",
+        leaderboard::render_csv(&analysis)
+    );
+}
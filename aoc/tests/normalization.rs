@@ -0,0 +1,136 @@
+//! Every solution gives the example's answer for its example input however the input was saved.
+//!
+//! The examples live in `tests/fixtures/day-XX/partN.txt`, along with an optional
+//! `params.json` for days that need puzzle parameters.
+
+use aoc::params::Params;
+use aoc::registry;
+use std::fs;
+use std::path::Path;
+
+/// The answers to the examples, as the puzzle descriptions give them. Days 14, 18, 20 and 21 are
+/// solved with the parameters in their `params.json`. The descriptions give no answer for day 11
+/// part 2, day 14 part 2 or day 21 part 2, so those pin the answers the solvers gave when added.
+const EXAMPLE_ANSWERS: &[(u8, u8, &str)] = &[
+    (1, 1, "11"),
+    (1, 2, "31"),
+    (2, 1, "2"),
+    (2, 2, "4"),
+    (3, 1, "161"),
+    (3, 2, "48"),
+    (4, 1, "18"),
+    (4, 2, "9"),
+    (5, 1, "143"),
+    (5, 2, "123"),
+    (6, 1, "41"),
+    (6, 2, "6"),
+    (7, 1, "3749"),
+    (7, 2, "11387"),
+    (8, 1, "14"),
+    (8, 2, "34"),
+    (9, 1, "1928"),
+    (9, 2, "2858"),
+    (10, 1, "36"),
+    (10, 2, "81"),
+    (11, 1, "55312"),
+    (11, 2, "65601038650482"),
+    (12, 1, "140"),
+    (12, 2, "80"),
+    (13, 1, "480"),
+    (13, 2, "875318608908"),
+    (14, 1, "12"),
+    (14, 2, "1"),
+    (15, 1, "2028"),
+    (15, 2, "9021"),
+    (16, 1, "7036"),
+    (16, 2, "45"),
+    (17, 1, "4,6,3,5,6,3,5,2,1,0"),
+    (17, 2, "117440"),
+    (18, 1, "22"),
+    (18, 2, "6,1"),
+    (19, 1, "6"),
+    (19, 2, "16"),
+    (20, 1, "1"),
+    (20, 2, "285"),
+    (21, 1, "126384"),
+    (21, 2, "126384"),
+    (22, 1, "37327623"),
+    (22, 2, "23"),
+    (23, 1, "7"),
+    (23, 2, "co,de,ka,ta"),
+    (24, 1, "4"),
+    (25, 1, "3"),
+];
+
+fn variants(input: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("crlf", input.replace('\n', "\r\n")),
+        ("bom", format!("\u{feff}{input}")),
+        ("trailing newline", format!("{input}\n")),
+        ("trailing blank lines", format!("{input}\n\n\n")),
+        (
+            "trailing spaces",
+            input
+                .lines()
+                .map(|line| format!("{line}  "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        (
+            "all of them",
+            format!("\u{feff}{}\r\n \r\n\r\n", input.replace('\n', " \r\n")),
+        ),
+    ]
+}
+
+#[test]
+fn test_every_day_accepts_input_variants() -> miette::Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for solution in registry::solutions() {
+        let day_dir = fixtures.join(format!("day-{:02}", solution.day));
+        let input = fs::read_to_string(day_dir.join(format!("part{}.txt", solution.part)))
+            .unwrap_or_else(|_| {
+                panic!(
+                    "missing example for day {} part {}",
+                    solution.day, solution.part
+                )
+            });
+        let params: Params = fs::read_to_string(day_dir.join("params.json"))
+            .map(|json| serde_json::from_str(&json).unwrap())
+            .unwrap_or_default();
+
+        let expected = EXAMPLE_ANSWERS
+            .iter()
+            .find(|(day, part, _)| (*day, *part) == (solution.day, solution.part))
+            .map(|(_, _, answer)| answer.to_string())
+            .unwrap_or_else(|| {
+                panic!(
+                    "no example answer for day {} part {}",
+                    solution.day, solution.part
+                )
+            });
+        assert_eq!(
+            expected,
+            solution.solve(&input, &params)?,
+            "day {} part {}",
+            solution.day,
+            solution.part
+        );
+        for (variant, input) in variants(input.trim_end()) {
+            let answer = solution.solve(&input, &params).unwrap_or_else(|report| {
+                panic!(
+                    "day {} part {} rejects {variant}: {report:?}",
+                    solution.day, solution.part
+                )
+            });
+            assert_eq!(
+                expected, answer,
+                "day {} part {} with {variant}",
+                solution.day, solution.part
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! `aoc solve --param` reports why a parameter was rejected, not just that it was, and a
+//! parameter out of range fails the same way from the command line and from `aoc.toml`.
+
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_unknown_param() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["solve", "1", "--param", "depth=3"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(Some(5), output.status.code(), "{stderr}");
+    assert!(stderr.contains("invalid parameters"), "{stderr}");
+    assert!(stderr.contains("unknown field `depth`"), "{stderr}");
+}
+
+#[test]
+fn test_out_of_range_param() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["solve", "14", "--param", "height=0"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(Some(5), output.status.code(), "{stderr}");
+    assert!(stderr.contains("aoc::invalid_parameter"), "{stderr}");
+}
+
+#[test]
+fn test_out_of_range_config() {
+    let path = std::env::temp_dir().join(format!("aoc-{}.toml", std::process::id()));
+    fs::write(&path, "[day-14]\nheight = 0\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("--config")
+        .arg(&path)
+        .args(["solve", "14"])
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(Some(5), output.status.code(), "{stderr}");
+    assert!(stderr.contains("Invalid `height` in [day-14]"), "{stderr}");
+}
@@ -0,0 +1,75 @@
+//! Every day's model prints its example input back exactly, parses its own output again, and
+//! rejects input that does not have the puzzle's format.
+//!
+//! The examples are the same `tests/fixtures/day-XX/partN.txt` the normalisation test uses.
+//! Days whose `Display` rebuilds the text from positions, like the grids, have round-trip tests
+//! of their own as well.
+
+use aoc::dump::{dump, first_mismatch};
+use std::fs;
+use std::path::Path;
+
+/// An input each day's parser rejects. Day 3 has none, its memory is corrupted anyway.
+const MALFORMED: &[(u8, &str)] = &[
+    (1, "3   4\n4"),
+    (2, "7 x"),
+    (4, "XMAS\nXMA"),
+    (5, "47|53\n\n75,x"),
+    (6, "..#\n..."),
+    (7, "190 10 19"),
+    (8, "..#."),
+    (9, "23x3"),
+    (10, "89.1"),
+    (11, "125 -17"),
+    (12, "AAAA\nBB.D"),
+    (13, "Button A: X+94, Y+34"),
+    (14, "p=0,4 v=3"),
+    (15, "#@.#\n\n<x"),
+    (16, "#S.#\n#..#"),
+    (
+        17,
+        "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,8,0",
+    ),
+    (18, "5,4\n4"),
+    (19, "r, wr\nbrwrr"),
+    (20, "#S.#\n#..#"),
+    (21, "029A\n98A0"),
+    (22, "1\n1O"),
+    (23, "kh-tc\nqpkh"),
+    (24, "x00: 2\n\nx00 AND y00 -> z00"),
+    (25, "#####\n.####\n.....\n.#...\n.....\n.....\n....."),
+];
+
+#[test]
+fn test_every_model_prints_its_input() -> miette::Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    for day in 1..=25 {
+        for part in [1, 2] {
+            let path = fixtures.join(format!("day-{day:02}/part{part}.txt"));
+            let Ok(input) = fs::read_to_string(&path) else {
+                continue;
+            };
+
+            let parsed = dump(day, &input).expect("every day has a model")?;
+            assert_eq!(
+                None,
+                first_mismatch(&input, &parsed.printed),
+                "{} prints differently",
+                path.display()
+            );
+
+            let reparsed = dump(day, &parsed.printed).expect("every day has a model")?;
+            assert_eq!(parsed, reparsed, "{} parses differently", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_every_model_rejects_malformed_input() {
+    for &(day, input) in MALFORMED {
+        let parsed = dump(day, input).expect("every day has a model");
+        assert!(parsed.is_err(), "day {day} accepts {input:?}");
+    }
+}
@@ -0,0 +1,241 @@
+//! The streaming solvers give the same answers as the in-memory ones, also when their input is
+//! far larger than memory and comes through a pipe.
+//!
+//! The large inputs are a day's example repeated many times, written by a thread while the
+//! `aoc` binary reads them, so they are never stored anywhere. Repeating the example multiplies
+//! its answers by the number of repetitions, which gives the answers to expect.
+//!
+//! `cargo test` pipes a few MiB through every streaming day. The GiB the streaming solvers are
+//! meant for take too long for that, so `just stream-test` is the check for them.
+
+use aoc::params::Params;
+use aoc::registry;
+use aoc::runner::read_input;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// The most a streaming solve may allocate at once, whatever the size of its input.
+const MAX_PEAK_BYTES: f64 = 8.0 * 1024.0 * 1024.0;
+
+fn example(day: u8, part: u8) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("tests/fixtures/day-{day:02}/part{part}.txt"));
+    fs::read_to_string(&path).unwrap_or_else(|_| panic!("missing {}", path.display()))
+}
+
+/// A day's input as a header, written once, and a block that can be repeated.
+struct Repeated {
+    day: u8,
+    header: String,
+    block: String,
+}
+
+impl Repeated {
+    fn new(day: u8) -> Self {
+        let (header, block) = match day {
+            // The block starts enabled, whatever the previous one ended with
+            3 => (String::new(), format!("do(){}\n", example(3, 2).trim_end())),
+            // A single line, however long
+            11 => (String::new(), format!("{} ", example(11, 1).trim_end())),
+            19 => {
+                let example = example(19, 1);
+                let (patterns, designs) = example.split_once("\n\n").unwrap();
+                (
+                    format!("{patterns}\n\n"),
+                    format!("{}\n", designs.trim_end()),
+                )
+            }
+            _ => (String::new(), format!("{}\n", example(day, 1).trim_end())),
+        };
+        Repeated { day, header, block }
+    }
+
+    /// The in-memory answers for the header and a single block.
+    fn answers(&self) -> (u128, u128) {
+        let input = format!("{}{}", self.header, self.block);
+        let (part1, part2) = registry::find_both(self.day)
+            .unwrap()
+            .solve(&input, &Params::default())
+            .unwrap();
+        (part1.parse().unwrap(), part2.parse().unwrap())
+    }
+
+    /// The answers for the header and `blocks` blocks.
+    fn expected(&self, blocks: u64) -> (u128, u128) {
+        let (part1, part2) = self.answers();
+        let blocks = u128::from(blocks);
+        match self.day {
+            // Every left ID meets every copy of itself on the right
+            1 => (part1 * blocks, part2 * blocks * blocks),
+            _ => (part1 * blocks, part2 * blocks),
+        }
+    }
+}
+
+/// Pipes the header and `blocks` blocks into `aoc solve --stream`, returning both answers and
+/// the peak of the allocations it reports.
+fn solve_through_pipe(repeated: &Repeated, blocks: u64) -> ((u128, u128), f64) {
+    let (header, block) = (repeated.header.clone(), repeated.block.clone());
+    pipe_into_solve(repeated.day, move |stdin| {
+        stdin.write_all(header.as_bytes())?;
+        for _ in 0..blocks {
+            stdin.write_all(block.as_bytes())?;
+        }
+        Ok(())
+    })
+}
+
+/// Pipes what `write` writes into `aoc solve --stream` for `day`, returning both answers and
+/// the peak of the allocations it reports.
+fn pipe_into_solve(
+    day: u8,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()> + Send + 'static,
+) -> ((u128, u128), f64) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["solve", &day.to_string(), "--stream", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let stdin = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || {
+        let mut stdin = BufWriter::with_capacity(1 << 16, stdin);
+        write(&mut stdin)?;
+        stdin.flush()
+    });
+
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "day {}: {stdout}", day);
+
+    let lines = stdout.lines().collect::<Vec<_>>();
+    let [part1, part2] = lines.as_slice() else {
+        panic!("day {}: {stdout}", day)
+    };
+    let answer = |line: &str| -> u128 {
+        let (_, rest) = line.split_once(": ").unwrap();
+        rest.split_whitespace().next().unwrap().parse().unwrap()
+    };
+    ((answer(part1), answer(part2)), peak_bytes(part2))
+}
+
+/// The peak from stats such as `(1.234 ms, 5 allocations, peak 1.5 KiB)`.
+fn peak_bytes(line: &str) -> f64 {
+    let (_, peak) = line.rsplit_once("peak ").unwrap();
+    let mut words = peak.split([' ', ')']);
+    let value = words.next().unwrap().parse::<f64>().unwrap();
+    match words.next().unwrap() {
+        "B" => value,
+        "KiB" => value * 1024.0,
+        "MiB" => value * 1024.0 * 1024.0,
+        _ => panic!("unknown unit in {line}"),
+    }
+}
+
+#[test]
+fn test_streaming_matches_in_memory() -> miette::Result<()> {
+    for streaming in registry::streaming() {
+        let day = streaming.day;
+        let examples = (example(day, 1), example(day, 2));
+        let mut inputs = vec![
+            ("the part 1 example", examples.0.clone()),
+            ("the part 2 example", examples.1.clone()),
+            (
+                "a saved example",
+                format!("\u{feff}{}\r\n \r\n\r\n", examples.0.replace('\n', " \r\n")),
+            ),
+        ];
+        if let Ok(input) = read_input(day, 1) {
+            inputs.push(("the puzzle input", input));
+        }
+
+        for (name, input) in inputs {
+            let expected = registry::find_both(day)
+                .unwrap()
+                .solve(&input, &Params::default())?;
+            let answers = streaming.solve(&mut input.as_bytes())?;
+            assert_eq!(expected, answers, "day {day} on {name}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_stream_mebibytes_through_pipe() {
+    for streaming in registry::streaming() {
+        let repeated = Repeated::new(streaming.day);
+        // The buyers take longest, there are 2000 secret numbers each
+        let bytes = if streaming.day == 22 {
+            64 << 10
+        } else {
+            2 << 20
+        };
+        let blocks = bytes / repeated.block.len() as u64;
+
+        let (answers, peak) = solve_through_pipe(&repeated, blocks);
+        assert_eq!(repeated.expected(blocks), answers, "day {}", streaming.day);
+        assert!(peak < MAX_PEAK_BYTES, "day {}: peak {peak}", streaming.day);
+    }
+}
+
+/// Day 11 remembers counts by stone, so stones that are all different would grow its memo
+/// without end if it were not bounded.
+#[test]
+fn test_stream_distinct_stones_through_pipe() {
+    let stones = (0..2000_u64)
+        .map(|index| (index * 7919 + 1_000_003).to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (part1, part2) = registry::find_both(11)
+        .unwrap()
+        .solve(&stones, &Params::default())
+        .unwrap();
+
+    let (answers, peak) = pipe_into_solve(11, move |stdin| stdin.write_all(stones.as_bytes()));
+    assert_eq!((part1.parse().unwrap(), part2.parse().unwrap()), answers);
+    assert!(peak < MAX_PEAK_BYTES, "day 11: peak {peak}");
+}
+
+/// Day 1 counts every ID, so its memory grows with the number of different IDs. The puzzle's
+/// IDs have five digits, so there are at most 90 000 of them in each list.
+#[test]
+fn test_stream_distinct_ids_through_pipe() {
+    let ids = 10000..100000_u64;
+    let lines = ids
+        .clone()
+        .zip(ids.clone().rev())
+        .map(|(left, right)| format!("{left}   {right}\n"))
+        .collect::<String>();
+
+    let (answers, peak) = pipe_into_solve(1, move |stdin| stdin.write_all(lines.as_bytes()));
+    // Both lists hold every ID once, so sorted they pair up every ID with itself
+    assert_eq!((0, u128::from(ids.sum::<u64>())), answers);
+    assert!(peak < MAX_PEAK_BYTES, "day 1: peak {peak}");
+}
+
+/// Streams several GiB, `AOC_STREAM_GIB` of them, through the days that get through it
+/// quickest. The others would take hours at that size. Run it in release mode with
+/// `just stream-test`.
+#[test]
+#[ignore]
+fn test_stream_gigabytes_through_pipe() {
+    let gib = std::env::var("AOC_STREAM_GIB")
+        .ok()
+        .and_then(|gib| gib.parse::<u64>().ok())
+        .unwrap_or(4);
+
+    for day in [1, 3] {
+        let repeated = Repeated::new(day);
+        let blocks = (gib << 30) / repeated.block.len() as u64;
+
+        let (answers, peak) = solve_through_pipe(&repeated, blocks);
+        assert_eq!(repeated.expected(blocks), answers, "day {day}");
+        assert!(peak < MAX_PEAK_BYTES, "day {day}: peak {peak}");
+    }
+}
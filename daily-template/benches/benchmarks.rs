@@ -1,22 +1,29 @@
+use aoc_common::input::bench_input;
 use {{crate_name}}::*;
+use std::sync::LazyLock;
+
+static INPUT1: LazyLock<String> = LazyLock::new(|| bench_input(env!("CARGO_MANIFEST_DIR"), 1));
+static INPUT2: LazyLock<String> = LazyLock::new(|| bench_input(env!("CARGO_MANIFEST_DIR"), 2));
 
 fn main() {
+    // Load the inputs up front, so no benchmark measures it
+    LazyLock::force(&INPUT1);
+    LazyLock::force(&INPUT2);
     // Run registered benchmarks.
     divan::main();
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
+    part1::process(divan::black_box(INPUT1.as_str())).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+    part2::process(divan::black_box(INPUT2.as_str())).unwrap();
+}
+
+#[divan::bench]
+fn solve_both() {
+    {{crate_name}}::solve_both(divan::black_box(INPUT1.as_str())).unwrap();
+}
@@ -1,3 +1,4 @@
+use aoc_common::input;
 use {{crate_name}}::part1::process;
 use miette::Context;
 
@@ -13,7 +14,7 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
+    let file = &input::load(env!("CARGO_MANIFEST_DIR"), 1).context("load the input")?;
     let result = process(file).context("process part 1")?;
     println!("{}", result);
     Ok(())
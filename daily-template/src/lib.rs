@@ -1,4 +1,18 @@
+//! {{project-name}}: TODO.
+//!
+//! Wire the day into `aoc` with `solution!` and `both!` in `aoc/src/registry.rs` and `model!` in
+//! `aoc/src/dump.rs`, and add an input [`model::parse`] rejects to `aoc/tests/round_trip.rs`.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let model = model::parse(input)?;
+    Ok((part1::solve(&model)?, part2::solve(&model)?))
+}
@@ -0,0 +1,49 @@
+use crate::custom_error::AocError;
+use nom::character::complete::{newline, not_line_ending};
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::terminated;
+use nom::{Finish, IResult, Parser};
+use serde::Serialize;
+use std::fmt;
+
+/// The puzzle input. Replace the lines with the day's own model, and keep [`parse`] rejecting
+/// input that does not have the puzzle's format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Model {
+    pub lines: Vec<String>,
+}
+
+fn model(input: &str) -> IResult<&str, Model> {
+    separated_list1(newline, not_line_ending.map(str::to_string))
+        .map(|lines| Model { lines })
+        .parse(input)
+}
+
+pub fn parse(input: &str) -> Result<Model, AocError> {
+    all_consuming(terminated(model, opt(newline)))(input)
+        .finish()
+        .map(|(_, model)| model)
+        .map_err(|err| AocError::malformed(format!("Invalid input at: {:.20}", err.input)))
+}
+
+/// Prints the model back in the puzzle input format, so `aoc dump` and the round-trip tests can
+/// compare it with the input.
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.lines.iter().try_for_each(|line| writeln!(f, "{line}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() -> miette::Result<()> {
+        todo!("haven't built test yet");
+        let input = "";
+        assert_eq!(input, parse(input)?.to_string());
+        Ok(())
+    }
+}
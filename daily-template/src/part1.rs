@@ -1,10 +1,13 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, Model};
+
+pub fn solve(_model: &Model) -> miette::Result<String, AocError> {
+    todo!("{{project-name}} - part 1");
+}
 
 #[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
 #[cfg(test)]
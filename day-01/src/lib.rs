@@ -1,4 +1,22 @@
+//! Day 1: Historian Hysteria.
+//!
+//! [`model::parse`] reads the two location ID lists, [`part1::total_distance`] and
+//! [`part2::similarity_score`] compare them.
+//!
+//! [`stream::solve_both`] reads the lists from a reader instead, counting the IDs rather than
+//! keeping them.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+pub mod stream;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let lists = model::parse(input)?;
+    Ok((part1::solve(&lists)?, part2::solve(&lists)?))
+}
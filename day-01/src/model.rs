@@ -0,0 +1,43 @@
+use crate::custom_error::AocError;
+use serde::Serialize;
+use std::fmt;
+
+/// The two location ID lists, one column each.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LocationLists {
+    pub left: Vec<u32>,
+    pub right: Vec<u32>,
+}
+
+pub fn parse(input: &str) -> Result<LocationLists, AocError> {
+    let mut lists = LocationLists {
+        left: Vec::new(),
+        right: Vec::new(),
+    };
+
+    for line in input.lines() {
+        let (left, right) = parse_line(line)?;
+        lists.left.push(left);
+        lists.right.push(right);
+    }
+
+    Ok(lists)
+}
+
+/// The left and right location ID of a single line.
+pub fn parse_line(line: &str) -> Result<(u32, u32), AocError> {
+    let mut ids = line.split_whitespace().map(str::parse::<u32>);
+    match (ids.next(), ids.next(), ids.next()) {
+        (Some(Ok(left)), Some(Ok(right)), None) => Ok((left, right)),
+        _ => Err(AocError::malformed(format!("Invalid line: {line}"))),
+    }
+}
+
+impl fmt::Display for LocationLists {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (left, right) in self.left.iter().zip(&self.right) {
+            writeln!(f, "{left}   {right}")?;
+        }
+        Ok(())
+    }
+}
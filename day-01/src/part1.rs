@@ -1,43 +1,28 @@
 use crate::custom_error::AocError;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::multispace1;
-use nom::multi::fold_many1;
-use nom::sequence::{separated_pair, terminated};
-use nom::IResult;
+use crate::model::{parse, LocationLists};
+
+/// Pairs up the smallest IDs of both lists, then the second smallest and so on, and sums up
+/// how far apart the IDs of each pair are.
+pub fn total_distance(lists: &LocationLists) -> u32 {
+    let mut left = lists.left.clone();
+    let mut right = lists.right.clone();
+    left.sort();
+    right.sort();
+
+    left.iter()
+        .zip(right.iter())
+        .map(|(v1, v2)| v1.abs_diff(*v2))
+        .sum()
+}
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
-    fold_many1(
-        terminated(
-            separated_pair(complete::u32, multispace1, complete::u32),
-            tag("\n"),
-        ),
-        || (Vec::new(), Vec::new()),
-        |mut acc, item| {
-            acc.0.push(item.0);
-            acc.1.push(item.1);
-            acc
-        },
-    )(input)
+/// Solves part 1 for the parsed location lists.
+pub fn solve(lists: &LocationLists) -> Result<String, AocError> {
+    Ok(total_distance(lists).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (_, mut lists) = parse_input(_input).unwrap();
-
-    lists.0.sort();
-    lists.1.sort();
-
-    dbg!(&lists);
-
-    let res = lists
-        .0
-        .iter()
-        .zip(lists.1.iter())
-        .map(|(v1, v2)| (*v1 as i32 - *v2 as i32).abs())
-        .sum::<i32>();
-
-    Ok(res.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
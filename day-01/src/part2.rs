@@ -1,46 +1,32 @@
 use crate::custom_error::AocError;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::multispace1;
-use nom::multi::fold_many0;
-use nom::sequence::{separated_pair, terminated};
-use nom::IResult;
+use crate::model::{parse, LocationLists};
 use std::collections::HashMap;
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
-    fold_many0(
-        terminated(
-            separated_pair(complete::u32, multispace1, complete::u32),
-            tag("\n"),
-        ),
-        || (Vec::new(), Vec::new()),
-        |mut acc, item| {
-            acc.0.push(item.0);
-            acc.1.push(item.1);
-            acc
-        },
-    )(input)
-}
-
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (_, lists) = parse_input(_input).unwrap();
-
-    let frequencies = lists.1.iter().fold(HashMap::new(), |mut frequency, item| {
-        dbg!(&item);
-        frequency.entry(item).and_modify(|e| *e += 1).or_insert(1);
-        frequency
-    });
-
-    dbg!(&frequencies);
+/// Adds up every ID of the left list multiplied by how often it appears in the right list.
+pub fn similarity_score(lists: &LocationLists) -> u32 {
+    let frequencies = lists
+        .right
+        .iter()
+        .fold(HashMap::new(), |mut frequency, item| {
+            frequency.entry(item).and_modify(|e| *e += 1).or_insert(1);
+            frequency
+        });
 
-    let res = lists
-        .0
+    lists
+        .left
         .iter()
         .filter_map(|item| frequencies.get(item).map(|freq| item * freq))
-        .sum::<u32>();
+        .sum::<u32>()
+}
+
+/// Solves part 2 for the parsed location lists.
+pub fn solve(lists: &LocationLists) -> Result<String, AocError> {
+    Ok(similarity_score(lists).to_string())
+}
 
-    Ok(res.to_string())
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -0,0 +1,80 @@
+use crate::custom_error::AocError;
+use crate::model::parse_line;
+use aoc_common::stream::for_each_line;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+/// How often each ID appears in a list, in order of the IDs.
+type Counts = BTreeMap<u32, u64>;
+
+/// [`crate::part1::total_distance`] on the counted lists: walking both in order pairs up the
+/// smallest IDs first, as sorting them would.
+fn total_distance(left: &Counts, right: &Counts) -> u64 {
+    let mut left = left.iter().map(|(&id, &count)| (id, count));
+    let mut right = right.iter().map(|(&id, &count)| (id, count));
+    let (mut l, mut r) = (left.next(), right.next());
+    let mut distance = 0;
+    while let (Some((left_id, left_count)), Some((right_id, right_count))) = (l, r) {
+        let pairs = left_count.min(right_count);
+        distance += u64::from(left_id.abs_diff(right_id)) * pairs;
+        l = match left_count - pairs {
+            0 => left.next(),
+            rest => Some((left_id, rest)),
+        };
+        r = match right_count - pairs {
+            0 => right.next(),
+            rest => Some((right_id, rest)),
+        };
+    }
+    distance
+}
+
+/// [`crate::part2::similarity_score`] on the counted lists.
+fn similarity_score(left: &Counts, right: &Counts) -> u64 {
+    left.iter()
+        .filter_map(|(&id, &count)| right.get(&id).map(|freq| u64::from(id) * count * freq))
+        .sum()
+}
+
+/// Solves both parts a line at a time, remembering only how often each ID appears. The memory
+/// grows with the number of distinct IDs, not with the number of lines: the puzzle's IDs have
+/// five digits, which bounds the counts to 90 000 per list, a few MiB. IDs of any size are read,
+/// and with them the memory is only bounded by how many there are.
+pub fn solve_both(reader: impl BufRead) -> miette::Result<(String, String), AocError> {
+    let mut left = Counts::new();
+    let mut right = Counts::new();
+    for_each_line(reader, |line| {
+        let (l, r) = parse_line(line)?;
+        *left.entry(l).or_default() += 1;
+        *right.entry(r).or_default() += 1;
+        Ok::<_, AocError>(())
+    })?;
+
+    Ok((
+        total_distance(&left, &right).to_string(),
+        similarity_score(&left, &right).to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_both() -> miette::Result<()> {
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+        assert_eq!(crate::solve_both(input)?, solve_both(input.as_bytes())?);
+        assert_eq!(
+            ("22".to_string(), "124".to_string()),
+            solve_both(input.repeat(2).as_bytes())?
+        );
+        assert!(solve_both("3   4\n4\n".as_bytes()).is_err());
+        Ok(())
+    }
+}
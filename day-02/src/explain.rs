@@ -0,0 +1,108 @@
+use crate::model::Reports;
+use crate::part1::is_safe;
+use crate::part2::{dampen, Dampened};
+use serde::Serialize;
+use std::fmt;
+
+/// How one report was judged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// The report's line in the input, counting from 1
+    pub line: usize,
+    pub levels: Vec<i32>,
+    /// Whether the report is safe as it is, for part 1
+    pub safe: bool,
+    /// What the Problem Dampener made of it, for part 2
+    pub dampened: Dampened,
+}
+
+/// Which reports are safe, and which level the dampener removed to make a report safe.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    pub reports: Vec<Report>,
+}
+
+pub fn explain(reports: &Reports) -> Explanation {
+    Explanation {
+        reports: (1..)
+            .zip(&reports.0)
+            .map(|(line, levels)| Report {
+                line,
+                levels: levels.clone(),
+                safe: is_safe(levels),
+                dampened: dampen(levels),
+            })
+            .collect(),
+    }
+}
+
+impl Explanation {
+    /// The answers, counted from the judged reports.
+    pub fn answers(&self) -> (String, String) {
+        let safe = self.reports.iter().filter(|report| report.safe).count();
+        let safe_with_dampener = self
+            .reports
+            .iter()
+            .filter(|report| report.dampened != Dampened::Unsafe)
+            .count();
+        (safe.to_string(), safe_with_dampener.to_string())
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for report in &self.reports {
+            // The removed level is shown in brackets
+            let levels = report
+                .levels
+                .iter()
+                .enumerate()
+                .map(|(index, level)| match report.dampened {
+                    Dampened::Removed(removed) if removed == index => format!("[{level}]"),
+                    _ => level.to_string(),
+                })
+                .collect::<Vec<_>>();
+            let verdict = match (report.safe, report.dampened) {
+                (true, _) => "safe".to_string(),
+                (false, Dampened::Removed(removed)) => {
+                    format!("safe without level {}", removed + 1)
+                }
+                (false, Dampened::NotNeeded) => "safe for the dampener".to_string(),
+                (false, Dampened::Unsafe) => "unsafe".to_string(),
+            };
+            writeln!(f, "{:>4}: {}  {verdict}", report.line, levels.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse;
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+        let explanation = explain(&parse(input)?);
+        assert_eq!(crate::solve_both(input)?, explanation.answers());
+        assert_eq!(Dampened::Removed(1), explanation.reports[3].dampened);
+        assert_eq!(
+            "   1: 7 6 4 2 1  safe
+   2: 1 2 7 8 9  unsafe
+   3: 9 7 6 2 1  unsafe
+   4: 1 [3] 2 4 5  safe without level 2
+   5: 8 6 [4] 4 1  safe without level 3
+   6: 1 3 6 7 9  safe
+",
+            explanation.to_string()
+        );
+        Ok(())
+    }
+}
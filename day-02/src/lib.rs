@@ -1,4 +1,23 @@
+//! Day 2: Red-Nosed Reports.
+//!
+//! [`model::parse`] reads the reports, [`part1::is_safe`] and
+//! [`part2::is_safe_with_dampener`] check a single report's levels.
+//!
+//! [`stream::solve_both`] checks the reports as it reads them, for inputs larger than memory.
+//! [`explain::explain`] shows how each report was judged.
+
 pub mod custom_error;
+pub mod model;
 
+pub mod explain;
 pub mod part1;
 pub mod part2;
+pub mod stream;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let reports = model::parse(input)?;
+    Ok((part1::solve(&reports)?, part2::solve(&reports)?))
+}
@@ -0,0 +1,36 @@
+use crate::custom_error::AocError;
+use serde::Serialize;
+use std::fmt;
+
+/// The unusual data, one report of levels per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Reports(pub Vec<Vec<i32>>);
+
+pub fn parse(input: &str) -> Result<Reports, AocError> {
+    input
+        .lines()
+        .map(parse_report)
+        .collect::<Result<_, _>>()
+        .map(Reports)
+}
+
+/// The levels of a single report.
+pub fn parse_report(line: &str) -> Result<Vec<i32>, AocError> {
+    line.split(' ')
+        .map(|level| {
+            level
+                .parse()
+                .map_err(|_| AocError::malformed(format!("Invalid level: {level}")))
+        })
+        .collect()
+}
+
+impl fmt::Display for Reports {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for report in &self.0 {
+            let levels: Vec<String> = report.iter().map(i32::to_string).collect();
+            writeln!(f, "{}", levels.join(" "))?;
+        }
+        Ok(())
+    }
+}
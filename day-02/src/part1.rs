@@ -1,49 +1,55 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, Reports};
 use itertools::Itertools;
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> Result<String, AocError> {
-    let res = _input
-        .lines()
-        .map(|line| {
-            line.split(' ')
-                .map(|number| number.parse::<i32>().unwrap())
-                .tuple_windows()
-                .fold(
-                    (None, true),
-                    |acc: (Option<bool>, bool), window: (i32, i32)| {
-                        let abs_diff = window.1.abs_diff(window.0);
-                        if !(1..=3).contains(&abs_diff) {
-                            (None, false)
-                        } else {
-                            match acc {
-                                (inc, false) => (inc, false),
-                                (None, true) => {
-                                    if window.0 < window.1 {
-                                        (Some(true), true)
-                                    } else {
-                                        (Some(false), true)
-                                    }
-                                }
-                                (Some(inc), true) => {
-                                    if inc {
-                                        (Some(inc), window.1 - window.0 > 0)
-                                    } else {
-                                        (Some(inc), window.0 - window.1 > 0)
-                                    }
-                                }
+/// A report is safe if its levels all increase or all decrease, by one to three each step.
+pub fn is_safe(levels: &[i32]) -> bool {
+    levels
+        .iter()
+        .copied()
+        .tuple_windows()
+        .fold(
+            (None, true),
+            |acc: (Option<bool>, bool), window: (i32, i32)| {
+                let abs_diff = window.1.abs_diff(window.0);
+                if !(1..=3).contains(&abs_diff) {
+                    (None, false)
+                } else {
+                    match acc {
+                        (inc, false) => (inc, false),
+                        (None, true) => {
+                            if window.0 < window.1 {
+                                (Some(true), true)
+                            } else {
+                                (Some(false), true)
+                            }
+                        }
+                        (Some(inc), true) => {
+                            if inc {
+                                (Some(inc), window.1 - window.0 > 0)
+                            } else {
+                                (Some(inc), window.0 - window.1 > 0)
                             }
                         }
-                    },
-                )
-                .1
-        })
-        .filter(|&safe| safe)
-        .count();
+                    }
+                }
+            },
+        )
+        .1
+}
+
+/// Solves part 1 for the parsed reports.
+pub fn solve(reports: &Reports) -> Result<String, AocError> {
+    let res = reports.0.iter().filter(|levels| is_safe(levels)).count();
 
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,7 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, Reports};
 use itertools::Itertools;
+use serde::Serialize;
 
 fn find_faulty_index(numbers: &[i32]) -> Option<usize> {
     let diffs = numbers
@@ -14,46 +16,63 @@ fn find_faulty_index(numbers: &[i32]) -> Option<usize> {
         .counts()
         .into_iter()
         .max_by(|a, b| a.1.cmp(&b.1))
-        .map(|(k, _v)| k)
-        .unwrap();
+        .map(|(k, _v)| k)?;
 
     diffs
         .iter()
         .map(|diff| diff.signum() == direction && (1..=3).contains(&diff.abs()))
         .position(|good| !good)
 }
-fn is_report_safe(report: &str) -> bool {
-    let numbers = report
-        .split(' ')
-        .map(|number| number.parse::<i32>().unwrap())
-        .collect_vec();
 
-    let bad_index = find_faulty_index(&numbers);
+/// What the Problem Dampener makes of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dampened {
+    /// The report is safe without removing a level
+    NotNeeded,
+    /// The report is safe once the level at this index is removed
+    Removed(usize),
+    /// The report is unsafe even with a level removed
+    Unsafe,
+}
+
+/// Tries removing either level of the first bad step of the report.
+pub fn dampen(levels: &[i32]) -> Dampened {
+    let Some(index) = find_faulty_index(levels) else {
+        return Dampened::NotNeeded;
+    };
 
-    match bad_index {
-        None => true,
-        Some(index) => {
-            let mut first_removed = numbers.clone();
-            first_removed.remove(index);
-            let mut second_removed = numbers.clone();
-            second_removed.remove(index + 1);
-            find_faulty_index(&first_removed).is_none()
-                || find_faulty_index(&second_removed).is_none()
-        }
-    }
+    [index, index + 1]
+        .into_iter()
+        .find(|&removed| {
+            let mut rest = levels.to_vec();
+            rest.remove(removed);
+            find_faulty_index(&rest).is_none()
+        })
+        .map_or(Dampened::Unsafe, Dampened::Removed)
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> Result<String, AocError> {
-    let res = _input
-        .lines()
-        .map(is_report_safe)
-        .filter(|&safe| safe)
+/// Like [`crate::part1::is_safe`], but tolerating a single bad level.
+pub fn is_safe_with_dampener(levels: &[i32]) -> bool {
+    dampen(levels) != Dampened::Unsafe
+}
+
+/// Solves part 2 for the parsed reports.
+pub fn solve(reports: &Reports) -> Result<String, AocError> {
+    let res = reports
+        .0
+        .iter()
+        .filter(|levels| is_safe_with_dampener(levels))
         .count();
 
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,4 +88,12 @@ mod tests {
         assert_eq!("4", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_dampen() {
+        assert_eq!(Dampened::NotNeeded, dampen(&[7, 6, 4, 2, 1]));
+        assert_eq!(Dampened::Unsafe, dampen(&[1, 2, 7, 8, 9]));
+        assert_eq!(Dampened::Removed(1), dampen(&[1, 3, 2, 4, 5]));
+        assert_eq!(Dampened::Removed(2), dampen(&[8, 6, 4, 4, 1]));
+    }
 }
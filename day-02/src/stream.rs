@@ -0,0 +1,39 @@
+use crate::custom_error::AocError;
+use crate::model::parse_report;
+use crate::part1::is_safe;
+use crate::part2::is_safe_with_dampener;
+use aoc_common::stream::for_each_line;
+use std::io::BufRead;
+
+/// Solves both parts a report at a time, so only the current line is held in memory.
+pub fn solve_both(reader: impl BufRead) -> miette::Result<(String, String), AocError> {
+    let mut safe = 0_u64;
+    let mut safe_with_dampener = 0_u64;
+    for_each_line(reader, |line| {
+        let levels = parse_report(line)?;
+        safe += u64::from(is_safe(&levels));
+        safe_with_dampener += u64::from(is_safe_with_dampener(&levels));
+        Ok::<_, AocError>(())
+    })?;
+
+    Ok((safe.to_string(), safe_with_dampener.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_both() -> miette::Result<()> {
+        let input = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+        assert_eq!(crate::solve_both(input)?, solve_both(input.as_bytes())?);
+        assert!(solve_both("7 6 4\n\n1 2\n".as_bytes()).is_err());
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
+use aoc_common::input::bench_input;
 use criterion::{criterion_group, criterion_main, Criterion};
 use day_03::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 1);
 
     let mut group = c.benchmark_group("day_03::part1");
     group.bench_with_input("part1", input, |b, input| b.iter(|| part1::process(input)));
@@ -11,7 +12,7 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 2);
 
     let mut group = c.benchmark_group("day_03::part2");
     group.bench_with_input("part2", input, |b, input| b.iter(|| part2::process(input)));
@@ -19,9 +20,19 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
     group.finish();
 }
 
+fn criterion_benchmark_solve_both(c: &mut Criterion) {
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 1);
+
+    let mut group = c.benchmark_group("day_03::solve_both");
+    group.bench_with_input("solve_both", input, |b, input| b.iter(|| solve_both(input)));
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_solve_both
 );
 criterion_main!(benches);
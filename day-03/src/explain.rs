@@ -0,0 +1,105 @@
+use crate::model::{Memory, Token};
+use serde::Serialize;
+use std::fmt;
+
+/// A `mul` instruction found in the corrupted memory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Mul {
+    /// Where the instruction starts, both counting from 1
+    pub line: usize,
+    pub column: usize,
+    pub operands: (i32, i32),
+    pub product: i64,
+    /// Whether no `don't()` without a `do()` after it came before, for part 2
+    pub enabled: bool,
+}
+
+/// Every `mul` instruction, and whether it was enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    pub muls: Vec<Mul>,
+}
+
+pub fn explain(memory: &Memory) -> Explanation {
+    let mut muls = Vec::new();
+    let (mut line, mut column) = (1, 1);
+    let mut enabled = true;
+    for token in &memory.0 {
+        match *token {
+            Token::Mul(v1, v2) => muls.push(Mul {
+                line,
+                column,
+                operands: (v1, v2),
+                product: i64::from(v1) * i64::from(v2),
+                enabled,
+            }),
+            Token::Do => enabled = true,
+            Token::Dont => enabled = false,
+            Token::Garbage(_) => {}
+        }
+        // The memory prints back to the input, so the printed tokens tell the positions
+        for c in token.to_string().chars() {
+            match c {
+                '\n' => (line, column) = (line + 1, 1),
+                _ => column += 1,
+            }
+        }
+    }
+    Explanation { muls }
+}
+
+impl Explanation {
+    /// The answers, added up from the instructions.
+    pub fn answers(&self) -> (String, String) {
+        let sum = self.muls.iter().map(|mul| mul.product).sum::<i64>();
+        let enabled_sum = self
+            .muls
+            .iter()
+            .filter(|mul| mul.enabled)
+            .map(|mul| mul.product)
+            .sum::<i64>();
+        (sum.to_string(), enabled_sum.to_string())
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mul in &self.muls {
+            let (v1, v2) = mul.operands;
+            writeln!(
+                f,
+                "{:>4}:{:<5} mul({v1},{v2}) = {}  {}",
+                mul.line,
+                mul.column,
+                mul.product,
+                if mul.enabled { "enabled" } else { "disabled" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse;
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+mul(1,1)
+";
+        let explanation = explain(&parse(input)?);
+        assert_eq!(crate::solve_both(input)?, explanation.answers());
+        assert_eq!(
+            "   1:2     mul(2,4) = 8  enabled
+   1:29    mul(5,5) = 25  disabled
+   1:49    mul(11,8) = 88  disabled
+   1:65    mul(8,5) = 40  enabled
+   2:1     mul(1,1) = 1  enabled
+",
+            explanation.to_string()
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,23 @@
+//! Day 3: Mull It Over.
+//!
+//! [`model::parse`] finds the instructions in the corrupted memory,
+//! [`part1::sum_of_products`] and [`part2::sum_of_enabled_products`] execute them.
+//!
+//! [`stream::solve_both`] executes the memory as it is read, a line at a time.
+//! [`explain::explain`] lists the `mul` instructions and whether each was enabled.
+
 pub mod custom_error;
+pub mod model;
 
+pub mod explain;
 pub mod part1;
 pub mod part2;
+pub mod stream;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let memory = model::parse(input)?;
+    Ok((part1::solve(&memory)?, part2::solve(&memory)?))
+}
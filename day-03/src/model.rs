@@ -0,0 +1,86 @@
+use crate::custom_error::AocError;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::sequence::{delimited, separated_pair};
+use nom::{IResult, Parser};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Token {
+    Mul(i32, i32),
+    Do,
+    Dont,
+    /// Corrupted characters between instructions
+    Garbage(String),
+}
+
+/// The corrupted memory, split into instructions and the garbage around them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Memory(pub Vec<Token>);
+
+impl Memory {
+    pub fn instructions(&self) -> impl Iterator<Item = &Token> {
+        self.0
+            .iter()
+            .filter(|token| !matches!(token, Token::Garbage(_)))
+    }
+}
+
+fn instruction(input: &str) -> IResult<&str, Token> {
+    alt((
+        delimited(
+            tag("mul("),
+            separated_pair(complete::i32, tag(","), complete::i32),
+            tag(")"),
+        )
+        .map(|(v1, v2)| Token::Mul(v1, v2)),
+        tag("do()").map(|_| Token::Do),
+        tag("don't()").map(|_| Token::Dont),
+    ))(input)
+}
+
+pub fn parse(input: &str) -> Result<Memory, AocError> {
+    let mut tokens = Vec::new();
+    let mut garbage = String::new();
+    let mut rest = input;
+
+    while let Some(c) = rest.chars().next() {
+        match instruction(rest) {
+            Ok((remaining, token)) => {
+                if !garbage.is_empty() {
+                    tokens.push(Token::Garbage(std::mem::take(&mut garbage)));
+                }
+                tokens.push(token);
+                rest = remaining;
+            }
+            Err(_) => {
+                garbage.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !garbage.is_empty() {
+        tokens.push(Token::Garbage(garbage));
+    }
+
+    Ok(Memory(tokens))
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Mul(v1, v2) => write!(f, "mul({v1},{v2})"),
+            Token::Do => write!(f, "do()"),
+            Token::Dont => write!(f, "don't()"),
+            Token::Garbage(garbage) => write!(f, "{garbage}"),
+        }
+    }
+}
+
+impl fmt::Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|token| write!(f, "{token}"))
+    }
+}
@@ -1,34 +1,25 @@
 use crate::custom_error::AocError;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::anychar;
-use nom::multi::{many0, many_till};
-use nom::sequence::{delimited, separated_pair};
-use nom::{IResult, Parser};
+use crate::model::{parse, Memory, Token};
 
-fn parse(input: &str) -> IResult<&str, Vec<(i32, i32)>> {
-    many0(
-        many_till(
-            anychar,
-            delimited(
-                tag("mul("),
-                separated_pair(complete::i32, tag(","), complete::i32),
-                tag(")"),
-            ),
-        )
-        .map(|(_disc, values)| values),
-    )(input)
+/// Adds up the results of every `mul` instruction, ignoring `do()` and `don't()`.
+pub fn sum_of_products(memory: &Memory) -> i32 {
+    memory
+        .instructions()
+        .map(|token| match token {
+            Token::Mul(m1, m2) => m1 * m2,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Solves part 1 for the parsed memory.
+pub fn solve(memory: &Memory) -> Result<String, AocError> {
+    Ok(sum_of_products(memory).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (_, numbers) = parse(_input).unwrap();
-
-    Ok(numbers
-        .iter()
-        .map(|(m1, m2)| m1 * m2)
-        .sum::<i32>()
-        .to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
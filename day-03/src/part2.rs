@@ -1,51 +1,28 @@
 use crate::custom_error::AocError;
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::anychar;
-use nom::multi::{many0, many_till};
-use nom::sequence::{delimited, separated_pair};
-use nom::{IResult, Parser};
+use crate::model::{parse, Memory, Token};
 
-fn parse(input: &str) -> IResult<&str, Vec<(Option<i32>, i32)>> {
-    many0(
-        many_till(
-            anychar,
-            alt((
-                delimited(
-                    tag("mul("),
-                    separated_pair(complete::i32, tag(","), complete::i32),
-                    tag(")"),
-                )
-                .map(|(v1, v2)| (Some(v1), v2)),
-                tag("do()").map(|_| (None, 1)),
-                tag("don't()").map(|_| (None, 0)),
-            )),
-        )
-        .map(|(_disc, values)| values),
-    )(input)
+/// Adds up the results of the `mul` instructions that are enabled, that is not preceded by a
+/// `don't()` without a `do()` in between.
+pub fn sum_of_enabled_products(memory: &Memory) -> i32 {
+    memory
+        .instructions()
+        .fold((0_i32, true), |(sum, active), token| match token {
+            Token::Do => (sum, true),
+            Token::Dont => (sum, false),
+            Token::Mul(v1, v2) if active => (sum + v1 * v2, active),
+            _ => (sum, active),
+        })
+        .0
+}
+
+/// Solves part 2 for the parsed memory.
+pub fn solve(memory: &Memory) -> Result<String, AocError> {
+    Ok(sum_of_enabled_products(memory).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (_, numbers) = parse(_input).unwrap();
-
-    let res = numbers
-        .iter()
-        .fold((0_i32, true), |(sum, active), item| match item {
-            (None, 1) => (sum, true),
-            (None, 0) => (sum, false),
-            (Some(v1), v2) => {
-                if active {
-                    (sum + v1 * v2, active)
-                } else {
-                    (sum, active)
-                }
-            }
-            _ => (sum, active),
-        });
-
-    Ok(res.0.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -0,0 +1,53 @@
+use crate::custom_error::AocError;
+use crate::model::{parse, Token};
+use aoc_common::stream::for_each_line;
+use std::io::BufRead;
+
+/// Solves both parts a line at a time. No instruction spans a line break, so each line is
+/// parsed on its own, with only whether `mul` is enabled carried over to the next one.
+///
+/// The sums are 64 bits wide, as a long enough input overflows the 32 bits of
+/// [`crate::part1::sum_of_products`].
+pub fn solve_both(reader: impl BufRead) -> miette::Result<(String, String), AocError> {
+    let mut sum = 0_i64;
+    let mut enabled_sum = 0_i64;
+    let mut enabled = true;
+    for_each_line(reader, |line| {
+        for token in parse(line)?.instructions() {
+            match *token {
+                Token::Mul(v1, v2) => {
+                    let product = i64::from(v1) * i64::from(v2);
+                    sum += product;
+                    if enabled {
+                        enabled_sum += product;
+                    }
+                }
+                Token::Do => enabled = true,
+                Token::Dont => enabled = false,
+                Token::Garbage(_) => {}
+            }
+        }
+        Ok::<_, AocError>(())
+    })?;
+
+    Ok((sum.to_string(), enabled_sum.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_both() -> miette::Result<()> {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+mul(1,1)do()mul(2,2)
+";
+        assert_eq!(crate::solve_both(input)?, solve_both(input.as_bytes())?);
+        // The `don't()` on the first line still holds on the second
+        assert_eq!(
+            ("1".to_string(), "0".to_string()),
+            solve_both("don't()\nmul(1,1)\n".as_bytes())?
+        );
+        Ok(())
+    }
+}
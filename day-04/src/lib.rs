@@ -1,4 +1,18 @@
+//! Day 4: Ceres Search.
+//!
+//! [`model::parse`] reads the word search, [`part1::count_xmas`] and [`part2::count_x_mas`]
+//! search it.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let word_search = model::parse(input)?;
+    Ok((part1::solve(&word_search)?, part2::solve(&word_search)?))
+}
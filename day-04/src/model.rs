@@ -0,0 +1,25 @@
+use crate::custom_error::AocError;
+use serde::Serialize;
+use std::fmt;
+
+/// The word search, one string per row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WordSearch(pub Vec<String>);
+
+pub fn parse(input: &str) -> Result<WordSearch, AocError> {
+    let rows = input.lines().map(str::to_string).collect::<Vec<_>>();
+
+    match rows.first() {
+        None => Err(AocError::malformed("Empty word search")),
+        Some(first) if rows.iter().any(|row| row.len() != first.len()) => {
+            Err(AocError::malformed("Rows differ in length"))
+        }
+        Some(_) => Ok(WordSearch(rows)),
+    }
+}
+
+impl fmt::Display for WordSearch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|row| writeln!(f, "{row}"))
+    }
+}
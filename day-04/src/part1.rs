@@ -1,4 +1,5 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, WordSearch};
 
 fn get_row(grid: &Vec<&str>, row: usize) -> String {
     grid.get(row)
@@ -46,9 +47,9 @@ fn get_ascending_diagonal(grid: &Vec<&str>, index: usize) -> String {
         .concat()
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let grid = _input.lines().collect::<Vec<_>>();
+/// Counts the occurrences of `XMAS` in every direction, including backwards and diagonally.
+pub fn count_xmas(word_search: &WordSearch) -> usize {
+    let grid = word_search.0.iter().map(String::as_str).collect::<Vec<_>>();
 
     let height = grid.len();
     let width = grid[0].len();
@@ -64,15 +65,6 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
                 .match_indices("XMAS")
                 .count();
 
-            println!("Row {}", row);
-            dbg!(str.match_indices("XMAS").collect::<Vec<_>>());
-            dbg!(str
-                .chars()
-                .rev()
-                .collect::<String>()
-                .match_indices("XMAS")
-                .collect::<Vec<_>>());
-
             count + rev_count
         })
         .sum::<usize>();
@@ -88,15 +80,6 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
                 .match_indices("XMAS")
                 .count();
 
-            println!("Column {}", col);
-            dbg!(str.match_indices("XMAS").collect::<Vec<_>>());
-            dbg!(str
-                .chars()
-                .rev()
-                .collect::<String>()
-                .match_indices("XMAS")
-                .collect::<Vec<_>>());
-
             count + rev_count
         })
         .sum::<usize>();
@@ -112,15 +95,6 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
                 .match_indices("XMAS")
                 .count();
 
-            println!("Desc Diag {}", diag);
-            dbg!(str.match_indices("XMAS").collect::<Vec<_>>());
-            dbg!(str
-                .chars()
-                .rev()
-                .collect::<String>()
-                .match_indices("XMAS")
-                .collect::<Vec<_>>());
-
             count + rev_count
         })
         .sum::<usize>();
@@ -136,20 +110,21 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
                 .match_indices("XMAS")
                 .count();
 
-            println!("Asc Diag {}", diag);
-            dbg!(str.match_indices("XMAS").collect::<Vec<_>>());
-            dbg!(str
-                .chars()
-                .rev()
-                .collect::<String>()
-                .match_indices("XMAS")
-                .collect::<Vec<_>>());
-
             count + rev_count
         })
         .sum::<usize>();
 
-    Ok((row_sum + col_sum + desc_diag_sum + asc_diag_sum).to_string())
+    row_sum + col_sum + desc_diag_sum + asc_diag_sum
+}
+
+/// Solves part 1 for the parsed word search.
+pub fn solve(word_search: &WordSearch) -> Result<String, AocError> {
+    Ok(count_xmas(word_search).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
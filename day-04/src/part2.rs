@@ -1,4 +1,5 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, WordSearch};
 
 fn get_block(grid: &Vec<&str>, x: usize, y: usize) -> Vec<Vec<String>> {
     (y..y + 3)
@@ -24,22 +25,24 @@ fn block_valid(block: Vec<Vec<String>>) -> bool {
                 && block.get(2).and_then(|row| row.get(0)) == Some(&"M".to_string())))
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let grid = _input.lines().collect::<Vec<_>>();
+/// Counts the crosses of two diagonal `MAS`, each of which may be written backwards.
+pub fn count_x_mas(word_search: &WordSearch) -> usize {
+    let grid = word_search.0.iter().map(String::as_str).collect::<Vec<_>>();
 
-    let count = (0..grid.len() - 2)
-        .flat_map(|y| (0..grid[0].len() - 2).map(move |x| (x, y)))
-        .filter_map(|(x, y)| {
-            println!("{:?}", get_block(&grid, x, y));
-            match block_valid(get_block(&grid, x, y)) {
-                true => Some(()),
-                false => None,
-            }
-        })
-        .count();
+    (0..grid.len().saturating_sub(2))
+        .flat_map(|y| (0..grid[0].len().saturating_sub(2)).map(move |x| (x, y)))
+        .filter(|&(x, y)| block_valid(get_block(&grid, x, y)))
+        .count()
+}
 
-    Ok(count.to_string())
+/// Solves part 2 for the parsed word search.
+pub fn solve(word_search: &WordSearch) -> Result<String, AocError> {
+    Ok(count_x_mas(word_search).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
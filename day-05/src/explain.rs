@@ -0,0 +1,139 @@
+use crate::model::PageOrdering;
+use serde::Serialize;
+use std::fmt;
+
+/// How one update was checked, and fixed if it had to be.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Update {
+    /// The update's line in the input, counting from 1
+    pub line: usize,
+    pub pages: Vec<u32>,
+    /// The first rule the update breaks, `None` if it is in the right order
+    pub broken_rule: Option<(u32, u32)>,
+    /// The pages put in the right order, only for updates that were not
+    pub reordered: Option<Vec<u32>>,
+    /// The middle page of the update in the right order
+    pub middle_page: u32,
+}
+
+/// Which updates were in the right order already, and how the others were reordered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    pub updates: Vec<Update>,
+}
+
+pub fn explain(page_ordering: &PageOrdering) -> Explanation {
+    // The updates follow the rules and a blank line
+    let first_line = page_ordering.order_rules.len() + 2;
+    let updates = (first_line..)
+        .zip(&page_ordering.updates)
+        .map(|(line, pages)| {
+            let broken_rule = page_ordering.first_broken_rule(pages);
+            let reordered = broken_rule.map(|_| {
+                let mut reordered = pages.clone();
+                page_ordering.sort(&mut reordered);
+                reordered
+            });
+            let in_order = reordered.as_ref().unwrap_or(pages);
+            Update {
+                line,
+                pages: pages.clone(),
+                broken_rule,
+                middle_page: in_order[(in_order.len() - 1) / 2],
+                reordered,
+            }
+        })
+        .collect();
+    Explanation { updates }
+}
+
+impl Explanation {
+    /// The answers, added up from the middle pages of the updates.
+    pub fn answers(&self) -> (String, String) {
+        let (reordered, ordered): (Vec<_>, Vec<_>) = self
+            .updates
+            .iter()
+            .partition(|update| update.reordered.is_some());
+        let sum =
+            |updates: Vec<&Update>| updates.iter().map(|update| update.middle_page).sum::<u32>();
+        (sum(ordered).to_string(), sum(reordered).to_string())
+    }
+}
+
+fn pages(pages: &[u32]) -> String {
+    pages
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for update in &self.updates {
+            write!(f, "{:>4}: {}", update.line, pages(&update.pages))?;
+            match (&update.reordered, update.broken_rule) {
+                (Some(reordered), Some((predecessor, successor))) => writeln!(
+                    f,
+                    " breaks {predecessor}|{successor}, reordered to {}, middle page {}",
+                    pages(reordered),
+                    update.middle_page
+                )?,
+                _ => writeln!(f, " in order, middle page {}", update.middle_page)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse;
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+        let explanation = explain(&parse(input)?);
+        assert_eq!(crate::solve_both(input)?, explanation.answers());
+        assert_eq!(
+            "  23: 75,47,61,53,29 in order, middle page 61
+  24: 97,61,53,29,13 in order, middle page 53
+  25: 75,29,13 in order, middle page 29
+  26: 75,97,47,61,53 breaks 97|75, reordered to 97,75,47,61,53, middle page 47
+  27: 61,13,29 breaks 29|13, reordered to 61,29,13, middle page 29
+  28: 97,13,75,29,47 breaks 29|13, reordered to 97,75,47,29,13, middle page 47
+",
+            explanation.to_string()
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,20 @@
+//! Day 5: Print Queue.
+//!
+//! [`model::parse`] reads the page ordering rules and updates, which
+//! [`model::PageOrdering::is_ordered`] checks and [`model::PageOrdering::sort`] fixes.
+//! [`explain::explain`] shows which updates were reordered and why.
+
 pub mod custom_error;
+pub mod model;
 
+pub mod explain;
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let ordering = model::parse(input)?;
+    Ok((part1::solve(&ordering)?, part2::solve(&ordering)?))
+}
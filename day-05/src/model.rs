@@ -0,0 +1,97 @@
+use crate::custom_error::AocError;
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{separated_pair, terminated};
+use nom::{Finish, IResult};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The page ordering rules and the updates to check against them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PageOrdering {
+    /// `(predecessor, successor)` pairs
+    pub order_rules: Vec<(u32, u32)>,
+    pub updates: Vec<Vec<u32>>,
+}
+
+impl PageOrdering {
+    /// Whether `update` satisfies every rule whose pages it both contains.
+    pub fn is_ordered(&self, update: &[u32]) -> bool {
+        self.first_broken_rule(update).is_none()
+    }
+
+    /// The first rule whose pages `update` contains in the wrong order.
+    pub fn first_broken_rule(&self, update: &[u32]) -> Option<(u32, u32)> {
+        self.order_rules
+            .iter()
+            .find(|(predecessor, successor)| {
+                match (
+                    update.iter().position(|item| item == predecessor),
+                    update.iter().position(|item| item == successor),
+                ) {
+                    (Some(p1), Some(p2)) => p1 > p2,
+                    _ => false,
+                }
+            })
+            .copied()
+    }
+
+    /// Sorts `update` so it satisfies the rules.
+    pub fn sort(&self, update: &mut [u32]) {
+        update.sort_by(|a, b| {
+            if self.order_rules.contains(&(*a, *b)) {
+                Ordering::Less
+            } else if self.order_rules.contains(&(*b, *a)) {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        });
+    }
+}
+
+fn page_ordering(input: &str) -> IResult<&str, PageOrdering> {
+    let (input, (order_rules, updates)) = terminated(
+        separated_pair(
+            separated_list1(
+                tag("\n"),
+                separated_pair(complete::u32, tag("|"), complete::u32),
+            ),
+            tag("\n\n"),
+            separated_list1(tag("\n"), separated_list1(tag(","), complete::u32)),
+        ),
+        opt(tag("\n")),
+    )(input)?;
+
+    Ok((
+        input,
+        PageOrdering {
+            order_rules,
+            updates,
+        },
+    ))
+}
+
+pub fn parse(input: &str) -> Result<PageOrdering, AocError> {
+    all_consuming(page_ordering)(input)
+        .finish()
+        .map(|(_, page_ordering)| page_ordering)
+        .map_err(|err| AocError::malformed(format!("Invalid input at: {:.20}", err.input)))
+}
+
+impl fmt::Display for PageOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (predecessor, successor) in &self.order_rules {
+            writeln!(f, "{predecessor}|{successor}")?;
+        }
+        writeln!(f)?;
+        for update in &self.updates {
+            let pages: Vec<String> = update.iter().map(u32::to_string).collect();
+            writeln!(f, "{}", pages.join(","))?;
+        }
+        Ok(())
+    }
+}
@@ -1,66 +1,24 @@
 use crate::custom_error::AocError;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::multi::separated_list0;
-use nom::sequence::separated_pair;
-use nom::IResult;
+use crate::model::{parse, PageOrdering};
 
-struct PageOrdering {
-    order_rules: Vec<(u32, u32)>,
-    updates: Vec<Vec<u32>>,
+/// Adds up the middle page numbers of the updates that are already in the right order.
+pub fn ordered_middle_page_sum(page_ordering: &PageOrdering) -> u32 {
+    page_ordering
+        .updates
+        .iter()
+        .filter(|update| page_ordering.is_ordered(update))
+        .map(|update| update[(update.len() - 1) / 2])
+        .sum()
 }
 
-fn parse(input: &str) -> IResult<&str, PageOrdering> {
-    separated_pair(
-        separated_list0(
-            tag("\n"),
-            separated_pair(complete::u32, tag("|"), complete::u32),
-        ),
-        tag("\n\n"),
-        separated_list0(tag("\n"), separated_list0(tag(","), complete::u32)),
-    )(input)
-    .map(|(input, (order_rules, updates))| {
-        (
-            input,
-            PageOrdering {
-                order_rules,
-                updates,
-            },
-        )
-    })
+/// Solves part 1 for the parsed ordering rules and updates.
+pub fn solve(ordering: &PageOrdering) -> Result<String, AocError> {
+    Ok(ordered_middle_page_sum(ordering).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (_, page_ordering) = parse(_input.trim()).unwrap();
-
-    let result = page_ordering
-        .updates
-        .iter()
-        .filter_map(|update| {
-            match page_ordering
-                .order_rules
-                .iter()
-                .map(|(predecessor, successor)| {
-                    match (
-                        update.iter().position(|item| item == predecessor),
-                        update.iter().position(|item| item == successor),
-                    ) {
-                        (Some(p1), Some(p2)) if p1 < p2 => true,
-                        (None, _) => true,
-                        (_, None) => true,
-                        _ => false,
-                    }
-                })
-                .all(|valid| valid)
-            {
-                true => update.get((update.len() - 1) / 2),
-                false => None,
-            }
-        })
-        .sum::<u32>();
-
-    Ok(result.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
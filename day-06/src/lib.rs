@@ -1,4 +1,19 @@
+//! Day 6: Guard Gallivant.
+//!
+//! [`model::parse`] reads the lab map, [`patrol::GuardMap::patrol`] follows the guard through
+//! it.
+
 pub mod custom_error;
+pub mod model;
+pub mod patrol;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let lab = model::parse(input)?;
+    Ok((part1::solve(&lab)?, part2::solve(&lab)?))
+}
@@ -0,0 +1,105 @@
+use crate::custom_error::AocError;
+use glam::IVec2;
+use serde::Serialize;
+use std::fmt;
+
+const DIRECTIONS: [(char, IVec2); 4] = [
+    ('^', IVec2::NEG_Y),
+    ('>', IVec2::X),
+    ('v', IVec2::Y),
+    ('<', IVec2::NEG_X),
+];
+
+/// The lab map with the guard's starting position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Lab {
+    pub width: i32,
+    pub height: i32,
+    /// Obstacle positions in reading order
+    pub obstacles: Vec<IVec2>,
+    pub guard_position: IVec2,
+    pub guard_direction: IVec2,
+}
+
+pub fn parse(input: &str) -> Result<Lab, AocError> {
+    let mut obstacles = Vec::new();
+    let mut guard = None;
+    let mut width = None;
+    let mut height = 0;
+
+    for (y, line) in input.lines().enumerate() {
+        if *width.get_or_insert(line.len()) != line.len() {
+            return Err(AocError::malformed("Rows differ in length"));
+        }
+        for (x, c) in line.chars().enumerate() {
+            let position = IVec2::new(x as i32, y as i32);
+            match c {
+                '.' => {}
+                '#' => obstacles.push(position),
+                _ => match DIRECTIONS.iter().find(|(guard_char, _)| *guard_char == c) {
+                    Some(&(_, direction)) if guard.is_none() => guard = Some((position, direction)),
+                    Some(_) => return Err(AocError::malformed("More than one guard")),
+                    None => return Err(AocError::malformed(format!("Invalid map tile: {c}"))),
+                },
+            }
+        }
+        height += 1;
+    }
+
+    let (guard_position, guard_direction) =
+        guard.ok_or_else(|| AocError::malformed("The map has no guard"))?;
+
+    Ok(Lab {
+        width: width.unwrap_or_default() as i32,
+        height,
+        obstacles,
+        guard_position,
+        guard_direction,
+    })
+}
+
+impl fmt::Display for Lab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = vec![vec!['.'; self.width as usize]; self.height as usize];
+        for obstacle in &self.obstacles {
+            grid[obstacle.y as usize][obstacle.x as usize] = '#';
+        }
+        if let Some(&(guard_char, _)) = DIRECTIONS
+            .iter()
+            .find(|(_, direction)| *direction == self.guard_direction)
+        {
+            grid[self.guard_position.y as usize][self.guard_position.x as usize] = guard_char;
+        }
+
+        for row in grid {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let input = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+        let lab = parse(input)?;
+        assert_eq!(IVec2::new(4, 6), lab.guard_position);
+        assert_eq!(8, lab.obstacles.len());
+        assert_eq!(input, lab.to_string());
+        assert_eq!(lab, parse(&lab.to_string())?);
+        Ok(())
+    }
+}
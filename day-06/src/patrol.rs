@@ -0,0 +1,211 @@
+use crate::model::Lab;
+use aoc_common::cycle;
+use aoc_common::simulation::Simulation;
+use glam::IVec2;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocationContent {
+    Empty,
+    Obstacle,
+}
+
+/// The lab as the guard sees it, with every position inside it.
+#[derive(Debug, Clone)]
+pub struct GuardMap {
+    pub map: HashMap<IVec2, LocationContent>,
+    pub guard_position: IVec2,
+    pub guard_direction: IVec2,
+}
+
+/// The states the guard went through, as `(position, direction)`, and how the patrol ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patrol {
+    pub visited: HashSet<(IVec2, IVec2)>,
+    /// Whether the guard got stuck in a loop instead of leaving the lab
+    pub is_loop: bool,
+}
+
+impl Patrol {
+    /// The distinct positions the guard visited.
+    pub fn positions(&self) -> HashSet<IVec2> {
+        self.visited.iter().map(|(pos, _)| *pos).collect()
+    }
+}
+
+impl From<&Lab> for GuardMap {
+    fn from(lab: &Lab) -> Self {
+        let obstacles = lab.obstacles.iter().collect::<HashSet<_>>();
+        let map = (0..lab.height)
+            .flat_map(|y| (0..lab.width).map(move |x| IVec2::new(x, y)))
+            .map(|position| match obstacles.contains(&position) {
+                true => (position, LocationContent::Obstacle),
+                false => (position, LocationContent::Empty),
+            })
+            .collect();
+
+        GuardMap {
+            map,
+            guard_position: lab.guard_position,
+            guard_direction: lab.guard_direction,
+        }
+    }
+}
+
+impl GuardMap {
+    /// Follows the guard, who turns right at every obstacle, until they leave the lab or come
+    /// back to a state they were in before.
+    pub fn patrol(&self) -> Patrol {
+        let trace = cycle::trace((self.guard_position, self.guard_direction), |state| {
+            self.next_state(state)
+        });
+
+        Patrol {
+            visited: trace.states.into_iter().collect(),
+            is_loop: trace.cycle.is_some(),
+        }
+    }
+
+    /// Whether the guard ends up walking in a loop. Unlike [`GuardMap::patrol`], this does not
+    /// remember where the guard has been.
+    pub fn traps_guard(&self) -> bool {
+        cycle::brent((self.guard_position, self.guard_direction), |state| {
+            self.next_state(state)
+        })
+        .is_some()
+    }
+
+    /// The guard's `(position, direction)` after one step from `state`, or `None` once they
+    /// left the lab.
+    fn next_state(&self, &state: &(IVec2, IVec2)) -> Option<(IVec2, IVec2)> {
+        let mut walk = GuardWalk::new(self);
+        walk.restore(state);
+        match walk.step()? {
+            GuardEvent::Left => None,
+            GuardEvent::Moved(_) | GuardEvent::Turned(_) => Some(walk.snapshot()),
+        }
+    }
+}
+
+/// What the guard did in one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardEvent {
+    Moved(IVec2),
+    /// Turned right in front of an obstacle, now facing the given direction
+    Turned(IVec2),
+    /// Walked out of the lab
+    Left,
+}
+
+/// The guard walking through the lab one step at a time. Its snapshots are the guard's
+/// `(position, direction)`.
+#[derive(Debug, Clone)]
+pub struct GuardWalk<'a> {
+    map: &'a GuardMap,
+    pub position: IVec2,
+    pub direction: IVec2,
+}
+
+impl<'a> GuardWalk<'a> {
+    pub fn new(map: &'a GuardMap) -> Self {
+        GuardWalk {
+            map,
+            position: map.guard_position,
+            direction: map.guard_direction,
+        }
+    }
+
+    /// Whether the guard is still inside the lab.
+    pub fn in_lab(&self) -> bool {
+        self.map.map.contains_key(&self.position)
+    }
+}
+
+impl Simulation for GuardWalk<'_> {
+    type Snapshot = (IVec2, IVec2);
+    type Event = GuardEvent;
+
+    fn step(&mut self) -> Option<GuardEvent> {
+        if !self.in_lab() {
+            return None;
+        }
+
+        let event = match self.map.map.get(&(self.position + self.direction)) {
+            Some(LocationContent::Obstacle) => {
+                self.direction = IVec2::new(-self.direction.y, self.direction.x);
+                GuardEvent::Turned(self.direction)
+            }
+            Some(LocationContent::Empty) => {
+                self.position += self.direction;
+                GuardEvent::Moved(self.position)
+            }
+            None => {
+                self.position += self.direction;
+                GuardEvent::Left
+            }
+        };
+        Some(event)
+    }
+
+    fn snapshot(&self) -> (IVec2, IVec2) {
+        (self.position, self.direction)
+    }
+
+    fn restore(&mut self, (position, direction): (IVec2, IVec2)) {
+        self.position = position;
+        self.direction = direction;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse;
+    use aoc_common::simulation::History;
+
+    #[test]
+    fn test_guard_walk() -> miette::Result<()> {
+        let lab = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...",
+        )?;
+        let map = GuardMap::from(&lab);
+        let mut history = History::new(GuardWalk::new(&map), 10);
+
+        // Up to the first obstacle, then a turn to the right
+        assert_eq!(
+            Some(5),
+            history.run_until(|walk| walk.position == IVec2::new(4, 1))
+        );
+        assert_eq!(Some(GuardEvent::Turned(IVec2::new(1, 0))), history.step());
+        assert_eq!(
+            Some((IVec2::new(4, 5), IVec2::new(0, -1))),
+            history.snapshot_at(1)
+        );
+
+        history.rewind(6);
+        assert_eq!(IVec2::new(4, 6), history.simulation().position);
+
+        let mut walk = history.into_simulation();
+        assert_eq!(None, walk.run_until(|_| false));
+        assert!(!walk.in_lab());
+        assert_eq!(None, walk.step());
+
+        assert!(!map.traps_guard());
+        let mut trapping = map.clone();
+        trapping
+            .map
+            .insert(IVec2::new(3, 6), LocationContent::Obstacle);
+        assert!(trapping.traps_guard());
+        assert!(trapping.patrol().is_loop);
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use crate::model::{Calibration, Operator};
+use serde::Serialize;
+use std::fmt;
+
+const PART1_OPERATORS: &[Operator] = &[Operator::Add, Operator::Multiply];
+const PART2_OPERATORS: &[Operator] = &[Operator::Add, Operator::Multiply, Operator::Concatenate];
+
+/// The operators found for one equation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Solved {
+    /// The equation's line in the input, counting from 1
+    pub line: usize,
+    pub result: u64,
+    pub operands: Vec<u64>,
+    /// The operators that give the result by adding and multiplying, if any do
+    pub part1: Option<Vec<Operator>>,
+    /// The operators that give the result when concatenating as well, if any do
+    pub part2: Option<Vec<Operator>>,
+}
+
+/// Which operators satisfied each equation, for either part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    pub equations: Vec<Solved>,
+}
+
+pub fn explain(calibration: &Calibration) -> Explanation {
+    Explanation {
+        equations: (1..)
+            .zip(&calibration.0)
+            .map(|(line, equation)| Solved {
+                line,
+                result: equation.result,
+                operands: equation.operands.clone(),
+                part1: equation.solution(PART1_OPERATORS),
+                part2: equation.solution(PART2_OPERATORS),
+            })
+            .collect(),
+    }
+}
+
+impl Explanation {
+    /// The answers, added up from the equations that have operators.
+    pub fn answers(&self) -> (String, String) {
+        let total = |solved: fn(&Solved) -> bool| {
+            self.equations
+                .iter()
+                .filter(|equation| solved(equation))
+                .map(|equation| equation.result)
+                .sum::<u64>()
+        };
+        (
+            total(|equation| equation.part1.is_some()).to_string(),
+            total(|equation| equation.part2.is_some()).to_string(),
+        )
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for equation in &self.equations {
+            write!(f, "{:>4}: {} = ", equation.line, equation.result)?;
+            // Part 1's operators work for part 2 as well, so they are shown if there are any
+            match equation.part1.as_ref().or(equation.part2.as_ref()) {
+                Some(operators) => {
+                    write!(f, "{}", equation.operands[0])?;
+                    for (operator, operand) in operators.iter().zip(&equation.operands[1..]) {
+                        write!(f, " {operator} {operand}")?;
+                    }
+                    match equation.part1 {
+                        Some(_) => writeln!(f)?,
+                        None => writeln!(f, "  part 2 only")?,
+                    }
+                }
+                None => {
+                    let operands = equation
+                        .operands
+                        .iter()
+                        .map(u64::to_string)
+                        .collect::<Vec<_>>();
+                    writeln!(f, "? {}  unsolvable", operands.join(" ? "))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse;
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+        let explanation = explain(&parse(input)?);
+        assert_eq!(crate::solve_both(input)?, explanation.answers());
+        assert_eq!(
+            "   1: 190 = 10 * 19
+   2: 3267 = 81 + 40 * 27
+   3: 83 = ? 17 ? 5  unsolvable
+   4: 156 = 15 || 6  part 2 only
+   5: 7290 = 6 * 8 || 6 * 15  part 2 only
+   6: 161011 = ? 16 ? 10 ? 13  unsolvable
+   7: 192 = 17 || 8 + 14  part 2 only
+   8: 21037 = ? 9 ? 7 ? 18 ? 13  unsolvable
+   9: 292 = 11 + 6 * 16 + 20
+",
+            explanation.to_string()
+        );
+        assert_eq!(
+            Some(vec![Operator::Add, Operator::Multiply]),
+            explanation.equations[1].part1
+        );
+        Ok(())
+    }
+}
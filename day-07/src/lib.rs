@@ -1,4 +1,23 @@
+//! Day 7: Bridge Repair.
+//!
+//! [`model::parse`] reads the calibration equations, [`model::Equation::is_solvable`] tries
+//! every combination of operators on one.
+//!
+//! [`stream::solve_both`] checks the equations as it reads them, for inputs larger than
+//! memory. [`explain::explain`] shows the operators that satisfied each equation.
+
 pub mod custom_error;
+pub mod model;
 
+pub mod explain;
 pub mod part1;
 pub mod part2;
+pub mod stream;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let calibration = model::parse(input)?;
+    Ok((part1::solve(&calibration)?, part2::solve(&calibration)?))
+}
@@ -0,0 +1,133 @@
+use crate::custom_error::AocError;
+use itertools::Itertools;
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::character::complete::newline;
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{separated_pair, terminated};
+use nom::{Finish, IResult, Parser};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Equation {
+    pub result: u64,
+    pub operands: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Operator {
+    #[serde(rename = "+")]
+    Add,
+    #[serde(rename = "*")]
+    Multiply,
+    /// Joins the digits of both operands
+    #[serde(rename = "||")]
+    Concatenate,
+}
+
+impl Operator {
+    pub fn apply(&self, left: u64, right: u64) -> u64 {
+        match self {
+            Operator::Add => left + right,
+            Operator::Multiply => left * right,
+            Operator::Concatenate => [left.to_string(), right.to_string()]
+                .join("")
+                .parse::<u64>()
+                .unwrap(),
+        }
+    }
+}
+
+impl Equation {
+    /// Whether some choice of `operators` between the operands, evaluated left to right, gives
+    /// the result.
+    pub fn is_solvable(&self, operators: &[Operator]) -> bool {
+        self.solution(operators).is_some()
+    }
+
+    /// The first choice of `operators` between the operands that gives the result, trying them
+    /// in the order given.
+    pub fn solution(&self, operators: &[Operator]) -> Option<Vec<Operator>> {
+        let (&first, rest) = self.operands.split_first()?;
+        // None of the operators makes the value smaller unless an operand is zero
+        let can_prune = !rest.contains(&0);
+
+        (0..rest.len())
+            .map(|_| operators.iter().copied())
+            .multi_cartesian_product()
+            .find(|operators| {
+                let mut result = first;
+                for (operator, &operand) in operators.iter().zip(rest) {
+                    result = operator.apply(result, operand);
+                    if can_prune && result > self.result {
+                        return false;
+                    }
+                }
+                result == self.result
+            })
+    }
+}
+
+/// The calibration equations, with their operators missing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Calibration(pub Vec<Equation>);
+
+fn equation(input: &str) -> IResult<&str, Equation> {
+    separated_pair(
+        complete::u64,
+        tag(": "),
+        separated_list1(tag(" "), complete::u64),
+    )
+    .map(|(result, operands)| Equation { result, operands })
+    .parse(input)
+}
+
+fn calibration(input: &str) -> IResult<&str, Calibration> {
+    terminated(separated_list1(newline, equation), opt(newline))
+        .map(Calibration)
+        .parse(input)
+}
+
+pub fn parse(input: &str) -> Result<Calibration, AocError> {
+    all_consuming(calibration)(input)
+        .finish()
+        .map(|(_, calibration)| calibration)
+        .map_err(|err| AocError::malformed(format!("Invalid input at: {:.20}", err.input)))
+}
+
+/// A single equation, on a line of its own.
+pub fn parse_equation(line: &str) -> Result<Equation, AocError> {
+    all_consuming(equation)(line)
+        .finish()
+        .map(|(_, equation)| equation)
+        .map_err(|err| AocError::malformed(format!("Invalid input at: {:.20}", err.input)))
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Multiply => write!(f, "*"),
+            Operator::Concatenate => write!(f, "||"),
+        }
+    }
+}
+
+impl fmt::Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.result)?;
+        self.operands
+            .iter()
+            .try_for_each(|operand| write!(f, " {operand}"))
+    }
+}
+
+impl fmt::Display for Calibration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|equation| writeln!(f, "{equation}"))
+    }
+}
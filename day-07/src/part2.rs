@@ -1,90 +1,26 @@
 use crate::custom_error::AocError;
-use itertools::Itertools;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::newline;
-use nom::multi::separated_list0;
-use nom::sequence::separated_pair;
-use nom::IResult;
+use crate::model::{parse, Calibration, Operator};
 
-#[derive(Debug)]
-struct Equation {
-    result: u64,
-    operands: Vec<u64>,
-}
-
-#[derive(Debug, Clone)]
-enum Operator {
-    Add,
-    Multiply,
-    Concatenate,
-}
-
-impl Equation {
-    fn is_valid(&self) -> bool {
-        (1..=self.operands.len() - 1)
-            .map(|_| vec![Operator::Add, Operator::Multiply, Operator::Concatenate].into_iter())
-            .multi_cartesian_product()
-            .map(|operators| {
-                let mut operand_iter = self.operands.iter();
-                let mut result = operand_iter.next().unwrap().clone();
-                for operand in operators {
-                    match operand {
-                        Operator::Add => {
-                            result += operand_iter.next().unwrap();
-                        }
-                        Operator::Multiply => {
-                            result *= operand_iter.next().unwrap();
-                        }
-                        Operator::Concatenate => {
-                            result = [result.to_string(), operand_iter.next().unwrap().to_string()]
-                                .join("")
-                                .parse::<u64>()
-                                .unwrap();
-                        }
-                    }
-                    if result > self.result {
-                        break;
-                    }
-                }
-                result == self.result
-            })
-            .any(|x| x)
-    }
+/// Adds up the results of the equations that can be solved by adding, multiplying and concatenating.
+pub fn total_calibration_result(calibration: &Calibration) -> u64 {
+    calibration
+        .0
+        .iter()
+        .filter(|equation| {
+            equation.is_solvable(&[Operator::Add, Operator::Multiply, Operator::Concatenate])
+        })
+        .map(|equation| equation.result)
+        .sum()
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
-    separated_list0(
-        newline,
-        separated_pair(
-            complete::u64,
-            tag(": "),
-            separated_list0(tag(" "), complete::u64),
-        ),
-    )(input)
+/// Solves part 2 for the parsed calibration equations.
+pub fn solve(calibration: &Calibration) -> Result<String, AocError> {
+    Ok(total_calibration_result(calibration).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (_, parsed) = parse(_input).unwrap();
-
-    let equations = parsed
-        .into_iter()
-        .map(|eq| Equation {
-            result: eq.0,
-            operands: eq.1,
-        })
-        .collect_vec();
-
-    let result = equations
-        .iter()
-        .filter_map(|eq| match eq.is_valid() {
-            true => Some(eq.result),
-            false => None,
-        })
-        .sum::<u64>();
-
-    Ok(result.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
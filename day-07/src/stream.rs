@@ -0,0 +1,45 @@
+use crate::custom_error::AocError;
+use crate::model::{parse_equation, Operator};
+use aoc_common::stream::for_each_line;
+use std::io::BufRead;
+
+/// Solves both parts an equation at a time, so only the current line is held in memory.
+pub fn solve_both(reader: impl BufRead) -> miette::Result<(String, String), AocError> {
+    let mut total = 0_u64;
+    let mut total_with_concatenation = 0_u64;
+    for_each_line(reader, |line| {
+        let equation = parse_equation(line)?;
+        if equation.is_solvable(&[Operator::Add, Operator::Multiply]) {
+            total += equation.result;
+            total_with_concatenation += equation.result;
+        } else if equation.is_solvable(&[Operator::Add, Operator::Multiply, Operator::Concatenate])
+        {
+            total_with_concatenation += equation.result;
+        }
+        Ok::<_, AocError>(())
+    })?;
+
+    Ok((total.to_string(), total_with_concatenation.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_both() -> miette::Result<()> {
+        let input = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+        assert_eq!(crate::solve_both(input)?, solve_both(input.as_bytes())?);
+        assert!(solve_both("190: 10 19\n190 10 19\n".as_bytes()).is_err());
+        Ok(())
+    }
+}
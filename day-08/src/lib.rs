@@ -1,4 +1,18 @@
+//! Day 8: Resonant Collinearity.
+//!
+//! [`model::parse`] reads the antenna map, [`part1::antinodes`] and
+//! [`part2::resonant_antinodes`] locate the antinodes on it.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let map = model::parse(input)?;
+    Ok((part1::solve(&map)?, part2::solve(&map)?))
+}
@@ -0,0 +1,108 @@
+use crate::custom_error::AocError;
+use glam::IVec2;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Antenna {
+    pub frequency: char,
+    pub position: IVec2,
+}
+
+/// The city map with its antennas in reading order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AntennaMap {
+    pub width: usize,
+    pub height: usize,
+    pub antennas: Vec<Antenna>,
+}
+
+impl AntennaMap {
+    pub fn in_bounds(&self, pos: &IVec2) -> bool {
+        pos.x >= 0 && pos.x < self.width as i32 && pos.y >= 0 && pos.y < self.height as i32
+    }
+
+    /// The antenna positions grouped by frequency.
+    pub fn antennas_by_frequency(&self) -> HashMap<char, Vec<IVec2>> {
+        self.antennas
+            .iter()
+            .fold(HashMap::new(), |mut map, antenna| {
+                map.entry(antenna.frequency)
+                    .or_insert_with(Vec::new)
+                    .push(antenna.position);
+                map
+            })
+    }
+}
+
+pub fn parse(input: &str) -> Result<AntennaMap, AocError> {
+    let mut antennas = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    for (y, line) in input.lines().enumerate() {
+        if *width.get_or_insert(line.len()) != line.len() {
+            return Err(AocError::malformed("Rows differ in length"));
+        }
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                '.' => {}
+                'a'..='z' | 'A'..='Z' | '0'..='9' => antennas.push(Antenna {
+                    frequency: c,
+                    position: IVec2::new(x as i32, y as i32),
+                }),
+                _ => return Err(AocError::malformed(format!("Invalid map tile: {c}"))),
+            }
+        }
+        height += 1;
+    }
+
+    Ok(AntennaMap {
+        width: width.ok_or_else(|| AocError::malformed("Empty map"))?,
+        height,
+        antennas,
+    })
+}
+
+impl fmt::Display for AntennaMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = vec![vec!['.'; self.width]; self.height];
+        for antenna in &self.antennas {
+            grid[antenna.position.y as usize][antenna.position.x as usize] = antenna.frequency;
+        }
+
+        for row in grid {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let input = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+        let map = parse(input)?;
+        assert_eq!((12, 12), (map.width, map.height));
+        assert_eq!(7, map.antennas.len());
+        assert_eq!(input, map.to_string());
+        assert_eq!(map, parse(&map.to_string())?);
+        Ok(())
+    }
+}
@@ -1,4 +1,18 @@
+//! Day 9: Disk Fragmenter.
+//!
+//! [`model::parse`] reads the disk map, [`part1::compacted_checksum`] and
+//! [`part2::defragmented_checksum`] compact it and compute the filesystem checksum.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let disk = model::parse(input)?;
+    Ok((part1::solve(&disk)?, part2::solve(&disk)?))
+}
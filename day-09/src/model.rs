@@ -0,0 +1,58 @@
+use crate::custom_error::AocError;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BlockType {
+    /// File with its ID
+    File(u64),
+    Space,
+}
+
+/// The disk map, alternating between files and free space.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Disk {
+    /// Blocks with their length
+    pub blocks: Vec<(BlockType, u8)>,
+}
+
+pub fn parse(input: &str) -> Result<Disk, AocError> {
+    let blocks = input
+        .trim_end()
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            let size = c
+                .to_digit(10)
+                .ok_or_else(|| AocError::malformed(format!("Invalid block size: {c}")))?;
+            let block_type = match index % 2 {
+                0 => BlockType::File(index as u64 / 2),
+                _ => BlockType::Space,
+            };
+            Ok((block_type, size as u8))
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+
+    Ok(Disk { blocks })
+}
+
+impl fmt::Display for Disk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.blocks
+            .iter()
+            .try_for_each(|(_, size)| write!(f, "{size}"))?;
+        writeln!(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() -> miette::Result<()> {
+        let disk = parse("12345")?;
+        insta::assert_snapshot!(disk);
+        Ok(())
+    }
+}
@@ -1,36 +1,11 @@
 use crate::custom_error::AocError;
-use crate::part1::BlockType::{File, Space};
-use std::num::ParseIntError;
+use crate::model::BlockType::{File, Space};
+use crate::model::{parse, Disk};
 
-#[derive(Debug, Clone, Copy)]
-enum BlockType {
-    File(u64),
-    Space,
-}
-
-#[derive(Debug)]
-struct Disk {
-    blocks: Vec<(BlockType, u8)>,
-}
-
-fn parse(input: &str) -> Result<Disk, ParseIntError> {
-    let mut chars = input.trim().chars();
-
-    let mut blocks = vec![(File(0), chars.next().unwrap().to_string().parse::<u8>()?)];
-    let mut id = 0;
-
-    while let Some(c) = chars.next() {
-        id += 1;
-        blocks.push((Space, c.to_string().parse::<u8>()?));
-        blocks.push((File(id), chars.next().unwrap().to_string().parse::<u8>()?));
-    }
-
-    Ok(Disk { blocks })
-}
-
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let mut disk = parse(_input).unwrap();
+/// The filesystem checksum after moving file blocks one at a time from the end of the disk to
+/// the leftmost free space.
+pub fn compacted_checksum(disk: &Disk) -> u64 {
+    let mut disk = disk.clone();
     let mut block_index = 0;
     let mut disk_position: u64 = 0;
 
@@ -71,7 +46,17 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
         }
     }
 
-    Ok(sum.to_string())
+    sum
+}
+
+/// Solves part 1 for the parsed disk map.
+pub fn solve(disk: &Disk) -> Result<String, AocError> {
+    Ok(compacted_checksum(disk).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -1,94 +1,52 @@
 use crate::custom_error::AocError;
-use crate::part2::BlockType::{File, Space};
-use itertools::Itertools;
-use std::fmt::{Display, Formatter};
-use std::num::ParseIntError;
+use crate::model::BlockType::{self, File, Space};
+use crate::model::{parse, Disk};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum BlockType {
-    File(u64),
-    Space,
-}
-
-struct Disk {
-    blocks: Vec<(BlockType, u64)>,
-}
-
-impl Display for Disk {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let string = self
-            .blocks
-            .iter()
-            .map(|(block_type, size)| match block_type {
-                File(id) => id.to_string().repeat(*size as usize),
-                Space => ".".repeat(*size as usize),
-            })
-            .join("");
-
-        write!(f, "{}", string)
-    }
-}
-
-fn parse(input: &str) -> Result<Disk, ParseIntError> {
-    let mut chars = input.trim().chars();
-
-    let mut blocks = vec![(File(0), chars.next().unwrap().to_string().parse::<u64>()?)];
-    let mut id = 0;
-
-    while let Some(c) = chars.next() {
-        id += 1;
-        blocks.push((Space, c.to_string().parse::<u64>()?));
-        blocks.push((File(id), chars.next().unwrap().to_string().parse::<u64>()?));
-    }
-
-    Ok(Disk { blocks })
-}
-
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let mut disk = parse(_input).unwrap();
-
-    let max_id = disk
+/// The filesystem checksum after moving whole files, highest ID first, to the leftmost free
+/// space that fits them.
+pub fn defragmented_checksum(disk: &Disk) -> u64 {
+    let mut blocks: Vec<(BlockType, u64)> = disk
         .blocks
+        .iter()
+        .map(|(block_type, size)| (*block_type, u64::from(*size)))
+        .collect();
+
+    let max_id = blocks
         .iter()
         .filter_map(|(t, _)| match t {
             File(id) => Some(*id),
             _ => None,
         })
         .max()
-        .unwrap();
+        .unwrap_or_default();
 
     for id in (0..=max_id).rev() {
-        //println!("{}", &disk);
-        let file_index = disk
-            .blocks
-            .iter()
-            .position(|(t, _)| *t == File(id))
-            .unwrap();
-        let file_size = disk.blocks[file_index].1;
+        let Some(file_index) = blocks.iter().position(|(t, _)| *t == File(id)) else {
+            continue;
+        };
+        let file_size = blocks[file_index].1;
 
-        if let Some(space_index) = disk
-            .blocks
+        if let Some(space_index) = blocks
             .iter()
             .position(|(t, s)| (*t == Space) && (*s >= file_size))
         {
             if space_index > file_index {
                 continue;
             }
-            let space_size = disk.blocks[space_index].1;
-            disk.blocks.insert(space_index, disk.blocks[file_index]);
-            disk.blocks[space_index + 1].1 = space_size - file_size;
+            let space_size = blocks[space_index].1;
+            blocks.insert(space_index, blocks[file_index]);
+            blocks[space_index + 1].1 = space_size - file_size;
 
-            disk.blocks[file_index + 1].0 = Space;
+            blocks[file_index + 1].0 = Space;
 
-            if let Some((Space, size_succ)) = disk.blocks.get(file_index + 2) {
-                disk.blocks[file_index + 1].1 += *size_succ;
-                disk.blocks.remove(file_index + 2);
+            if let Some((Space, size_succ)) = blocks.get(file_index + 2) {
+                blocks[file_index + 1].1 += *size_succ;
+                blocks.remove(file_index + 2);
             }
 
-            if let Some((Space, _)) = disk.blocks.get(file_index) {
-                disk.blocks[file_index].1 += disk.blocks[file_index + 1].1;
-                disk.blocks.remove(file_index + 1);
+            if let Some((Space, _)) = blocks.get(file_index) {
+                blocks[file_index].1 += blocks[file_index + 1].1;
+                blocks.remove(file_index + 1);
             }
         }
     }
@@ -96,17 +54,24 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
     let mut sum = 0;
     let mut disk_position = 0;
 
-    for (block_type, size) in disk.blocks.iter() {
-        match block_type {
-            File(id) => {
-                sum += (disk_position..disk_position + *size).sum::<u64>() * *id;
-            }
-            _ => {}
+    for (block_type, size) in blocks.iter() {
+        if let File(id) = block_type {
+            sum += (disk_position..disk_position + *size).sum::<u64>() * *id;
         }
         disk_position += *size;
     }
 
-    Ok(sum.to_string())
+    sum
+}
+
+/// Solves part 2 for the parsed disk map.
+pub fn solve(disk: &Disk) -> Result<String, AocError> {
+    Ok(defragmented_checksum(disk).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -1,4 +1,28 @@
+//! Day 10: Hoof It.
+//!
+//! [`model::parse`] reads the topographic map and [`model::TopographicMap::to_graph`] turns it
+//! into a graph of hiking trail steps, on which [`part1::trailhead_score_sum`] and
+//! [`part2::trailhead_rating_sum`] score the trailheads.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use aoc_common::cancel::CancelToken;
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    solve_both_cancellable(input, &CancelToken::new())
+}
+
+/// Like [`solve_both`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+pub fn solve_both_cancellable(
+    input: &str,
+    cancel: &CancelToken,
+) -> miette::Result<(String, String), AocError> {
+    let map = model::parse(input)?;
+    Ok((part1::solve(&map, cancel)?, part2::solve(&map, cancel)?))
+}
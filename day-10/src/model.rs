@@ -0,0 +1,89 @@
+use crate::custom_error::AocError;
+use glam::IVec2;
+use petgraph::{Directed, Graph};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The topographic map, one height from 0 to 9 per position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopographicMap(pub Vec<Vec<u8>>);
+
+const ABOVE: IVec2 = IVec2::new(0, -1);
+const BELOW: IVec2 = IVec2::new(0, 1);
+const LEFT: IVec2 = IVec2::new(-1, 0);
+const RIGHT: IVec2 = IVec2::new(1, 0);
+
+const SURROUNDINGS: [IVec2; 4] = [ABOVE, BELOW, LEFT, RIGHT];
+
+impl TopographicMap {
+    /// A graph of the positions weighted by height, with an edge wherever a hiking trail can
+    /// climb from one position to the next.
+    pub fn to_graph(&self) -> Graph<u8, (), Directed> {
+        let map = self
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(x, &height)| (IVec2::new(x as i32, y as i32), height))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut graph = Graph::<u8, (), Directed>::new();
+        let mut index_mapping = HashMap::new();
+
+        for (coordinates, height) in &map {
+            let nodeindex = graph.add_node(*height);
+            index_mapping.insert(coordinates, nodeindex);
+        }
+
+        for (coordinates, height) in &map {
+            SURROUNDINGS
+                .iter()
+                .map(|&direction| coordinates + direction)
+                .filter(|neighbor| map.get(neighbor) == Some(&(height + 1)))
+                .for_each(|neighbor| {
+                    if let (Some(index), Some(neighbor)) =
+                        (index_mapping.get(coordinates), index_mapping.get(&neighbor))
+                    {
+                        graph.add_edge(*index, *neighbor, ());
+                    }
+                })
+        }
+        graph
+    }
+}
+
+pub fn parse(input: &str) -> Result<TopographicMap, AocError> {
+    let rows = input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|height| height as u8)
+                        .ok_or_else(|| AocError::malformed(format!("Invalid height: {c}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match rows.first() {
+        Some(first) if rows.iter().any(|row| row.len() != first.len()) => {
+            Err(AocError::malformed("Rows differ in length"))
+        }
+        _ => Ok(TopographicMap(rows)),
+    }
+}
+
+impl fmt::Display for TopographicMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.0 {
+            row.iter().try_for_each(|height| write!(f, "{height}"))?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -1,60 +1,14 @@
 use crate::custom_error::AocError;
-use glam::IVec2;
+use crate::model::{parse, TopographicMap};
+use aoc_common::cancel::CancelToken;
 use itertools::Itertools;
 use petgraph::algo::has_path_connecting;
 use petgraph::graph::NodeIndex;
-use petgraph::Directed;
-use std::collections::HashMap;
 
-const ABOVE: IVec2 = IVec2::new(0, -1);
-const BELOW: IVec2 = IVec2::new(0, 1);
-const LEFT: IVec2 = IVec2::new(-1, 0);
-const RIGHT: IVec2 = IVec2::new(1, 0);
-
-const SURROUNDINGS: [IVec2; 4] = [ABOVE, BELOW, LEFT, RIGHT];
-
-fn parse(input: &str) -> petgraph::Graph<u8, (), Directed> {
-    let map = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map(move |(x, c)| {
-                (
-                    IVec2::new(x as i32, y as i32),
-                    c.to_digit(10).unwrap() as u8,
-                )
-            })
-        })
-        .collect::<HashMap<_, _>>();
-
-    let mut graph = petgraph::Graph::<u8, (), Directed>::new();
-    let mut index_mapping = HashMap::new();
-
-    for (coordinates, height) in &map {
-        let nodeindex = graph.add_node(*height);
-        index_mapping.insert(coordinates, nodeindex);
-    }
-
-    for (coordinates, height) in &map {
-        SURROUNDINGS
-            .iter()
-            .map(|&direction| coordinates + direction)
-            .filter(|neighbor| map.get(neighbor) == Some(&(height + 1)))
-            .for_each(|neighbor| {
-                match (index_mapping.get(coordinates), index_mapping.get(&neighbor)) {
-                    (Some(index), Some(neighbor)) => {
-                        graph.add_edge(index.clone(), neighbor.clone(), ());
-                    }
-                    _ => {}
-                }
-            })
-    }
-    graph
-}
-
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let graph = parse(_input);
+/// The sum of the trailhead scores, each the number of height 9 positions reachable from a
+/// trailhead. Gives up with [`AocError::Timeout`] once `cancel` expires.
+pub fn trailhead_score_sum(map: &TopographicMap, cancel: &CancelToken) -> Result<usize, AocError> {
+    let graph = map.to_graph();
 
     let trail_heads = graph
         .raw_nodes()
@@ -76,20 +30,36 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
         })
         .collect::<Vec<_>>();
 
-    let result = trail_heads
+    // Every pair is a search of its own, so the budget is checked per pair
+    trail_heads
         .iter()
         .cartesian_product(targets.iter())
-        .filter(|(&trail_head, &target)| {
-            has_path_connecting(
+        .try_fold(0, |count, (&trail_head, &target)| {
+            cancel.check()?;
+            let connected = has_path_connecting(
                 &graph,
                 NodeIndex::new(trail_head),
                 NodeIndex::new(target),
                 None,
-            )
+            );
+            Ok(count + usize::from(connected))
         })
-        .count();
+}
+
+/// Solves part 1 for the parsed topographic map.
+pub fn solve(map: &TopographicMap, cancel: &CancelToken) -> Result<String, AocError> {
+    Ok(trailhead_score_sum(map, cancel)?.to_string())
+}
 
-    Ok(result.to_string())
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    process_cancellable(_input, &CancelToken::new())
+}
+
+/// Like [`process`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+#[tracing::instrument(skip(cancel))]
+pub fn process_cancellable(_input: &str, cancel: &CancelToken) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?, cancel)
 }
 
 #[cfg(test)]
@@ -109,4 +79,14 @@ mod tests {
         assert_eq!("36", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(matches!(
+            process_cancellable("0123456789", &cancel),
+            Err(AocError::Timeout(_))
+        ));
+    }
 }
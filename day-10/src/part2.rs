@@ -1,60 +1,30 @@
 use crate::custom_error::AocError;
-use glam::IVec2;
+use crate::model::{parse, TopographicMap};
+use aoc_common::cancel::CancelToken;
 use itertools::Itertools;
 use petgraph::algo::all_simple_paths;
 use petgraph::graph::NodeIndex;
-use petgraph::Directed;
-use std::collections::HashMap;
 
-const ABOVE: IVec2 = IVec2::new(0, -1);
-const BELOW: IVec2 = IVec2::new(0, 1);
-const LEFT: IVec2 = IVec2::new(-1, 0);
-const RIGHT: IVec2 = IVec2::new(1, 0);
-
-const SURROUNDINGS: [IVec2; 4] = [ABOVE, BELOW, LEFT, RIGHT];
-
-fn parse(input: &str) -> petgraph::Graph<u8, (), Directed> {
-    let map = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map(move |(x, c)| {
-                (
-                    IVec2::new(x as i32, y as i32),
-                    c.to_digit(10).unwrap() as u8,
-                )
-            })
-        })
-        .collect::<HashMap<_, _>>();
-
-    let mut graph = petgraph::Graph::<u8, (), Directed>::new();
-    let mut index_mapping = HashMap::new();
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    process_cancellable(_input, &CancelToken::new())
+}
 
-    for (coordinates, height) in &map {
-        let nodeindex = graph.add_node(*height);
-        index_mapping.insert(coordinates, nodeindex);
-    }
+/// Solves part 2 for the parsed topographic map.
+pub fn solve(map: &TopographicMap, cancel: &CancelToken) -> Result<String, AocError> {
+    Ok(trailhead_rating_sum(map, cancel)?.to_string())
+}
 
-    for (coordinates, height) in &map {
-        SURROUNDINGS
-            .iter()
-            .map(|&direction| coordinates + direction)
-            .filter(|neighbor| map.get(neighbor) == Some(&(height + 1)))
-            .for_each(|neighbor| {
-                match (index_mapping.get(coordinates), index_mapping.get(&neighbor)) {
-                    (Some(index), Some(neighbor)) => {
-                        graph.add_edge(index.clone(), neighbor.clone(), ());
-                    }
-                    _ => {}
-                }
-            })
-    }
-    graph
+/// Like [`process`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+#[tracing::instrument(skip(cancel))]
+pub fn process_cancellable(_input: &str, cancel: &CancelToken) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?, cancel)
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let graph = parse(_input);
+/// The sum of the trailhead ratings, each the number of distinct hiking trails starting at a
+/// trailhead. Gives up with [`AocError::Timeout`] once `cancel` expires.
+pub fn trailhead_rating_sum(map: &TopographicMap, cancel: &CancelToken) -> Result<u64, AocError> {
+    let graph = map.to_graph();
 
     let trail_heads = graph
         .raw_nodes()
@@ -76,7 +46,8 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
         })
         .collect::<Vec<_>>();
 
-    let result = trail_heads
+    // The number of simple paths can grow exponentially, so the budget is checked per path
+    trail_heads
         .iter()
         .cartesian_product(targets.iter())
         .map(|(&trail_head, &target)| {
@@ -87,11 +58,10 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
                 0,
                 None,
             )
-            .count() as u64
+            .try_fold(0u64, |count, _| cancel.check().map(|_| count + 1))
         })
-        .sum::<u64>();
-
-    Ok(result.to_string())
+        .sum::<Result<u64, _>>()
+        .map_err(AocError::from)
 }
 
 #[cfg(test)]
@@ -111,4 +81,14 @@ mod tests {
         assert_eq!("81", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(matches!(
+            process_cancellable("0123456789", &cancel),
+            Err(AocError::Timeout(_))
+        ));
+    }
 }
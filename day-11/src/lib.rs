@@ -1,4 +1,22 @@
+//! Day 11: Plutonian Pebbles.
+//!
+//! [`model::parse`] reads the stones and [`model::blink`] applies the rules to a single stone.
+//! [`part1::stones_after_blinking`] simulates the stones, [`part2::stones_after_blinking`]
+//! only counts them.
+//!
+//! [`stream::solve_both`] counts the stones as they are read, without holding the line.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+pub mod stream;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let stones = model::parse(input)?;
+    Ok((part1::solve(&stones)?, part2::solve(&stones)?))
+}
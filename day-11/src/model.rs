@@ -0,0 +1,41 @@
+use crate::custom_error::AocError;
+use serde::Serialize;
+use std::fmt;
+
+/// The engravings on the stones, in line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Stones(pub Vec<u64>);
+
+/// The stones a single stone turns into when blinking once.
+pub fn blink(stone: u64) -> Vec<u64> {
+    if stone == 0 {
+        vec![1]
+    } else {
+        let digits = stone.to_string().len() as u32;
+        if digits.is_multiple_of(2) {
+            let power = 10_u64.pow(digits / 2);
+            vec![stone / power, stone % power]
+        } else {
+            vec![stone * 2024]
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Stones, AocError> {
+    input
+        .split_whitespace()
+        .map(|stone| {
+            stone
+                .parse()
+                .map_err(|_| AocError::malformed(format!("Invalid stone: {stone}")))
+        })
+        .collect::<Result<_, _>>()
+        .map(Stones)
+}
+
+impl fmt::Display for Stones {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stones: Vec<String> = self.0.iter().map(u64::to_string).collect();
+        writeln!(f, "{}", stones.join(" "))
+    }
+}
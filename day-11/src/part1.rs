@@ -1,34 +1,25 @@
 use crate::custom_error::AocError;
+use crate::model::{blink, parse, Stones};
 
-fn process_number(n: &u64) -> Vec<u64> {
-    if *n == 0 {
-        vec![1]
-    } else {
-        let digits = n.to_string().len() as u32;
-        if digits % 2 == 0 {
-            let power = 10_u64.pow(digits / 2);
-            vec![n / power, n % power]
-        } else {
-            vec![n * 2024]
-        }
+/// The number of stones after blinking `blinks` times, simulating every single stone.
+pub fn stones_after_blinking(stones: &Stones, blinks: usize) -> usize {
+    let mut numbers = stones.0.clone();
+
+    for _ in 0..blinks {
+        numbers = numbers.into_iter().flat_map(blink).collect::<Vec<u64>>();
     }
+
+    numbers.len()
+}
+
+/// Solves part 1 for the parsed stones.
+pub fn solve(stones: &Stones) -> Result<String, AocError> {
+    Ok(stones_after_blinking(stones, 25).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let mut numbers = _input
-        .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u64>>();
-
-    for _ in 0..25 {
-        numbers = numbers
-            .iter()
-            .flat_map(process_number)
-            .collect::<Vec<u64>>();
-    }
-
-    Ok(numbers.len().to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -1,36 +1,47 @@
 use crate::custom_error::AocError;
-use cached::proc_macro::cached;
+use crate::model::{blink, parse, Stones};
+use aoc_common::memo::Memo;
 
-#[cached]
-fn process_number(number: u64, times: u8) -> u64 {
+/// A stone about to be blinked at `times` times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Blinks {
+    pub stone: u64,
+    pub times: u8,
+}
+
+/// The number of stones a single stone turns into after blinking `times` times, remembering
+/// the counts along the way in `memo`.
+pub fn stone_count(stone: u64, times: u8, memo: &mut Memo<Blinks, u64>) -> u64 {
     if times == 0 {
-        1
-    } else {
-        if number == 0 {
-            process_number(1, times - 1)
-        } else {
-            let digits = number.to_string().len() as u32;
-            if digits % 2 == 0 {
-                let power = 10_u64.pow(digits / 2);
-                process_number(number / power, times - 1)
-                    + process_number(number % power, times - 1)
-            } else {
-                process_number(number * 2024, times - 1)
-            }
-        }
+        return 1;
     }
+    memo.get_or_compute(Blinks { stone, times }, |memo| {
+        blink(stone)
+            .into_iter()
+            .map(|stone| stone_count(stone, times - 1, memo))
+            .sum()
+    })
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let numbers = _input
-        .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u64>>();
+/// The number of stones after blinking `blinks` times, counting each stone's descendants
+/// without simulating them.
+pub fn stones_after_blinking(stones: &Stones, blinks: u8) -> u64 {
+    let mut memo = Memo::new();
+    stones
+        .0
+        .iter()
+        .map(|stone| stone_count(*stone, blinks, &mut memo))
+        .sum()
+}
 
-    let result = numbers.iter().map(|n| process_number(*n, 75)).sum::<u64>();
+/// Solves part 2 for the parsed stones.
+pub fn solve(stones: &Stones) -> Result<String, AocError> {
+    Ok(stones_after_blinking(stones, 75).to_string())
+}
 
-    Ok(result.to_string())
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -43,4 +54,17 @@ mod tests {
         assert_eq!("55312", crate::part1::process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_stone_count() -> miette::Result<()> {
+        assert_eq!(55312, stones_after_blinking(&parse("125 17")?, 25));
+
+        let mut memo = Memo::new();
+        assert_eq!(7, stone_count(125, 6, &mut memo));
+        let remembered = memo.len();
+        assert_eq!(15, stone_count(17, 6, &mut memo));
+        assert!(memo.len() > remembered);
+        assert_eq!(7, stone_count(125, 6, &mut memo));
+        Ok(())
+    }
 }
@@ -0,0 +1,46 @@
+use crate::custom_error::AocError;
+use crate::part2::{stone_count, Blinks};
+use aoc_common::memo::Memo;
+use aoc_common::stream::for_each_word;
+use std::io::BufRead;
+
+/// The most counts remembered at once. A single stone the size of the puzzle's needs about
+/// 130 000, and 200 000 fit a table of 2^18 buckets of 25 bytes, about 6.3 MiB.
+const MEMO_LIMIT: usize = 200_000;
+
+/// Solves both parts a stone at a time, counting with [`stone_count`]. The input is a single
+/// line, so it is read word by word rather than line by line, and the memo is bounded so that
+/// stones that are all different cannot grow it without end.
+pub fn solve_both(reader: impl BufRead) -> miette::Result<(String, String), AocError> {
+    let mut memo = Memo::<Blinks, u64>::bounded(MEMO_LIMIT);
+    // A long enough line of stones has more than a `u64` counts
+    let mut after_25 = 0_u128;
+    let mut after_75 = 0_u128;
+    for_each_word(reader, |stone| {
+        let stone = stone
+            .parse()
+            .map_err(|_| AocError::malformed(format!("Invalid stone: {stone}")))?;
+        after_25 += u128::from(stone_count(stone, 25, &mut memo));
+        after_75 += u128::from(stone_count(stone, 75, &mut memo));
+        Ok::<_, AocError>(())
+    })?;
+
+    Ok((after_25.to_string(), after_75.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_both() -> miette::Result<()> {
+        let input = "125 17\n";
+        assert_eq!(crate::solve_both(input)?, solve_both(input.as_bytes())?);
+        assert_eq!(
+            crate::solve_both("125 17 125 17")?,
+            solve_both("125 17\n125\t17".as_bytes())?
+        );
+        assert!(solve_both("125 -17".as_bytes()).is_err());
+        Ok(())
+    }
+}
@@ -1,4 +1,19 @@
+//! Day 12: Garden Groups.
+//!
+//! [`model::parse`] reads the garden and [`model::Garden::to_graph`] connects neighbouring
+//! plots of the same plant type, whose regions [`part1::fence_price`] and
+//! [`part2::bulk_fence_price`] price.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let garden = model::parse(input)?;
+    Ok((part1::solve(&garden)?, part2::solve(&garden)?))
+}
@@ -0,0 +1,81 @@
+use crate::custom_error::AocError;
+use glam::IVec2;
+use petgraph::Graph;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The garden plots, one plant type letter per plot and one string per row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Garden(pub Vec<String>);
+
+const NEIGHBORS: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 0),
+];
+
+impl Garden {
+    /// A graph of the plots weighted by their position, with an edge between every two
+    /// neighbouring plots of the same plant type.
+    pub fn to_graph(&self) -> Graph<IVec2, ()> {
+        let map = self
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(move |(x, c)| (IVec2::new(x as i32, y as i32), c))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut graph = Graph::new();
+        let mut index_mapping = HashMap::new();
+
+        for coordinates in map.keys() {
+            let nodeindex = graph.add_node(*coordinates);
+            index_mapping.insert(coordinates, nodeindex);
+        }
+
+        for (coordinates, c) in &map {
+            NEIGHBORS
+                .iter()
+                .map(|&direction| coordinates + direction)
+                .filter(|neighbor| map.get(neighbor) == Some(c))
+                .for_each(|neighbor| {
+                    if let (Some(index), Some(neighbor)) =
+                        (index_mapping.get(coordinates), index_mapping.get(&neighbor))
+                    {
+                        graph.add_edge(*index, *neighbor, ());
+                    }
+                })
+        }
+        graph
+    }
+}
+
+pub fn parse(input: &str) -> Result<Garden, AocError> {
+    let rows = input.lines().map(str::to_string).collect::<Vec<_>>();
+
+    if let Some(c) = rows
+        .iter()
+        .flat_map(|row| row.chars())
+        .find(|c| !c.is_ascii_uppercase())
+    {
+        return Err(AocError::malformed(format!("Invalid plant type: {c}")));
+    }
+    match rows.first() {
+        Some(first) if rows.iter().any(|row| row.len() != first.len()) => {
+            Err(AocError::malformed("Rows differ in length"))
+        }
+        _ => Ok(Garden(rows)),
+    }
+}
+
+impl fmt::Display for Garden {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|row| writeln!(f, "{row}"))
+    }
+}
@@ -1,57 +1,12 @@
 use crate::custom_error::AocError;
-use glam::IVec2;
+use crate::model::{parse, Garden};
 use petgraph::algo::kosaraju_scc;
-use petgraph::Graph;
-use std::collections::HashMap;
 
-const ABOVE: IVec2 = IVec2::new(0, -1);
-const BELOW: IVec2 = IVec2::new(0, 1);
-const LEFT: IVec2 = IVec2::new(-1, 0);
-const RIGHT: IVec2 = IVec2::new(1, 0);
+/// The total price of fencing every region, each its area times its perimeter.
+pub fn fence_price(garden: &Garden) -> u64 {
+    let graph = garden.to_graph();
 
-const SURROUNDINGS: [IVec2; 4] = [ABOVE, BELOW, LEFT, RIGHT];
-
-fn parse(input: &str) -> Graph<(), ()> {
-    let map = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(x, c)| (IVec2::new(x as i32, y as i32), c))
-        })
-        .collect::<HashMap<_, _>>();
-
-    let mut graph = Graph::new();
-    let mut index_mapping = HashMap::new();
-
-    for (coordinates, _) in &map {
-        let nodeindex = graph.add_node(());
-        index_mapping.insert(coordinates, nodeindex);
-    }
-
-    for (coordinates, c) in &map {
-        SURROUNDINGS
-            .iter()
-            .map(|&direction| coordinates + direction)
-            .filter(|neighbor| map.get(neighbor) == Some(c))
-            .for_each(|neighbor| {
-                match (index_mapping.get(coordinates), index_mapping.get(&neighbor)) {
-                    (Some(index), Some(neighbor)) => {
-                        graph.add_edge(index.clone(), neighbor.clone(), ());
-                    }
-                    _ => {}
-                }
-            })
-    }
-    graph
-}
-
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let graph = parse(_input);
-
-    let price = kosaraju_scc(&graph)
+    kosaraju_scc(&graph)
         .iter()
         .map(|nodes| {
             let area = nodes.len() as u64;
@@ -62,9 +17,17 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
 
             area * perimeter
         })
-        .sum::<u64>();
+        .sum::<u64>()
+}
 
-    Ok(price.to_string())
+/// Solves part 1 for the parsed garden.
+pub fn solve(garden: &Garden) -> Result<String, AocError> {
+    Ok(fence_price(garden).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
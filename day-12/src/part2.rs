@@ -1,10 +1,10 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, Garden};
 use glam::IVec2;
 use itertools::Itertools;
 use petgraph::algo::kosaraju_scc;
 use petgraph::graph::NodeIndex;
 use petgraph::Graph;
-use std::collections::HashMap;
 
 const ABOVE: IVec2 = IVec2::new(0, -1);
 const BELOW: IVec2 = IVec2::new(0, 1);
@@ -13,43 +13,7 @@ const RIGHT: IVec2 = IVec2::new(1, 0);
 
 const SURROUNDINGS: [IVec2; 4] = [ABOVE, RIGHT, BELOW, LEFT];
 
-fn parse(input: &str) -> Graph<IVec2, ()> {
-    let map = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(x, c)| (IVec2::new(x as i32, y as i32), c))
-        })
-        .collect::<HashMap<_, _>>();
-
-    let mut graph = Graph::new();
-    let mut index_mapping = HashMap::new();
-
-    for (coordinates, _) in &map {
-        let nodeindex = graph.add_node(coordinates.clone());
-        index_mapping.insert(coordinates, nodeindex);
-    }
-
-    for (coordinates, c) in &map {
-        SURROUNDINGS
-            .iter()
-            .map(|&direction| coordinates + direction)
-            .filter(|neighbor| map.get(neighbor) == Some(c))
-            .for_each(|neighbor| {
-                match (index_mapping.get(coordinates), index_mapping.get(&neighbor)) {
-                    (Some(index), Some(neighbor)) => {
-                        graph.add_edge(index.clone(), neighbor.clone(), ());
-                    }
-                    _ => {}
-                }
-            })
-    }
-    graph
-}
-
-fn count_corners(graph: &Graph<IVec2, ()>, node: &NodeIndex, node_group: &Vec<NodeIndex>) -> u64 {
+fn count_corners(graph: &Graph<IVec2, ()>, node: &NodeIndex, node_group: &[NodeIndex]) -> u64 {
     SURROUNDINGS
         .iter()
         .circular_tuple_windows()
@@ -73,23 +37,32 @@ fn count_corners(graph: &Graph<IVec2, ()>, node: &NodeIndex, node_group: &Vec<No
         .sum()
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let graph = parse(_input);
+/// The total price of fencing every region with the bulk discount, each its area times its
+/// number of sides.
+pub fn bulk_fence_price(garden: &Garden) -> u64 {
+    let graph = garden.to_graph();
 
-    let price = kosaraju_scc(&graph)
+    kosaraju_scc(&graph)
         .iter()
         .map(|nodes| {
             let area = nodes.len() as u64;
             let walls = nodes
                 .iter()
-                .map(|node| count_corners(&graph, &node, &nodes))
+                .map(|node| count_corners(&graph, node, nodes))
                 .sum::<u64>();
             area * walls
         })
-        .sum::<u64>();
+        .sum::<u64>()
+}
 
-    Ok(price.to_string())
+/// Solves part 2 for the parsed garden.
+pub fn solve(garden: &Garden) -> Result<String, AocError> {
+    Ok(bulk_fence_price(garden).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -1,20 +1,50 @@
+use aoc_common::input::bench_input;
 use criterion::{criterion_group, criterion_main, Criterion};
 use day_13::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 1);
 
     let mut group = c.benchmark_group("day_13::part1");
     group.bench_with_input("part1", input, |b, input| b.iter(|| part1::process(input)));
+    group.bench_with_input("cramer-i64", input, |b, input| {
+        b.iter(|| model::parse(input).map(|arcade| part1::total_tokens_cramer(&arcade)))
+    });
+    group.bench_with_input("lu-f64", input, |b, input| {
+        b.iter(|| model::parse(input).map(|arcade| part1::total_tokens_lu_f64(&arcade)))
+    });
 
     group.finish();
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 2);
 
     let mut group = c.benchmark_group("day_13::part2");
-    group.bench_with_input("part2", input, |b, input| b.iter(|| part2::process(input)));
+    group.bench_with_input("part2", input, |b, input| {
+        b.iter(|| part2::process(input, 10000000000000))
+    });
+    group.bench_with_input("cramer-i64", input, |b, input| {
+        b.iter(|| {
+            model::parse(input).map(|arcade| part2::total_tokens_cramer(&arcade, 10000000000000))
+        })
+    });
+    group.bench_with_input("lu-f64", input, |b, input| {
+        b.iter(|| {
+            model::parse(input).map(|arcade| part2::total_tokens_lu_f64(&arcade, 10000000000000))
+        })
+    });
+
+    group.finish();
+}
+
+fn criterion_benchmark_solve_both(c: &mut Criterion) {
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 1);
+
+    let mut group = c.benchmark_group("day_13::solve_both");
+    group.bench_with_input("solve_both", input, |b, input| {
+        b.iter(|| solve_both(input, 10000000000000))
+    });
 
     group.finish();
 }
@@ -22,6 +52,7 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_solve_both
 );
 criterion_main!(benches);
@@ -1,3 +1,5 @@
+use aoc_common::config::Config;
+use aoc_common::input;
 use day_13::part2::process;
 use miette::Context;
 
@@ -13,8 +15,9 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    let file = &input::load(env!("CARGO_MANIFEST_DIR"), 2).context("load the input")?;
+    let config = Config::load(&Config::default_path()).context("load aoc.toml")?;
+    let result = process(file, config.day13.prize_offset).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
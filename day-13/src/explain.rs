@@ -0,0 +1,135 @@
+use crate::model::{Arcade, Machine};
+use serde::Serialize;
+use std::fmt;
+
+/// The cheapest way found to win a prize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Win {
+    pub presses_a: u64,
+    pub presses_b: u64,
+    pub tokens: u64,
+}
+
+impl Win {
+    fn of(machine: &Machine) -> Option<Win> {
+        machine
+            .cheapest_presses()
+            .map(|(presses_a, presses_b)| Win {
+                presses_a,
+                presses_b,
+                tokens: 3 * presses_a + presses_b,
+            })
+    }
+}
+
+/// How one claw machine was played.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Played {
+    /// The line of the machine's button A, counting from 1
+    pub line: usize,
+    pub machine: Machine,
+    /// The win with the prize where the input puts it, if it can be won
+    pub part1: Option<Win>,
+    /// The win with the prize moved for part 2, if it can be won
+    pub part2: Option<Win>,
+}
+
+/// Which button presses won each prize, for either part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    pub prize_offset: u64,
+    pub machines: Vec<Played>,
+}
+
+pub fn explain(arcade: &Arcade, prize_offset: u64) -> Explanation {
+    Explanation {
+        prize_offset,
+        // Every machine takes three lines and a blank one
+        machines: (0..)
+            .zip(&arcade.0)
+            .map(|(index, machine)| Played {
+                line: 4 * index + 1,
+                machine: machine.clone(),
+                part1: Win::of(machine),
+                part2: Win::of(&machine.with_prize_offset(prize_offset)),
+            })
+            .collect(),
+    }
+}
+
+impl Explanation {
+    /// The answers, added up from the tokens of the prizes won.
+    pub fn answers(&self) -> (String, String) {
+        let total = |win: fn(&Played) -> Option<Win>| {
+            self.machines
+                .iter()
+                .filter_map(win)
+                .map(|win| win.tokens)
+                .sum::<u64>()
+        };
+        (
+            total(|played| played.part1).to_string(),
+            total(|played| played.part2).to_string(),
+        )
+    }
+}
+
+impl fmt::Display for Win {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "A {} times, B {} times for {} tokens",
+            self.presses_a, self.presses_b, self.tokens
+        )
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |win: Option<Win>| match win {
+            Some(win) => win.to_string(),
+            None => "no win".to_string(),
+        };
+        for played in &self.machines {
+            let (x, y) = played.machine.target;
+            writeln!(f, "{:>4}: prize at X={x}, Y={y}", played.line)?;
+            writeln!(f, "      part 1: {}", describe(played.part1))?;
+            writeln!(f, "      part 2: {}", describe(played.part2))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse;
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let input = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+";
+        let explanation = explain(&parse(input)?, 10000000000000);
+        assert_eq!(
+            crate::solve_both(input, 10000000000000)?,
+            explanation.answers()
+        );
+        assert_eq!(
+            "   1: prize at X=8400, Y=5400
+      part 1: A 80 times, B 40 times for 280 tokens
+      part 2: no win
+   5: prize at X=12748, Y=12176
+      part 1: no win
+      part 2: A 118679050709 times, B 103199174542 times for 459236326669 tokens
+",
+            explanation.to_string()
+        );
+        Ok(())
+    }
+}
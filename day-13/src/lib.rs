@@ -1,4 +1,20 @@
+//! Day 13: Claw Contraption.
+//!
+//! [`model::parse`] reads the claw machines and [`model::Machine::cheapest_win`] solves a
+//! single one, which [`part1::total_tokens`] and [`part2::total_tokens`] sum up.
+//! [`explain::explain`] shows the button presses that won each prize.
+
 pub mod custom_error;
+pub mod model;
 
+pub mod explain;
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str, prize_offset: u64) -> miette::Result<(String, String), AocError> {
+    let arcade = model::parse(input)?;
+    Ok((part1::solve(&arcade)?, part2::solve(&arcade, prize_offset)?))
+}
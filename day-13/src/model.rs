@@ -0,0 +1,274 @@
+use crate::custom_error::AocError;
+use nalgebra::{Matrix2, Vector2};
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::character::complete::newline;
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, terminated, tuple};
+use nom::{Finish, IResult, Parser};
+use serde::Serialize;
+use std::fmt;
+
+/// The furthest the prizes can be moved, a hundred times as far as part 2 moves them. With
+/// buttons and prizes the size of the puzzle's, every count stays well within a `u64` up to
+/// there.
+pub const MAX_PRIZE_OFFSET: u64 = 1_000_000_000_000_000;
+
+/// Checks that moving the prizes `offset` further keeps the token counts in range.
+pub fn check_prize_offset(offset: u64) -> Result<(), AocError> {
+    if offset > MAX_PRIZE_OFFSET {
+        return Err(AocError::invalid_parameter(format!(
+            "The prize offset {offset} is larger than {MAX_PRIZE_OFFSET}"
+        )));
+    }
+    Ok(())
+}
+
+/// A claw machine, with every pair as `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Machine {
+    pub button_a: (u64, u64),
+    pub button_b: (u64, u64),
+    pub target: (u64, u64),
+}
+
+impl Machine {
+    /// The machine with the prize moved `offset` further along both axes.
+    pub fn with_prize_offset(&self, offset: u64) -> Machine {
+        Machine {
+            target: (self.target.0 + offset, self.target.1 + offset),
+            ..self.clone()
+        }
+    }
+
+    /// The fewest tokens needed to win the prize, at 3 tokens per press of button A and 1 per
+    /// press of button B, or `None` if it can't be won.
+    pub fn cheapest_win(&self) -> Option<u64> {
+        self.cheapest_presses()
+            .map(|(presses_a, presses_b)| 3 * presses_a + presses_b)
+    }
+
+    /// How often to press button A and button B for [`Machine::cheapest_win`].
+    pub fn cheapest_presses(&self) -> Option<(u64, u64)> {
+        let [ax, ay, bx, by, tx, ty] = [
+            self.button_a.0,
+            self.button_a.1,
+            self.button_b.0,
+            self.button_b.1,
+            self.target.0,
+            self.target.1,
+        ]
+        .map(i128::from);
+
+        match aoc_math::solve_2x2([[ax, bx], [ay, by]], [tx, ty]) {
+            Some([presses_a, presses_b]) => {
+                // Whole presses only, and a machine can't be played backwards
+                let presses_a = u64::try_from(presses_a.to_integer()?).ok()?;
+                let presses_b = u64::try_from(presses_b.to_integer()?).ok()?;
+                Some((presses_a, presses_b))
+            }
+            None => self.cheapest_collinear_presses(),
+        }
+    }
+
+    /// The cheapest presses of buttons that move in the same direction, when the prize lies in
+    /// that direction too. Among all the ways to reach it along the X axis, the cost changes
+    /// steadily from the fewest presses of A to the most, so one of those two is cheapest.
+    fn cheapest_collinear_presses(&self) -> Option<(u64, u64)> {
+        let [ax, ay, bx, by, tx, ty] = [
+            self.button_a.0,
+            self.button_a.1,
+            self.button_b.0,
+            self.button_b.1,
+            self.target.0,
+            self.target.1,
+        ]
+        .map(i128::from);
+
+        let along_x = aoc_math::solve_linear(ax, bx, tx)?;
+        let ks = along_x.non_negative().filter(|ks| !ks.is_empty())?;
+        [*ks.start(), *ks.end()]
+            .into_iter()
+            .filter_map(|k| along_x.solution(k))
+            .filter(|&(presses_a, presses_b)| ay * presses_a + by * presses_b == ty)
+            .map(|(presses_a, presses_b)| (presses_a as u64, presses_b as u64))
+            .min_by_key(|&(presses_a, presses_b)| 3 * presses_a + presses_b)
+    }
+
+    /// Like [`Machine::cheapest_win`], but solves with Cramer's rule directly in `i64`. Buttons
+    /// moving in the same direction have no unique solution, so this gives up on them.
+    pub fn cheapest_win_cramer(&self) -> Option<u64> {
+        let [ax, ay, bx, by, tx, ty] = [
+            self.button_a.0,
+            self.button_a.1,
+            self.button_b.0,
+            self.button_b.1,
+            self.target.0,
+            self.target.1,
+        ]
+        .map(|value| value as i64);
+
+        let determinant = ax * by - bx * ay;
+        if determinant == 0 {
+            return None;
+        }
+        let presses_a = tx * by - bx * ty;
+        let presses_b = ax * ty - tx * ay;
+
+        // Whole presses only, and a machine can't be played backwards
+        if presses_a % determinant != 0 || presses_b % determinant != 0 {
+            return None;
+        }
+        let presses_a = u64::try_from(presses_a / determinant).ok()?;
+        let presses_b = u64::try_from(presses_b / determinant).ok()?;
+        Some(3 * presses_a + presses_b)
+    }
+
+    /// Like [`Machine::cheapest_win`], but solves with a floating point LU decomposition and
+    /// rounds to whole presses. Buttons moving in the same direction have no unique solution,
+    /// so this gives up on them too.
+    pub fn cheapest_win_lu_f64(&self) -> Option<u64> {
+        let buttons = Matrix2::new(
+            self.button_a.0 as f64,
+            self.button_b.0 as f64,
+            self.button_a.1 as f64,
+            self.button_b.1 as f64,
+        );
+
+        let target = Vector2::new(self.target.0 as f64, self.target.1 as f64);
+
+        let solution = buttons.lu().solve(&target)?;
+        let solution_a = solution.x.round() as u64;
+        let solution_b = solution.y.round() as u64;
+
+        // The solution is only rounded, so it has to hit the prize exactly in whole presses
+        (solution_a * self.button_a.0 + solution_b * self.button_b.0 == self.target.0
+            && solution_a * self.button_a.1 + solution_b * self.button_b.1 == self.target.1)
+            .then_some(3 * solution_a + solution_b)
+    }
+}
+
+fn machine(input: &str) -> IResult<&str, Machine> {
+    tuple((
+        preceded(tag("Button A: X+"), complete::u64),
+        preceded(tag(", Y+"), complete::u64),
+        newline,
+        preceded(tag("Button B: X+"), complete::u64),
+        preceded(tag(", Y+"), complete::u64),
+        newline,
+        preceded(tag("Prize: X="), complete::u64),
+        preceded(tag(", Y="), complete::u64),
+    ))
+    .map(|parsed| Machine {
+        button_a: (parsed.0, parsed.1),
+        button_b: (parsed.3, parsed.4),
+        target: (parsed.6, parsed.7),
+    })
+    .parse(input)
+}
+
+/// The claw machines of the arcade.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Arcade(pub Vec<Machine>);
+
+pub fn parse(input: &str) -> Result<Arcade, AocError> {
+    all_consuming(terminated(
+        separated_list1(tag("\n\n"), machine),
+        opt(newline),
+    ))(input)
+    .finish()
+    .map(|(_, machines)| Arcade(machines))
+    .map_err(|err| AocError::malformed(format!("Invalid input at: {:.20}", err.input)))
+}
+
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Button A: X+{}, Y+{}", self.button_a.0, self.button_a.1)?;
+        writeln!(f, "Button B: X+{}, Y+{}", self.button_b.0, self.button_b.1)?;
+        writeln!(f, "Prize: X={}, Y={}", self.target.0, self.target.1)
+    }
+}
+
+impl fmt::Display for Arcade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, machine) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{machine}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cheapest_win_cramer() {
+        let machine = Machine {
+            button_a: (94, 34),
+            button_b: (22, 67),
+            target: (8400, 5400),
+        };
+        assert_eq!(Some(280), machine.cheapest_win_cramer());
+        assert_eq!(machine.cheapest_win(), machine.cheapest_win_cramer());
+
+        let unwinnable = Machine {
+            button_a: (26, 66),
+            button_b: (67, 21),
+            target: (12748, 12176),
+        };
+        assert_eq!(None, unwinnable.cheapest_win_cramer());
+        let moved = unwinnable.with_prize_offset(10000000000000);
+        assert_eq!(Some(459236326669), moved.cheapest_win_cramer());
+        assert_eq!(moved.cheapest_win(), moved.cheapest_win_cramer());
+    }
+
+    #[test]
+    fn test_cheapest_win_lu_f64() {
+        let machine = Machine {
+            button_a: (94, 34),
+            button_b: (22, 67),
+            target: (8400, 5400),
+        };
+        assert_eq!(Some(280), machine.cheapest_win_lu_f64());
+
+        let unwinnable = Machine {
+            button_a: (26, 66),
+            button_b: (67, 21),
+            target: (12748, 12176),
+        };
+        assert_eq!(None, unwinnable.cheapest_win_lu_f64());
+        let moved = unwinnable.with_prize_offset(10000000000000);
+        assert_eq!(moved.cheapest_win(), moved.cheapest_win_lu_f64());
+    }
+
+    #[test]
+    fn test_cheapest_win_collinear() {
+        // Three presses of B cost 3, two of A cost 6
+        let machine = Machine {
+            button_a: (3, 6),
+            button_b: (2, 4),
+            target: (6, 12),
+        };
+        assert_eq!(Some(3), machine.cheapest_win());
+        assert_eq!(None, machine.cheapest_win_cramer());
+
+        // A is cheaper per step now
+        let machine = Machine {
+            button_a: (9, 9),
+            button_b: (1, 1),
+            target: (20, 20),
+        };
+        assert_eq!(Some(8), machine.cheapest_win());
+
+        let off_line = Machine {
+            target: (6, 13),
+            ..machine.clone()
+        };
+        assert_eq!(None, off_line.cheapest_win());
+    }
+}
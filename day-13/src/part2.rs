@@ -1,84 +1,51 @@
 use crate::custom_error::AocError;
-use nalgebra::{Matrix2, Vector2};
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::multi::separated_list0;
-use nom::sequence::{preceded, tuple};
-use nom::Parser;
+use crate::model::{check_prize_offset, parse, Arcade};
 
-#[derive(Debug)]
-struct Machine {
-    button_a: (u64, u64),
-    button_b: (u64, u64),
-    target: (u64, u64),
+/// The fewest tokens needed to win every prize that can be won, with every prize moved
+/// `prize_offset` further along both axes. The offset must have passed [`check_prize_offset`].
+pub fn total_tokens(arcade: &Arcade, prize_offset: u64) -> u64 {
+    arcade
+        .0
+        .iter()
+        .filter_map(|machine| machine.with_prize_offset(prize_offset).cheapest_win())
+        .sum()
 }
 
-fn parse(input: &str) -> Vec<Machine> {
-    let (_input, parsed_machines) = separated_list0(
-        tag::<&str, &str, ()>("\n\n"),
-        tuple((
-            preceded(tag("Button A: X+"), complete::u64),
-            preceded(tag(", Y+"), complete::u64),
-            tag("\n"),
-            preceded(tag("Button B: X+"), complete::u64),
-            preceded(tag(", Y+"), complete::u64),
-            tag("\n"),
-            preceded(tag("Prize: X="), complete::u64),
-            preceded(tag(", Y="), complete::u64),
-        )),
-    )(input)
-    .unwrap();
-
-    parsed_machines
+/// Like [`total_tokens`], but solving each machine with Cramer's rule in `i64`.
+pub fn total_tokens_cramer(arcade: &Arcade, prize_offset: u64) -> u64 {
+    arcade
+        .0
         .iter()
-        .map(|parsed| Machine {
-            button_a: (parsed.0, parsed.1),
-            button_b: (parsed.3, parsed.4),
-            target: (parsed.6 + 10000000000000, parsed.7 + 10000000000000),
+        .filter_map(|machine| {
+            machine
+                .with_prize_offset(prize_offset)
+                .cheapest_win_cramer()
         })
-        .collect()
+        .sum()
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let machines = parse(_input);
-
-    let result = machines
+/// Like [`total_tokens`], but solving each machine in floating point.
+pub fn total_tokens_lu_f64(arcade: &Arcade, prize_offset: u64) -> u64 {
+    arcade
+        .0
         .iter()
-        .map(|machine| {
-            let A = Matrix2::new(
-                machine.button_a.0 as f64,
-                machine.button_b.0 as f64,
-                machine.button_a.1 as f64,
-                machine.button_b.1 as f64,
-            );
-
-            let target = Vector2::new(machine.target.0 as f64, machine.target.1 as f64);
-
-            let maybe_solution = A.lu().solve(&target);
-
-            dbg!(&maybe_solution);
-
-            match maybe_solution {
-                Some(solution) => {
-                    let solution_a = solution.x.round() as u64;
-                    let solution_b = solution.y.round() as u64;
-                    if solution_a * machine.button_a.0 + solution_b * machine.button_b.0
-                        == machine.target.0
-                        && solution_a * machine.button_a.1 + solution_b * machine.button_b.1
-                            == machine.target.1
-                    {
-                        3 * solution_a + solution_b
-                    } else {
-                        0
-                    }
-                }
-                None => 0,
-            }
+        .filter_map(|machine| {
+            machine
+                .with_prize_offset(prize_offset)
+                .cheapest_win_lu_f64()
         })
-        .sum::<u64>();
+        .sum()
+}
 
-    Ok(result.to_string())
+/// Solves part 2 for the parsed claw machines.
+pub fn solve(arcade: &Arcade, prize_offset: u64) -> Result<String, AocError> {
+    check_prize_offset(prize_offset)?;
+    Ok(total_tokens(arcade, prize_offset).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str, prize_offset: u64) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?, prize_offset)
 }
 
 #[cfg(test)]
@@ -102,7 +69,18 @@ Prize: X=7870, Y=6450
 Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279";
-        assert_eq!("480", process(input)?);
+        assert_eq!("480", process(input, 0)?);
         Ok(())
     }
+
+    #[test]
+    fn test_prize_offset_out_of_range() {
+        let input = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400";
+        assert!(matches!(
+            process(input, u64::MAX),
+            Err(AocError::InvalidParameter(_))
+        ));
+    }
 }
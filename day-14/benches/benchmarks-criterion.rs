@@ -1,23 +1,40 @@
+use aoc_common::input::bench_input;
 use criterion::{criterion_group, criterion_main, Criterion};
 use day_14::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 1);
 
     let mut group = c.benchmark_group("day_14::part1");
     group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| part1::process(input, 103, 101))
+        b.iter(|| part1::process(input, 103, 101, 100))
     });
 
     group.finish();
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 2);
 
     let mut group = c.benchmark_group("day_14::part2");
     group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| part2::process(input, 103, 101))
+        b.iter(|| part2::process(input, 103, 101, 100000))
+    });
+    group.bench_with_input("crt", input, |b, input| {
+        b.iter(|| {
+            model::parse(input).map(|robots| part2::easter_egg_step_crt(&robots, 103, 101, 100000))
+        })
+    });
+
+    group.finish();
+}
+
+fn criterion_benchmark_solve_both(c: &mut Criterion) {
+    let input = &bench_input(env!("CARGO_MANIFEST_DIR"), 1);
+
+    let mut group = c.benchmark_group("day_14::solve_both");
+    group.bench_with_input("solve_both", input, |b, input| {
+        b.iter(|| solve_both(input, 103, 101, 100, 100000))
     });
 
     group.finish();
@@ -26,6 +43,7 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_solve_both
 );
 criterion_main!(benches);
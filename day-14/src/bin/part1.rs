@@ -1,3 +1,5 @@
+use aoc_common::config::Config;
+use aoc_common::input;
 use day_14::part1::process;
 use miette::Context;
 
@@ -13,8 +15,15 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file, 103, 101).context("process part 1")?;
+    let file = &input::load(env!("CARGO_MANIFEST_DIR"), 1).context("load the input")?;
+    let config = Config::load(&Config::default_path()).context("load aoc.toml")?;
+    let result = process(
+        file,
+        config.day14.height,
+        config.day14.width,
+        config.day14.steps,
+    )
+    .context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
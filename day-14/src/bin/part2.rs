@@ -1,3 +1,5 @@
+use aoc_common::config::Config;
+use aoc_common::input;
 use day_14::part2::process;
 use miette::Context;
 
@@ -13,9 +15,16 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
+    let file = &input::load(env!("CARGO_MANIFEST_DIR"), 2).context("load the input")?;
+    let config = Config::load(&Config::default_path()).context("load aoc.toml")?;
 
-    let result = process(file, 103, 101).context("process part 2")?;
+    let result = process(
+        file,
+        config.day14.height,
+        config.day14.width,
+        config.day14.max_steps,
+    )
+    .context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -1,4 +1,41 @@
+//! Day 14: Restroom Redoubt.
+//!
+//! [`model::parse`] reads the robots and [`model::Robot::position_after`] moves a single one.
+//! [`part1::safety_factor`] counts the robots per quadrant, [`part2::easter_egg_step`] finds
+//! the Christmas tree.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use aoc_common::cancel::CancelToken;
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(
+    input: &str,
+    height: u32,
+    width: u32,
+    steps: usize,
+    max_steps: usize,
+) -> miette::Result<(String, String), AocError> {
+    solve_both_cancellable(input, height, width, steps, max_steps, &CancelToken::new())
+}
+
+/// Like [`solve_both`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+pub fn solve_both_cancellable(
+    input: &str,
+    height: u32,
+    width: u32,
+    steps: usize,
+    max_steps: usize,
+    cancel: &CancelToken,
+) -> miette::Result<(String, String), AocError> {
+    let robots = model::parse(input)?;
+    Ok((
+        part1::solve(&robots, height, width, steps)?,
+        part2::solve(&robots, height, width, max_steps, cancel)?,
+    ))
+}
@@ -0,0 +1,216 @@
+use crate::custom_error::AocError;
+use aoc_common::cycle::Cycle;
+use aoc_common::simulation::Simulation;
+use glam::IVec2;
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::character::complete::line_ending;
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, separated_pair, terminated};
+use nom::{Finish, IResult, Parser};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Robot {
+    pub position: IVec2,
+    /// Tiles per second
+    pub velocity: IVec2,
+}
+
+/// The robots in the bathroom, in input order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Robots(pub Vec<Robot>);
+
+impl Robot {
+    /// The robot's position after `steps` seconds on a grid that wraps around at its edges.
+    /// Any number of seconds works, as the robot comes back to its column every `width` seconds
+    /// and to its row every `height` seconds.
+    pub fn position_after(&self, steps: usize, height: u32, width: u32) -> IVec2 {
+        // Reduced that way, the steps times the velocity fit an `i64` on any floor
+        let along = |position: i32, velocity: i32, size: u32| {
+            let steps = (steps % size as usize) as i64;
+            (i64::from(position) + i64::from(velocity) * steps).rem_euclid(i64::from(size)) as i32
+        };
+        IVec2::new(
+            along(self.position.x, self.velocity.x, width),
+            along(self.position.y, self.velocity.y, height),
+        )
+    }
+}
+
+/// Every robot wraps around both dimensions in `width × height` seconds, so the robots are back
+/// at their initial positions by then, if not sooner.
+pub fn period(height: u32, width: u32) -> Cycle {
+    Cycle {
+        start: 0,
+        period: height as usize * width as usize,
+    }
+}
+
+/// Checks that a bathroom of `height` by `width` tiles has room for the robots and that its
+/// positions fit an `i32`.
+pub fn check_floor(height: u32, width: u32) -> Result<(), AocError> {
+    if height == 0 || width == 0 {
+        return Err(AocError::invalid_parameter(format!(
+            "The floor is {height} by {width} tiles, it needs at least one tile"
+        )));
+    }
+    if i32::try_from(height.max(width)).is_err() {
+        return Err(AocError::invalid_parameter(format!(
+            "The floor is {height} by {width} tiles, which is too large"
+        )));
+    }
+    Ok(())
+}
+
+/// The robots moving around a bathroom of `height` by `width` tiles, one second per step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Floor {
+    pub robots: Robots,
+    pub height: u32,
+    pub width: u32,
+    /// Seconds passed since the robots were at their initial positions
+    pub seconds: usize,
+}
+
+impl Floor {
+    pub fn new(robots: Robots, height: u32, width: u32) -> Self {
+        Floor {
+            robots,
+            height,
+            width,
+            seconds: 0,
+        }
+    }
+
+    /// Moves the robots `seconds` ahead at once.
+    pub fn fast_forward(&mut self, seconds: usize) {
+        for robot in self.robots.0.iter_mut() {
+            robot.position = robot.position_after(seconds, self.height, self.width);
+        }
+        self.seconds += seconds;
+    }
+}
+
+impl Simulation for Floor {
+    type Snapshot = (usize, Vec<IVec2>);
+    /// The seconds passed after the step
+    type Event = usize;
+
+    fn step(&mut self) -> Option<usize> {
+        for robot in self.robots.0.iter_mut() {
+            robot.position = robot.position_after(1, self.height, self.width);
+        }
+        self.seconds += 1;
+        Some(self.seconds)
+    }
+
+    fn snapshot(&self) -> (usize, Vec<IVec2>) {
+        let positions = self.robots.0.iter().map(|robot| robot.position).collect();
+        (self.seconds, positions)
+    }
+
+    fn restore(&mut self, (seconds, positions): (usize, Vec<IVec2>)) {
+        self.seconds = seconds;
+        for (robot, position) in self.robots.0.iter_mut().zip(positions) {
+            robot.position = position;
+        }
+    }
+}
+
+fn vector(input: &str) -> IResult<&str, IVec2> {
+    separated_pair(complete::i32, tag(","), complete::i32)
+        .map(|(x, y)| IVec2::new(x, y))
+        .parse(input)
+}
+
+fn robots(input: &str) -> IResult<&str, Robots> {
+    terminated(
+        separated_list1(
+            line_ending,
+            separated_pair(
+                preceded(tag("p="), vector),
+                tag(" "),
+                preceded(tag("v="), vector),
+            )
+            .map(|(position, velocity)| Robot { position, velocity }),
+        ),
+        opt(line_ending),
+    )
+    .map(Robots)
+    .parse(input)
+}
+
+pub fn parse(input: &str) -> Result<Robots, AocError> {
+    all_consuming(robots)(input)
+        .finish()
+        .map(|(_, robots)| robots)
+        .map_err(|err| AocError::malformed(format!("Invalid input at: {:.20}", err.input)))
+}
+
+impl fmt::Display for Robot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "p={},{} v={},{}",
+            self.position.x, self.position.y, self.velocity.x, self.velocity.y
+        )
+    }
+}
+
+impl fmt::Display for Robots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|robot| writeln!(f, "{robot}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::cycle;
+    use aoc_common::simulation::History;
+
+    #[test]
+    fn test_floor() -> miette::Result<()> {
+        let robots = parse("p=2,4 v=2,-3")?;
+        let mut history = History::new(Floor::new(robots.clone(), 7, 11), 5);
+
+        assert_eq!(Some(5), history.run_until(|floor| floor.seconds == 5));
+        assert_eq!(
+            robots.0[0].position_after(5, 7, 11),
+            history.simulation().robots.0[0].position
+        );
+        assert_eq!(Some((2, vec![IVec2::new(6, 5)])), history.snapshot_at(2));
+
+        history.rewind(4);
+        assert_eq!(1, history.simulation().seconds);
+        assert_eq!(IVec2::new(4, 1), history.simulation().robots.0[0].position);
+        Ok(())
+    }
+
+    #[test]
+    fn test_period() -> miette::Result<()> {
+        let robots = parse("p=2,4 v=2,-3\np=0,0 v=1,3")?;
+        let detected = cycle::brent(robots.clone(), |robots| {
+            let mut floor = Floor::new(robots.clone(), 7, 11);
+            floor.step();
+            Some(floor.robots)
+        })
+        .unwrap();
+        assert_eq!(0, detected.start);
+        assert_eq!(0, period(7, 11).period % detected.period);
+
+        let mut floor = Floor::new(robots.clone(), 7, 11);
+        let mut far = floor.clone();
+        floor.fast_forward(5);
+        far.fast_forward(77 * 1_000_000_000_000 + 5);
+        assert_eq!(floor.robots, far.robots);
+        assert_eq!(
+            robots.0[1].position_after(5, 7, 11),
+            floor.robots.0[1].position
+        );
+        Ok(())
+    }
+}
@@ -1,65 +1,21 @@
 use crate::custom_error::AocError;
-use glam::IVec2;
+use crate::model::{check_floor, parse, Robots};
 use itertools::Itertools;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::line_ending;
-use nom::multi::separated_list0;
-use nom::sequence::{preceded, separated_pair};
-use nom::IResult;
 
-#[derive(Debug)]
-struct Robot {
-    position: IVec2,
-    velocity: IVec2,
-}
-
-const STEPS: usize = 100;
-
-fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
-    let result = separated_list0(
-        line_ending,
-        separated_pair(
-            preceded(
-                tag("p="),
-                separated_pair(complete::i32, tag(","), complete::i32),
-            ),
-            tag(" "),
-            preceded(
-                tag("v="),
-                separated_pair(complete::i32, tag(","), complete::i32),
-            ),
-        ),
-    )(input)
-    .map(|(input, output)| {
-        let robots = output
-            .into_iter()
-            .map(|(position, velocity)| Robot {
-                position: IVec2::new(position.0, position.1),
-                velocity: IVec2::new(velocity.0, velocity.1),
-            })
-            .collect();
-
-        (input, robots)
-    });
-
-    result
-}
-
-#[tracing::instrument]
-pub fn process(_input: &str, height: u32, width: u32) -> miette::Result<String, AocError> {
-    let (_, robots) = parse(_input).unwrap();
-
-    let safety_factor = robots
+/// The product of the number of robots in each quadrant after `steps` seconds, leaving out the
+/// robots on the middle row and column.
+pub fn safety_factor(robots: &Robots, height: u32, width: u32, steps: usize) -> usize {
+    robots
+        .0
         .iter()
         .filter_map(|robot| {
-            let final_position_unwrapped = robot.position + robot.velocity * STEPS as i32;
-            let final_position_x = final_position_unwrapped.x.rem_euclid(width as i32) as u32;
-            let final_position_y = final_position_unwrapped.y.rem_euclid(height as i32) as u32;
+            let final_position = robot.position_after(steps, height, width);
+            let final_position_x = final_position.x as u32;
+            let final_position_y = final_position.y as u32;
 
             let (middle_x, middle_y) = (width / 2, height / 2);
 
-            let quadrant = if (0..middle_x).contains(&final_position_x)
+            if (0..middle_x).contains(&final_position_x)
                 && (0..middle_y).contains(&final_position_y)
             {
                 Some(1)
@@ -77,15 +33,27 @@ pub fn process(_input: &str, height: u32, width: u32) -> miette::Result<String,
                 Some(4)
             } else {
                 None
-            };
-
-            quadrant
+            }
         })
         .counts()
         .values()
-        .product::<usize>();
+        .product::<usize>()
+}
 
-    Ok(safety_factor.to_string())
+/// Solves part 1 for the parsed robots.
+pub fn solve(robots: &Robots, height: u32, width: u32, steps: usize) -> Result<String, AocError> {
+    check_floor(height, width)?;
+    Ok(safety_factor(robots, height, width, steps).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(
+    _input: &str,
+    height: u32,
+    width: u32,
+    steps: usize,
+) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?, height, width, steps)
 }
 
 #[cfg(test)]
@@ -106,7 +74,27 @@ p=9,3 v=2,3
 p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
-        assert_eq!("12", process(input, 7, 11)?);
+        assert_eq!("12", process(input, 7, 11, 100)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_floor() {
+        assert!(matches!(
+            process("p=0,0 v=1,1", 0, 11, 100),
+            Err(AocError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_largest_floor() -> miette::Result<()> {
+        let size = i32::MAX as u32;
+        let robots = parse("p=0,0 v=3,-5")?;
+        assert_eq!(
+            glam::IVec2::new(2112828788, 1489413863),
+            robots.0[0].position_after(1_000_000_000_000, size, size)
+        );
+        assert_eq!("1", solve(&robots, size, size, 1_000_000_000_000)?);
         Ok(())
     }
 }
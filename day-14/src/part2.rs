@@ -1,52 +1,12 @@
 use crate::custom_error::AocError;
+use crate::model::{check_floor, parse, period, Floor, Robots};
+use aoc_common::cancel::CancelToken;
+use aoc_common::simulation::Simulation;
+use aoc_math::Congruence;
 use glam::IVec2;
 use itertools::Itertools;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::line_ending;
-use nom::multi::separated_list0;
-use nom::sequence::{preceded, separated_pair};
-use nom::IResult;
 use std::collections::HashMap;
 
-#[derive(Debug)]
-struct Robot {
-    position: IVec2,
-    velocity: IVec2,
-}
-
-const STEPS: usize = 100000;
-
-fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
-    let result = separated_list0(
-        line_ending,
-        separated_pair(
-            preceded(
-                tag("p="),
-                separated_pair(complete::i32, tag(","), complete::i32),
-            ),
-            tag(" "),
-            preceded(
-                tag("v="),
-                separated_pair(complete::i32, tag(","), complete::i32),
-            ),
-        ),
-    )(input)
-    .map(|(input, output)| {
-        let robots = output
-            .into_iter()
-            .map(|(position, velocity)| Robot {
-                position: IVec2::new(position.0, position.1),
-                velocity: IVec2::new(velocity.0, velocity.1),
-            })
-            .collect();
-
-        (input, robots)
-    });
-
-    result
-}
-
 fn visualize_grid(grid: &HashMap<IVec2, char>, height: u32, width: u32) -> String {
     (0..height)
         .map(|y| {
@@ -57,32 +17,254 @@ fn visualize_grid(grid: &HashMap<IVec2, char>, height: u32, width: u32) -> Strin
         .join("\n")
 }
 
+/// The robots as `#` on a grid of `.`.
+pub fn draw(robots: &Robots, height: u32, width: u32) -> String {
+    let mut grid = (0..width)
+        .cartesian_product(0..height)
+        .map(|(x, y)| (IVec2::new(x as i32, y as i32), '.'))
+        .collect::<HashMap<IVec2, char>>();
+
+    for robot in robots.0.iter() {
+        grid.insert(robot.position, '#');
+    }
+
+    visualize_grid(&grid, height, width)
+}
+
 #[tracing::instrument]
-pub fn process(_input: &str, height: u32, width: u32) -> miette::Result<String, AocError> {
-    let (_, mut robots) = parse(_input).unwrap();
-
-    for step in 1..=STEPS {
-        let mut grid = (0..width)
-            .cartesian_product(0..height)
-            .map(|(x, y)| (IVec2::new(x as i32, y as i32), '.'))
-            .collect::<HashMap<IVec2, char>>();
-
-        for robot in robots.iter_mut() {
-            let new_position_unwrapped = robot.position + robot.velocity;
-            robot.position = IVec2::new(
-                new_position_unwrapped.x.rem_euclid(width as i32),
-                new_position_unwrapped.y.rem_euclid(height as i32),
-            );
-            grid.insert(robot.position, '#');
-        }
+pub fn process(
+    _input: &str,
+    height: u32,
+    width: u32,
+    max_steps: usize,
+) -> miette::Result<String, AocError> {
+    process_cancellable(_input, height, width, max_steps, &CancelToken::new())
+}
+
+/// Solves part 2 for the parsed robots.
+pub fn solve(
+    robots: &Robots,
+    height: u32,
+    width: u32,
+    max_steps: usize,
+    cancel: &CancelToken,
+) -> Result<String, AocError> {
+    check_floor(height, width)?;
+    easter_egg_step(robots, height, width, max_steps, cancel)?
+        .map(|step| step.to_string())
+        .ok_or_else(|| no_tree(max_steps))
+}
+
+/// Solves part 2 for the parsed robots like [`solve`], with [`easter_egg_step_crt`].
+pub fn solve_crt(
+    robots: &Robots,
+    height: u32,
+    width: u32,
+    max_steps: usize,
+) -> Result<String, AocError> {
+    check_floor(height, width)?;
+    easter_egg_step_crt(robots, height, width, max_steps)
+        .map(|step| step.to_string())
+        .ok_or_else(|| no_tree(max_steps))
+}
+
+fn no_tree(max_steps: usize) -> AocError {
+    AocError::no_solution(format!(
+        "The robots draw no Christmas tree within {max_steps} seconds"
+    ))
+}
+
+/// Like [`process`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+#[tracing::instrument(skip(cancel))]
+pub fn process_cancellable(
+    _input: &str,
+    height: u32,
+    width: u32,
+    max_steps: usize,
+    cancel: &CancelToken,
+) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?, height, width, max_steps, cancel)
+}
+
+/// The first second within `max_steps` after which the robots draw a Christmas tree, detected
+/// by a horizontal line of ten robots. Only the first [`period`] is searched, as the robots
+/// repeat their moves after it. Gives up with [`AocError::Timeout`] once `cancel` expires.
+pub fn easter_egg_step(
+    robots: &Robots,
+    height: u32,
+    width: u32,
+    max_steps: usize,
+    cancel: &CancelToken,
+) -> Result<Option<usize>, AocError> {
+    let mut floor = Floor::new(robots.clone(), height, width);
+    let max_steps = max_steps.min(period(height, width).period);
+
+    while floor.seconds < max_steps {
+        cancel.check()?;
+        floor.step();
 
-        let grid_visualization = visualize_grid(&grid, height, width);
-        if grid_visualization.contains("##########") {
-            println!("{}", grid_visualization);
-            println!("After {} steps", step);
-            return Ok(step.to_string());
+        if draw(&floor.robots, height, width).contains("##########") {
+            return Ok(Some(floor.seconds));
         }
     }
 
-    Ok("0".to_string())
+    Ok(None)
+}
+
+/// Like [`easter_egg_step`], but steps through only `width + height` seconds instead of up to
+/// `width × height`. The robots' columns repeat every `width` seconds and their rows every
+/// `height` seconds, so the second their columns bunch up most and the second their rows do
+/// give the tree's second modulo each, which the Chinese remainder theorem combines. That
+/// second still has to show the tree.
+pub fn easter_egg_step_crt(
+    robots: &Robots,
+    height: u32,
+    width: u32,
+    max_steps: usize,
+) -> Option<usize> {
+    let tightest =
+        |period: u32, coordinate: fn(IVec2) -> i32| {
+            // From the first second that is checked for the tree, so ties go to the earliest
+            (1..=period)
+                .min_by_key(|&seconds| {
+                    spread(robots.0.iter().map(|robot| {
+                        coordinate(robot.position_after(seconds as usize, height, width))
+                    }))
+                })
+                .unwrap_or_default()
+        };
+    let column_second = tightest(width, |position| position.x);
+    let row_second = tightest(height, |position| position.y);
+
+    let tree = aoc_math::crt([
+        Congruence::new(column_second.into(), width.into())?,
+        Congruence::new(row_second.into(), height.into())?,
+    ])?;
+    // The initial positions come back at the end of the period, which is the last second searched
+    let seconds = match tree.residue {
+        0 => tree.modulus,
+        residue => residue,
+    } as usize;
+
+    let mut floor = Floor::new(robots.clone(), height, width);
+    floor.fast_forward(seconds);
+    (seconds <= max_steps && draw(&floor.robots, height, width).contains("##########"))
+        .then_some(seconds)
+}
+
+/// How far the values are spread out, as their variance times their count squared.
+fn spread(values: impl Iterator<Item = i32>) -> i64 {
+    let (count, sum, sum_of_squares) = values.fold((0, 0, 0), |(count, sum, squares), value| {
+        let value = i64::from(value);
+        (count + 1, sum + value, squares + value * value)
+    });
+    count * sum_of_squares - sum * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Robot;
+    use aoc_common::assert_grid_eq;
+
+    const EXAMPLE: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+    fn after(robots: &Robots, steps: usize) -> Robots {
+        let mut floor = Floor::new(robots.clone(), 7, 11);
+        floor.run_until(|floor| floor.seconds == steps);
+        floor.robots
+    }
+
+    #[test]
+    fn test_draw() -> miette::Result<()> {
+        let robots = parse(EXAMPLE)?;
+        assert_grid_eq!(
+            "......#..#.
+...........
+#..........
+.##........
+.....#.....
+...##......
+.#....#....",
+            draw(&after(&robots, 100), 7, 11)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_draw_first_seconds() -> miette::Result<()> {
+        let robots = parse(EXAMPLE)?;
+        let frames = (0..=5)
+            .map(|steps| format!("{steps}s:\n{}", draw(&after(&robots, steps), 7, 11)))
+            .join("\n\n");
+        insta::assert_snapshot!(frames);
+        Ok(())
+    }
+
+    #[test]
+    fn test_easter_egg_step_crt() -> miette::Result<()> {
+        // Ten robots from all directions that form a line after 100 seconds
+        let (height, width) = (29, 31);
+        let robots = Robots(
+            (0..10)
+                .map(|i| {
+                    let velocity = IVec2::new(i * i + 1, i * i * i + 2);
+                    let backwards = Robot {
+                        position: IVec2::new(10 + i, 14),
+                        velocity: -velocity,
+                    };
+                    Robot {
+                        position: backwards.position_after(100, height, width),
+                        velocity,
+                    }
+                })
+                .collect(),
+        );
+
+        let cancel = CancelToken::new();
+        assert_eq!(
+            Some(100),
+            easter_egg_step(&robots, height, width, 1000, &cancel)?
+        );
+        assert_eq!(Some(100), easter_egg_step_crt(&robots, height, width, 1000));
+        assert_eq!(None, easter_egg_step_crt(&robots, height, width, 99));
+        assert_eq!(None, easter_egg_step_crt(&parse(EXAMPLE)?, 7, 11, 1000));
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_tree() -> miette::Result<()> {
+        let robots = parse(EXAMPLE)?;
+        let cancel = CancelToken::new();
+        assert!(matches!(
+            solve(&robots, 7, 11, 1000, &cancel),
+            Err(AocError::NoSolution(_))
+        ));
+        assert!(matches!(
+            solve_crt(&robots, 7, 11, 1000),
+            Err(AocError::NoSolution(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(matches!(
+            process_cancellable("p=0,0 v=1,1", 7, 11, 100, &cancel),
+            Err(AocError::Timeout(_))
+        ));
+    }
 }
@@ -1,4 +1,19 @@
+//! Day 15: Warehouse Woes.
+//!
+//! [`model::parse`] reads the warehouse and the robot's moves. [`part1::move_object`] and
+//! [`part2::move_object`] push boxes around the normal and the [widened](model::widen)
+//! warehouse, on which [`part1::gps_sum`] and [`part2::wide_gps_sum`] run all moves.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let warehouse = model::parse(input)?;
+    Ok((part1::solve(&warehouse)?, part2::solve(&warehouse)?))
+}
@@ -0,0 +1,216 @@
+use crate::custom_error::AocError;
+use glam::IVec2;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Object {
+    Robot,
+    Box,
+    Wall,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The warehouse map and the robot's attempted moves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Warehouse {
+    pub width: usize,
+    pub height: usize,
+    /// Everything but empty floor, in reading order
+    pub objects: Vec<(IVec2, Object)>,
+    /// The moves, split into lines the way the input wraps them
+    pub moves: Vec<Vec<Direction>>,
+}
+
+impl Object {
+    fn from_char(c: char) -> Option<Option<Object>> {
+        match c {
+            '.' => Some(None),
+            '@' => Some(Some(Object::Robot)),
+            'O' => Some(Some(Object::Box)),
+            '#' => Some(Some(Object::Wall)),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Object::Robot => '@',
+            Object::Box => 'O',
+            Object::Wall => '#',
+        }
+    }
+}
+
+impl Direction {
+    /// The step one move in this direction takes.
+    pub fn offset(&self) -> IVec2 {
+        match self {
+            Direction::Up => IVec2::new(0, -1),
+            Direction::Down => IVec2::new(0, 1),
+            Direction::Left => IVec2::new(-1, 0),
+            Direction::Right => IVec2::new(1, 0),
+        }
+    }
+
+    fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            '>' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Warehouse, AocError> {
+    let (input_grid, input_moves) = input
+        .split_once("\n\n")
+        .ok_or(AocError::malformed("Bad input format"))?;
+
+    let mut objects = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+    for (y, line) in input_grid.lines().enumerate() {
+        if *width.get_or_insert(line.len()) != line.len() {
+            return Err(AocError::malformed("Rows differ in length"));
+        }
+        for (x, c) in line.chars().enumerate() {
+            match Object::from_char(c) {
+                Some(Some(object)) => objects.push((IVec2::new(x as i32, y as i32), object)),
+                Some(None) => {}
+                None => return Err(AocError::malformed(format!("Invalid map tile: {c}"))),
+            }
+        }
+        height += 1;
+    }
+
+    let moves = input_moves
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| {
+                    Direction::from_char(c)
+                        .ok_or_else(|| AocError::malformed(format!("Invalid move: {c}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Warehouse {
+        width: width.unwrap_or_default(),
+        height,
+        objects,
+        moves,
+    })
+}
+
+impl fmt::Display for Warehouse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = vec![vec!['.'; self.width]; self.height];
+        for (position, object) in &self.objects {
+            grid[position.y as usize][position.x as usize] = object.to_char();
+        }
+        for row in grid {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+
+        writeln!(f)?;
+        for line in &self.moves {
+            writeln!(
+                f,
+                "{}",
+                line.iter().map(Direction::to_char).collect::<String>()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// One of the robot's moves, and whether it got anywhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RobotMove {
+    pub direction: Direction,
+    pub moved: bool,
+}
+
+/// An object in the widened warehouse, where everything but the robot is twice as wide.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum WideObject {
+    Robot,
+    BoxLeftside,
+    BoxRightside,
+    Wall,
+}
+
+/// The warehouse with everything but the robot twice as wide.
+pub fn widen(warehouse: &Warehouse) -> HashMap<IVec2, WideObject> {
+    warehouse
+        .objects
+        .iter()
+        .flat_map(|(position, object)| {
+            let left = IVec2::new(position.x * 2, position.y);
+            let right = left + IVec2::new(1, 0);
+            match object {
+                Object::Robot => vec![(left, WideObject::Robot)],
+                Object::Box => vec![
+                    (left, WideObject::BoxLeftside),
+                    (right, WideObject::BoxRightside),
+                ],
+                Object::Wall => vec![(left, WideObject::Wall), (right, WideObject::Wall)],
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let input = "########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv
+<v>>v<<
+";
+        let warehouse = parse(input)?;
+        assert_eq!((8, 8), (warehouse.width, warehouse.height));
+        assert_eq!(
+            Some(&(IVec2::new(2, 2), Object::Robot)),
+            warehouse
+                .objects
+                .iter()
+                .find(|(_, object)| *object == Object::Robot)
+        );
+        assert_eq!(15, warehouse.moves.concat().len());
+        assert_eq!(input, warehouse.to_string());
+        assert_eq!(warehouse, parse(&warehouse.to_string())?);
+        Ok(())
+    }
+}
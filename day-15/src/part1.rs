@@ -1,70 +1,13 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, Direction, Object, RobotMove, Warehouse};
+use aoc_common::simulation::Simulation;
 use glam::IVec2;
 use itertools::Itertools;
 use std::collections::HashMap;
-use std::io::Error;
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-enum Object {
-    Robot,
-    Box,
-    Wall,
-}
-
-#[derive(PartialEq, Eq, Clone, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-fn parse(input: &str) -> Result<(HashMap<IVec2, Object>, Vec<Direction>), AocError> {
-    let (input_grid, input_directions) = input
-        .split("\n\n")
-        .next_tuple()
-        .ok_or(Error::other("Bad input format"))?;
-
-    let grid = input_grid
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().filter_map(move |(x, c)| {
-                let coordinates = IVec2::new(x as i32, y as i32);
-                match c {
-                    'O' => Some((coordinates, Object::Box)),
-                    '#' => Some((coordinates, Object::Wall)),
-                    '@' => Some((coordinates, Object::Robot)),
-                    _ => None,
-                }
-            })
-        })
-        .collect();
-
-    let directions = input_directions
-        .chars()
-        .filter_map(|c| match c {
-            '^' => Some(Direction::Up),
-            'v' => Some(Direction::Down),
-            '<' => Some(Direction::Left),
-            '>' => Some(Direction::Right),
-            _ => None,
-        })
-        .collect();
-
-    Ok((grid, directions))
-}
-
-fn get_coordinates_from_direction(direction: &Direction) -> IVec2 {
-    match direction {
-        Direction::Up => IVec2::new(0, -1),
-        Direction::Down => IVec2::new(0, 1),
-        Direction::Left => IVec2::new(-1, 0),
-        Direction::Right => IVec2::new(1, 0),
-    }
-}
-
-fn move_object(
+/// Pushes the object at `coordinates` one step in `direction`, along with any boxes in its
+/// way. Returns whether it moved.
+pub fn move_object(
     grid: &mut HashMap<IVec2, Object>,
     coordinates: &IVec2,
     direction: &Direction,
@@ -74,17 +17,13 @@ fn move_object(
         None => return false,
     }
     .clone();
-    let new_coordinates = coordinates + get_coordinates_from_direction(direction);
-
-    match grid.get(&new_coordinates) {
-        Some(Object::Box) => {
-            if move_object(grid, &new_coordinates, direction) {
-                grid.insert(new_coordinates, object);
-                grid.remove(coordinates);
-                true
-            } else {
-                false
-            }
+    let new_coordinates = coordinates + direction.offset();
+
+    match grid.get(&new_coordinates).cloned() {
+        Some(Object::Box) if move_object(grid, &new_coordinates, direction) => {
+            grid.insert(new_coordinates, object);
+            grid.remove(coordinates);
+            true
         }
         None => {
             grid.insert(new_coordinates, object);
@@ -95,18 +34,19 @@ fn move_object(
     }
 }
 
-fn visualize_grid(grid: &HashMap<IVec2, Object>) -> Result<String, AocError> {
+/// Draws the grid the way the puzzle input does.
+pub fn visualize_grid(grid: &HashMap<IVec2, Object>) -> Result<String, AocError> {
     let height = grid
-        .iter()
-        .map(|(k, _)| k.y)
+        .keys()
+        .map(|k| k.y)
         .max()
-        .ok_or(Error::other("Empty grid"))?
+        .ok_or(AocError::malformed("Empty grid"))?
         + 1;
     let width = grid
-        .iter()
-        .map(|(k, _)| k.x)
+        .keys()
+        .map(|k| k.x)
         .max()
-        .ok_or(Error::other("Empty grid"))?
+        .ok_or(AocError::malformed("Empty grid"))?
         + 1;
 
     let string = (0..height as usize)
@@ -125,24 +65,13 @@ fn visualize_grid(grid: &HashMap<IVec2, Object>) -> Result<String, AocError> {
     Ok(string)
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (mut grid, directions) = parse(_input)?;
-    let mut robot_position = grid
-        .iter()
-        .find(|(_, v)| **v == Object::Robot)
-        .ok_or(Error::other("No robot on grid"))?
-        .0
-        .clone();
-
-    for direction in directions.iter() {
-        if move_object(&mut grid, &robot_position, direction) {
-            robot_position += get_coordinates_from_direction(direction);
-        }
-        //println!("{}", visualize_grid(&grid).unwrap_or("Invalid grid".to_string()));
-    }
+/// The sum of the boxes' GPS coordinates after the robot made all its moves.
+pub fn gps_sum(warehouse: &Warehouse) -> Result<i32, AocError> {
+    let mut run = RobotRun::new(warehouse)?;
+    while run.step().is_some() {}
 
-    let gps_sum = grid
+    Ok(run
+        .grid
         .iter()
         .filter_map(|(k, v)| {
             if *v == Object::Box {
@@ -151,18 +80,79 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
                 None
             }
         })
-        .sum::<i32>();
+        .sum::<i32>())
+}
+
+/// The robot making its moves one at a time in the normal warehouse.
+#[derive(Debug, Clone)]
+pub struct RobotRun {
+    pub grid: HashMap<IVec2, Object>,
+    pub robot_position: IVec2,
+    moves: Vec<Direction>,
+    /// Index of the move the robot makes next
+    pub next_move: usize,
+}
+
+impl RobotRun {
+    pub fn new(warehouse: &Warehouse) -> Result<Self, AocError> {
+        let grid: HashMap<IVec2, Object> = warehouse.objects.iter().cloned().collect();
+        let robot_position = *grid
+            .iter()
+            .find(|(_, v)| **v == Object::Robot)
+            .ok_or(AocError::malformed("No robot on grid"))?
+            .0;
+
+        Ok(RobotRun {
+            grid,
+            robot_position,
+            moves: warehouse.moves.concat(),
+            next_move: 0,
+        })
+    }
+}
+
+impl Simulation for RobotRun {
+    type Snapshot = (HashMap<IVec2, Object>, IVec2, usize);
+    type Event = RobotMove;
+
+    fn step(&mut self) -> Option<RobotMove> {
+        let direction = self.moves.get(self.next_move)?.clone();
+        self.next_move += 1;
+
+        let moved = move_object(&mut self.grid, &self.robot_position, &direction);
+        if moved {
+            self.robot_position += direction.offset();
+        }
+        Some(RobotMove { direction, moved })
+    }
 
-    Ok(gps_sum.to_string())
+    fn snapshot(&self) -> Self::Snapshot {
+        (self.grid.clone(), self.robot_position, self.next_move)
+    }
+
+    fn restore(&mut self, (grid, robot_position, next_move): Self::Snapshot) {
+        self.grid = grid;
+        self.robot_position = robot_position;
+        self.next_move = next_move;
+    }
+}
+
+/// Solves part 1 for the parsed warehouse.
+pub fn solve(warehouse: &Warehouse) -> Result<String, AocError> {
+    Ok(gps_sum(warehouse)?.to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::assert_grid_eq;
 
-    #[test]
-    fn test_process_1() -> miette::Result<()> {
-        let input = "########
+    const SMALL_EXAMPLE: &str = "########
 #..O.O.#
 ##@.O..#
 #...O..#
@@ -172,7 +162,39 @@ mod tests {
 ########
 
 <^^>>>vv<v>>v<<";
-        assert_eq!("2028", process(input)?);
+
+    #[test]
+    fn test_moves() -> miette::Result<()> {
+        let warehouse = parse(SMALL_EXAMPLE)?;
+        let mut run = RobotRun::new(&warehouse)?;
+
+        let mut frames = vec![format!("Initial state:\n{}", visualize_grid(&run.grid)?)];
+        while let Some(RobotMove { direction, .. }) = run.step() {
+            frames.push(format!(
+                "Move {direction:?}:\n{}",
+                visualize_grid(&run.grid)?
+            ));
+        }
+        let frames = frames.join("\n\n");
+        insta::assert_snapshot!(frames);
+
+        assert_grid_eq!(
+            "########
+#....OO#
+##.....#
+#.....O#
+#.#O@..#
+#...O..#
+#...O..#
+########",
+            visualize_grid(&run.grid)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_1() -> miette::Result<()> {
+        assert_eq!("2028", process(SMALL_EXAMPLE)?);
         Ok(())
     }
 
@@ -1,91 +1,27 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, widen, Direction, RobotMove, Warehouse, WideObject};
+use aoc_common::simulation::Simulation;
 use glam::IVec2;
 use itertools::Itertools;
 use std::collections::HashMap;
-use std::io::Error;
-
-#[derive(PartialEq, Eq, Clone, Debug)]
-enum Object {
-    Robot,
-    BoxLeftside,
-    BoxRightside,
-    Wall,
-}
-
-#[derive(PartialEq, Eq, Clone, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-fn parse(input: &str) -> Result<(HashMap<IVec2, Object>, Vec<Direction>), AocError> {
-    let (input_grid, input_directions) = input
-        .split("\n\n")
-        .next_tuple()
-        .ok_or(Error::other("Bad input format"))?;
-
-    let grid = input_grid
-        .replace("#", "##")
-        .replace("O", "[]")
-        .replace(".", "..")
-        .replace("@", "@.")
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().filter_map(move |(x, c)| {
-                let coordinates = IVec2::new(x as i32, y as i32);
-                match c {
-                    '[' => Some((coordinates, Object::BoxLeftside)),
-                    ']' => Some((coordinates, Object::BoxRightside)),
-                    '#' => Some((coordinates, Object::Wall)),
-                    '@' => Some((coordinates, Object::Robot)),
-                    _ => None,
-                }
-            })
-        })
-        .collect();
-
-    let directions = input_directions
-        .chars()
-        .filter_map(|c| match c {
-            '^' => Some(Direction::Up),
-            'v' => Some(Direction::Down),
-            '<' => Some(Direction::Left),
-            '>' => Some(Direction::Right),
-            _ => None,
-        })
-        .collect();
-
-    Ok((grid, directions))
-}
-
-fn get_coordinates_from_direction(direction: &Direction) -> IVec2 {
-    match direction {
-        Direction::Up => IVec2::new(0, -1),
-        Direction::Down => IVec2::new(0, 1),
-        Direction::Left => IVec2::new(-1, 0),
-        Direction::Right => IVec2::new(1, 0),
-    }
-}
 
 fn can_move_object(
-    grid: &mut HashMap<IVec2, Object>,
+    grid: &HashMap<IVec2, WideObject>,
     coordinates: &IVec2,
     direction: &Direction,
 ) -> bool {
-    let new_coordinates = coordinates + get_coordinates_from_direction(direction);
+    let new_coordinates = coordinates + direction.offset();
 
     match grid.get(coordinates) {
-        Some(side @ (Object::BoxLeftside | Object::BoxRightside)) => {
+        Some(side @ (WideObject::BoxLeftside | WideObject::BoxRightside)) => {
             let new_coordinates_other_side = coordinates
-                + get_coordinates_from_direction(match side {
-                    Object::BoxLeftside => &Direction::Right,
-                    Object::BoxRightside => &Direction::Left,
+                + match side {
+                    WideObject::BoxLeftside => Direction::Right,
+                    WideObject::BoxRightside => Direction::Left,
                     _ => unreachable!(),
-                })
-                + get_coordinates_from_direction(direction);
+                }
+                .offset()
+                + direction.offset();
 
             match direction {
                 Direction::Up | Direction::Down => {
@@ -97,34 +33,36 @@ fn can_move_object(
                 }
             }
         }
-        Some(Object::Robot) => can_move_object(grid, &new_coordinates, direction),
-        Some(Object::Wall) => false,
+        Some(WideObject::Robot) => can_move_object(grid, &new_coordinates, direction),
+        Some(WideObject::Wall) => false,
         None => true,
     }
 }
 
-fn move_object(
-    grid: &mut HashMap<IVec2, Object>,
+/// Pushes the object at `coordinates` one step in `direction`, along with any boxes in its
+/// way, but only if all of them can move. Returns whether it moved.
+pub fn move_object(
+    grid: &mut HashMap<IVec2, WideObject>,
     coordinates: &IVec2,
     direction: &Direction,
 ) -> bool {
-    let new_coordinates = coordinates + get_coordinates_from_direction(direction);
+    let new_coordinates = coordinates + direction.offset();
 
-    if can_move_object(grid, &coordinates, direction) {
+    if can_move_object(grid, coordinates, direction) {
         match grid.get(coordinates) {
-            Some(side @ (Object::BoxLeftside | Object::BoxRightside)) => {
+            Some(side @ (WideObject::BoxLeftside | WideObject::BoxRightside)) => {
                 let coordinates_other_side = coordinates
-                    + get_coordinates_from_direction(match side {
-                        Object::BoxLeftside => &Direction::Right,
-                        Object::BoxRightside => &Direction::Left,
+                    + match side {
+                        WideObject::BoxLeftside => Direction::Right,
+                        WideObject::BoxRightside => Direction::Left,
                         _ => unreachable!(),
-                    });
-                let new_coordinates_other_side =
-                    coordinates_other_side + get_coordinates_from_direction(direction);
+                    }
+                    .offset();
+                let new_coordinates_other_side = coordinates_other_side + direction.offset();
 
                 match direction {
                     Direction::Up | Direction::Down => {
-                        if grid.get(&new_coordinates) == grid.get(&coordinates) {
+                        if grid.get(&new_coordinates) == grid.get(coordinates) {
                             // Box is aligned
                             move_object(grid, &new_coordinates, direction);
                         } else {
@@ -137,18 +75,18 @@ fn move_object(
                     }
                 }
 
-                let object = grid.get(&coordinates).unwrap().clone();
-                let object_other_side = grid.get(&coordinates_other_side).unwrap().clone();
+                let object = grid[coordinates].clone();
+                let object_other_side = grid[&coordinates_other_side].clone();
                 grid.remove(&coordinates_other_side);
                 grid.insert(new_coordinates, object);
                 grid.insert(new_coordinates_other_side, object_other_side);
             }
-            Some(Object::Robot) => {
+            Some(WideObject::Robot) => {
                 move_object(grid, &new_coordinates, direction);
-                grid.remove(&coordinates);
-                grid.insert(new_coordinates, Object::Robot);
+                grid.remove(coordinates);
+                grid.insert(new_coordinates, WideObject::Robot);
             }
-            Some(Object::Wall) => {}
+            Some(WideObject::Wall) => {}
             None => {}
         }
         true
@@ -157,28 +95,29 @@ fn move_object(
     }
 }
 
-fn visualize_grid(grid: &HashMap<IVec2, Object>) -> Result<String, AocError> {
+/// Draws the widened grid the way the puzzle input does.
+pub fn visualize_grid(grid: &HashMap<IVec2, WideObject>) -> Result<String, AocError> {
     let height = grid
-        .iter()
-        .map(|(k, _)| k.y)
+        .keys()
+        .map(|k| k.y)
         .max()
-        .ok_or(Error::other("Empty grid"))?
+        .ok_or(AocError::malformed("Empty grid"))?
         + 1;
     let width = grid
-        .iter()
-        .map(|(k, _)| k.x)
+        .keys()
+        .map(|k| k.x)
         .max()
-        .ok_or(Error::other("Empty grid"))?
+        .ok_or(AocError::malformed("Empty grid"))?
         + 1;
 
     let string = (0..height as usize)
         .map(|y| {
             (0..width as usize)
                 .map(move |x| match grid.get(&IVec2::new(x as i32, y as i32)) {
-                    Some(Object::BoxLeftside) => '[',
-                    Some(Object::BoxRightside) => ']',
-                    Some(Object::Wall) => '#',
-                    Some(Object::Robot) => '@',
+                    Some(WideObject::BoxLeftside) => '[',
+                    Some(WideObject::BoxRightside) => ']',
+                    Some(WideObject::Wall) => '#',
+                    Some(WideObject::Robot) => '@',
                     _ => '.',
                 })
                 .join("")
@@ -188,43 +127,131 @@ fn visualize_grid(grid: &HashMap<IVec2, Object>) -> Result<String, AocError> {
     Ok(string)
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (mut grid, directions) = parse(_input)?;
-    let mut robot_position = grid
-        .iter()
-        .find(|(_, v)| **v == Object::Robot)
-        .ok_or(Error::other("No robot on grid"))?
-        .0
-        .clone();
-
-    for direction in directions.iter() {
-        if move_object(&mut grid, &robot_position, direction) {
-            robot_position += get_coordinates_from_direction(direction);
-        }
-        //println!(
-        //    "{}",
-        //    visualize_grid(&grid).unwrap_or("Invalid grid".to_string())
-        //);
-    }
+/// The sum of the boxes' GPS coordinates in the widened warehouse after the robot made all
+/// its moves.
+pub fn wide_gps_sum(warehouse: &Warehouse) -> Result<i32, AocError> {
+    let mut run = WideRobotRun::new(warehouse)?;
+    while run.step().is_some() {}
 
-    let gps_sum = grid
+    Ok(run
+        .grid
         .iter()
         .filter_map(|(k, v)| {
-            if *v == Object::BoxLeftside {
+            if *v == WideObject::BoxLeftside {
                 Some(100 * k.y + k.x)
             } else {
                 None
             }
         })
-        .sum::<i32>();
+        .sum::<i32>())
+}
+
+/// The robot making its moves one at a time in the widened warehouse.
+#[derive(Debug, Clone)]
+pub struct WideRobotRun {
+    pub grid: HashMap<IVec2, WideObject>,
+    pub robot_position: IVec2,
+    moves: Vec<Direction>,
+    /// Index of the move the robot makes next
+    pub next_move: usize,
+}
+
+impl WideRobotRun {
+    pub fn new(warehouse: &Warehouse) -> Result<Self, AocError> {
+        let grid: HashMap<IVec2, WideObject> = widen(warehouse);
+        let robot_position = *grid
+            .iter()
+            .find(|(_, v)| **v == WideObject::Robot)
+            .ok_or(AocError::malformed("No robot on grid"))?
+            .0;
+
+        Ok(WideRobotRun {
+            grid,
+            robot_position,
+            moves: warehouse.moves.concat(),
+            next_move: 0,
+        })
+    }
+}
+
+impl Simulation for WideRobotRun {
+    type Snapshot = (HashMap<IVec2, WideObject>, IVec2, usize);
+    type Event = RobotMove;
+
+    fn step(&mut self) -> Option<RobotMove> {
+        let direction = self.moves.get(self.next_move)?.clone();
+        self.next_move += 1;
+
+        let moved = move_object(&mut self.grid, &self.robot_position, &direction);
+        if moved {
+            self.robot_position += direction.offset();
+        }
+        Some(RobotMove { direction, moved })
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        (self.grid.clone(), self.robot_position, self.next_move)
+    }
+
+    fn restore(&mut self, (grid, robot_position, next_move): Self::Snapshot) {
+        self.grid = grid;
+        self.robot_position = robot_position;
+        self.next_move = next_move;
+    }
+}
 
-    Ok(gps_sum.to_string())
+/// Solves part 2 for the parsed warehouse.
+pub fn solve(warehouse: &Warehouse) -> Result<String, AocError> {
+    Ok(wide_gps_sum(warehouse)?.to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::assert_grid_eq;
+
+    #[test]
+    fn test_moves() -> miette::Result<()> {
+        let warehouse = parse(
+            "#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^",
+        )?;
+        let mut run = WideRobotRun::new(&warehouse)?;
+
+        let mut frames = vec![format!("Initial state:\n{}", visualize_grid(&run.grid)?)];
+        while let Some(RobotMove { direction, .. }) = run.step() {
+            frames.push(format!(
+                "Move {direction:?}:\n{}",
+                visualize_grid(&run.grid)?
+            ));
+        }
+        let frames = frames.join("\n\n");
+        insta::assert_snapshot!(frames);
+
+        assert_grid_eq!(
+            "##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############",
+            visualize_grid(&run.grid)?
+        );
+        Ok(())
+    }
 
     #[test]
     fn test_process() -> miette::Result<()> {
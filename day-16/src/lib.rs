@@ -1,4 +1,19 @@
+//! Day 16: Reindeer Maze.
+//!
+//! [`model::parse`] reads the maze and [`maze::Maze`] defines the moves a reindeer can make
+//! in it, which [`part1::lowest_score`] and [`part2::best_path_tiles`] search.
+
 pub mod custom_error;
+pub mod maze;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let maze = model::parse(input)?;
+    Ok((part1::solve(&maze)?, part2::solve(&maze)?))
+}
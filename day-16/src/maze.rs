@@ -0,0 +1,85 @@
+use crate::model::ReindeerMaze;
+use glam::IVec2;
+use std::collections::HashSet;
+
+/// Cost of taking a step forward
+pub const STEP_COST: usize = 1;
+/// Cost of turning to face another direction
+pub const TURN_COST: usize = 1000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+pub const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
+
+impl From<Direction> for IVec2 {
+    fn from(dir: Direction) -> IVec2 {
+        match dir {
+            Direction::North => IVec2::new(0, -1),
+            Direction::South => IVec2::new(0, 1),
+            Direction::West => IVec2::new(-1, 0),
+            Direction::East => IVec2::new(1, 0),
+        }
+    }
+}
+
+/// A reindeer's position and the direction it faces.
+pub type State = (IVec2, Direction);
+
+/// The maze with its walls in a set, for searching paths through it.
+#[derive(Debug, Clone)]
+pub struct Maze {
+    pub walls: HashSet<IVec2>,
+    pub start: IVec2,
+    pub end: IVec2,
+}
+
+impl From<&ReindeerMaze> for Maze {
+    fn from(maze: &ReindeerMaze) -> Self {
+        Maze {
+            walls: maze.walls.iter().copied().collect(),
+            start: maze.start,
+            end: maze.end,
+        }
+    }
+}
+
+impl Maze {
+    /// The reindeer starts on the start tile facing east.
+    pub fn start_state(&self) -> State {
+        (self.start, Direction::East)
+    }
+
+    pub fn is_end(&self, (pos, _dir): &State) -> bool {
+        *pos == self.end
+    }
+
+    /// The states reachable from `state` in a single move, with their cost.
+    pub fn successors(&self, (pos, dir): &State) -> Vec<(State, usize)> {
+        // Rotate
+        let mut successors: Vec<(State, usize)> = DIRECTIONS
+            .iter()
+            .copied()
+            .filter(|new_dir| new_dir != dir)
+            .map(|new_dir| ((*pos, new_dir), TURN_COST))
+            .collect();
+
+        // Take step
+        let step = pos + IVec2::from(*dir);
+        if !self.walls.contains(&step) {
+            successors.push(((step, *dir), STEP_COST));
+        }
+
+        successors
+    }
+}
@@ -0,0 +1,84 @@
+use crate::custom_error::AocError;
+use glam::IVec2;
+use serde::Serialize;
+use std::fmt;
+
+/// The reindeer maze, with the walls in reading order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReindeerMaze {
+    pub width: usize,
+    pub height: usize,
+    pub walls: Vec<IVec2>,
+    pub start: IVec2,
+    pub end: IVec2,
+}
+
+pub fn parse(input: &str) -> Result<ReindeerMaze, AocError> {
+    let mut walls = Vec::new();
+    let mut start: Option<IVec2> = None;
+    let mut end: Option<IVec2> = None;
+    let mut width = None;
+    let mut height = 0;
+
+    for (y, line) in input.lines().enumerate() {
+        if *width.get_or_insert(line.len()) != line.len() {
+            return Err(AocError::malformed("Rows differ in length"));
+        }
+        for (x, c) in line.chars().enumerate() {
+            let position = IVec2::new(x as i32, y as i32);
+            match c {
+                '.' => {}
+                '#' => walls.push(position),
+                'S' => start = Some(position),
+                'E' => end = Some(position),
+                _ => return Err(AocError::malformed(format!("Invalid maze tile: {c}"))),
+            }
+        }
+        height += 1;
+    }
+
+    Ok(ReindeerMaze {
+        width: width.unwrap_or_default(),
+        height,
+        walls,
+        start: start.ok_or(AocError::malformed("No starting position"))?,
+        end: end.ok_or(AocError::malformed("No end position"))?,
+    })
+}
+
+impl fmt::Display for ReindeerMaze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = vec![vec!['.'; self.width]; self.height];
+        for wall in &self.walls {
+            grid[wall.y as usize][wall.x as usize] = '#';
+        }
+        grid[self.start.y as usize][self.start.x as usize] = 'S';
+        grid[self.end.y as usize][self.end.x as usize] = 'E';
+
+        for row in grid {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let input = "#######
+#....E#
+#.#.#.#
+#S....#
+#######
+";
+        let maze = parse(input)?;
+        assert_eq!(IVec2::new(1, 3), maze.start);
+        assert_eq!(IVec2::new(5, 1), maze.end);
+        assert_eq!(input, maze.to_string());
+        assert_eq!(maze, parse(&maze.to_string())?);
+        Ok(())
+    }
+}
@@ -1,109 +1,38 @@
 use crate::custom_error::AocError;
+use crate::maze::Maze;
+use crate::model::{parse, ReindeerMaze};
 use glam::IVec2;
 use pathfinding::prelude::astar_bag;
 use std::collections::HashSet;
-use std::io::Error;
 
-struct Maze {
-    walls: HashSet<IVec2>,
-    start: IVec2,
-    end: IVec2,
-}
-
-#[derive(PartialEq, Eq, Clone, Copy, Hash)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
+/// The tiles that are part of at least one of the best paths through the maze, or `None` if
+/// there is no path.
+pub fn best_path_tiles(maze: &ReindeerMaze) -> Option<HashSet<IVec2>> {
+    let maze = Maze::from(maze);
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::South,
-    Direction::West,
-    Direction::East,
-];
+    let (paths, _score) = astar_bag(
+        &maze.start_state(),
+        |state| maze.successors(state),
+        |_| 0,
+        |state| maze.is_end(state),
+    )?;
 
-impl From<Direction> for IVec2 {
-    fn from(dir: Direction) -> IVec2 {
-        match dir {
-            Direction::North => IVec2::new(0, -1),
-            Direction::South => IVec2::new(0, 1),
-            Direction::West => IVec2::new(-1, 0),
-            Direction::East => IVec2::new(1, 0),
-        }
-    }
+    Some(paths.fold(HashSet::new(), |mut tiles, path| {
+        tiles.extend(path.iter().map(|(pos, _dir)| *pos));
+        tiles
+    }))
 }
 
-fn parse(input: &str) -> Result<Maze, AocError> {
-    let mut walls = HashSet::new();
-    let mut start: Option<IVec2> = None;
-    let mut end: Option<IVec2> = None;
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            match c {
-                '#' => {
-                    walls.insert(IVec2::new(x as i32, y as i32));
-                }
-                'S' => {
-                    start = Some(IVec2::new(x as i32, y as i32));
-                }
-                'E' => {
-                    end = Some(IVec2::new(x as i32, y as i32));
-                }
-                _ => {}
-            }
-        }
-    }
+/// Solves part 2 for the parsed maze.
+pub fn solve(maze: &ReindeerMaze) -> Result<String, AocError> {
+    let tiles = best_path_tiles(maze).ok_or(AocError::no_solution("No path found"))?;
 
-    Ok(Maze {
-        walls,
-        start: start.ok_or(Error::other("No starting position"))?,
-        end: end.ok_or(Error::other("No starting position"))?,
-    })
+    Ok(tiles.len().to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let maze = parse(_input)?;
-
-    let shortest_path = astar_bag(
-        &(maze.start, Direction::East),
-        |(pos, dir)| {
-            // Rotate
-            let mut rotations: Vec<((IVec2, Direction), usize)> = DIRECTIONS
-                .iter()
-                .copied()
-                .filter(|new_dir| new_dir != dir)
-                .map(|new_dir| ((pos.clone(), new_dir), 1000))
-                .collect();
-
-            // Take step
-            let step = pos + IVec2::from(*dir);
-            if !maze.walls.contains(&step) {
-                rotations.push(((step, *dir), 1));
-            }
-
-            rotations
-        },
-        |_| 0,
-        |(pos, _dir)| *pos == maze.end,
-    );
-
-    let paths = shortest_path.ok_or(Error::other("No path found"))?.0;
-
-    let spots = paths
-        .fold(HashSet::new(), |mut map, path| {
-            path.iter().for_each(|(pos, _dir)| {
-                map.insert(*pos);
-            });
-            map
-        })
-        .len();
-
-    Ok(spots.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -0,0 +1,212 @@
+use crate::custom_error::AocError;
+use crate::model::{Program, Registers};
+use aoc_common::simulation::Simulation;
+use std::ops::{BitXor, Shr};
+
+fn retrieve_combo_operand_value(operator: &u8, registers: &Registers) -> Result<u64, AocError> {
+    match operator {
+        i @ (0..=3) => Ok(*i as u64),
+        4 => Ok(registers.a),
+        5 => Ok(registers.b),
+        6 => Ok(registers.c),
+        _ => Err(AocError::malformed("Bad combo operator")),
+    }
+}
+
+/// Runs `program` with register A set to `a` and the other registers at their initial values,
+/// returning everything it outputs.
+pub fn execute_program(program: &Program, a: u64) -> Result<Vec<u8>, AocError> {
+    let mut computer = Computer::new(program, a);
+    while let Some(executed) = computer.step() {
+        executed?;
+    }
+
+    Ok(computer.output)
+}
+
+/// An instruction the computer executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Executed {
+    pub opcode: u8,
+    pub operand: u8,
+    /// The value an `out` instruction printed
+    pub output: Option<u8>,
+}
+
+/// The computer running a program one instruction per step. A step that fails halts it.
+#[derive(Debug, Clone)]
+pub struct Computer<'a> {
+    program: &'a Program,
+    pub registers: Registers,
+    pub instruction_pointer: usize,
+    pub output: Vec<u8>,
+    failed: bool,
+}
+
+impl<'a> Computer<'a> {
+    /// The computer about to run `program` with register A set to `a` and the other registers
+    /// at their initial values.
+    pub fn new(program: &'a Program, a: u64) -> Self {
+        Computer {
+            program,
+            registers: Registers {
+                a,
+                ..program.initial_registers.clone()
+            },
+            instruction_pointer: 0,
+            output: Vec::new(),
+            failed: false,
+        }
+    }
+
+    fn execute(&mut self, instruction: u8) -> Result<Executed, AocError> {
+        let operand = *self
+            .program
+            .instructions
+            .get(self.instruction_pointer + 1)
+            .ok_or(AocError::malformed("Operand missing"))?;
+        let registers = &mut self.registers;
+        let mut executed = Executed {
+            opcode: instruction,
+            operand,
+            output: None,
+        };
+        let mut next_instruction = self.instruction_pointer + 2;
+
+        match instruction {
+            0 => {
+                // adv
+                let numerator = registers.a;
+                let num_shifts = retrieve_combo_operand_value(&operand, registers)?;
+
+                registers.a = numerator.shr(num_shifts);
+            }
+            1 => {
+                // bxl
+                registers.b = registers.b.bitxor(operand as u64);
+            }
+            2 => {
+                // bst
+                registers.b = retrieve_combo_operand_value(&operand, registers)? % 8;
+            }
+            3 if registers.a != 0 => {
+                // jnz
+                next_instruction = operand as usize;
+            }
+            3 => {}
+            4 => {
+                // bxc
+                registers.b = registers.b.bitxor(registers.c);
+            }
+            5 => {
+                // out
+                let value = (retrieve_combo_operand_value(&operand, registers)? % 8) as u8;
+                self.output.push(value);
+                executed.output = Some(value);
+            }
+            6 => {
+                // bdv
+                let numerator = registers.a;
+                let num_shifts = retrieve_combo_operand_value(&operand, registers)?;
+
+                registers.b = numerator.shr(num_shifts);
+            }
+            7 => {
+                // cdv
+                let numerator = registers.a;
+                let num_shifts = retrieve_combo_operand_value(&operand, registers)?;
+
+                registers.c = numerator.shr(num_shifts);
+            }
+            _ => {} // Why?
+        }
+
+        self.instruction_pointer = next_instruction;
+        Ok(executed)
+    }
+}
+
+impl Simulation for Computer<'_> {
+    type Snapshot = (Registers, usize, Vec<u8>);
+    type Event = Result<Executed, AocError>;
+
+    fn step(&mut self) -> Option<Result<Executed, AocError>> {
+        if self.failed {
+            return None;
+        }
+        let instruction = *self.program.instructions.get(self.instruction_pointer)?;
+
+        let executed = self.execute(instruction);
+        self.failed = executed.is_err();
+        Some(executed)
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        (
+            self.registers.clone(),
+            self.instruction_pointer,
+            self.output.clone(),
+        )
+    }
+
+    fn restore(&mut self, (registers, instruction_pointer, output): Self::Snapshot) {
+        self.registers = registers;
+        self.instruction_pointer = instruction_pointer;
+        self.output = output;
+        self.failed = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::simulation::History;
+
+    #[test]
+    fn test_execute_program() -> miette::Result<()> {
+        let program = Program {
+            initial_registers: Registers { a: 0, b: 0, c: 0 },
+            instructions: vec![5, 0, 5, 1, 5, 4],
+        };
+        assert_eq!(vec![0, 1, 2], execute_program(&program, 10)?);
+
+        let program = Program {
+            initial_registers: Registers { a: 0, b: 0, c: 0 },
+            instructions: vec![0, 1, 5, 4, 3, 0],
+        };
+        assert_eq!(
+            vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0],
+            execute_program(&program, 2024)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_computer() -> miette::Result<()> {
+        let program = Program {
+            initial_registers: Registers { a: 0, b: 0, c: 9 },
+            instructions: vec![2, 6, 5, 5],
+        };
+        let mut outputs = Vec::new();
+        let mut history = History::new(Computer::new(&program, 0), 4)
+            .on_event(|step, executed| outputs.push((step, executed.as_ref().unwrap().output)));
+
+        assert_eq!(None, history.run_until(|_| false));
+        assert_eq!(1, history.simulation().registers.b);
+        assert_eq!(vec![1], history.simulation().output);
+
+        history.rewind(1);
+        assert!(history.simulation().output.is_empty());
+        drop(history);
+        assert_eq!(vec![(1, None), (2, Some(1))], outputs);
+
+        let broken = Program {
+            initial_registers: Registers { a: 0, b: 0, c: 0 },
+            instructions: vec![2, 7],
+        };
+        let mut computer = Computer::new(&broken, 0);
+        assert!(matches!(computer.step(), Some(Err(_))));
+        assert!(computer.step().is_none());
+        Ok(())
+    }
+}
@@ -1,4 +1,20 @@
+//! Day 17: Chronospatial Computer.
+//!
+//! [`model::parse`] reads the registers and program, which [`computer::execute_program`]
+//! runs. [`part1::output`] prints the program's output and [`part2::self_replicating_a`]
+//! searches for the value of register A that makes it output itself.
+
+pub mod computer;
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let program = model::parse(input)?;
+    Ok((part1::solve(&program)?, part2::solve(&program)?))
+}
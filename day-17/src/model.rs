@@ -0,0 +1,61 @@
+use crate::custom_error::AocError;
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::character::complete::newline;
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, terminated, tuple};
+use nom::Finish;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+/// The debugger's view: initial register values and the program as 3-bit numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Program {
+    pub initial_registers: Registers,
+    pub instructions: Vec<u8>,
+}
+
+pub fn parse(input: &str) -> Result<Program, AocError> {
+    let (_, parsed) = all_consuming(tuple((
+        terminated(preceded(tag("Register A: "), complete::u64), newline),
+        terminated(preceded(tag("Register B: "), complete::u64), newline),
+        terminated(preceded(tag("Register C: "), complete::u64), tag("\n\n")),
+        preceded(tag("Program: "), separated_list1(tag(","), complete::u8)),
+        opt(newline),
+    )))(input)
+    .finish()
+    .map_err(|err: nom::error::Error<&str>| {
+        AocError::malformed(format!("Invalid input at: {:.20}", err.input))
+    })?;
+
+    if let Some(number) = parsed.3.iter().find(|&&number| number > 7) {
+        return Err(AocError::malformed(format!("Not a 3-bit number: {number}")));
+    }
+
+    Ok(Program {
+        initial_registers: Registers {
+            a: parsed.0,
+            b: parsed.1,
+            c: parsed.2,
+        },
+        instructions: parsed.3,
+    })
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Registers { a, b, c } = self.initial_registers;
+        writeln!(f, "Register A: {a}\nRegister B: {b}\nRegister C: {c}\n")?;
+
+        let instructions: Vec<String> = self.instructions.iter().map(u8::to_string).collect();
+        writeln!(f, "Program: {}", instructions.join(","))
+    }
+}
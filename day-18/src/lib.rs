@@ -1,4 +1,26 @@
+//! Day 18: RAM Run.
+//!
+//! [`model::parse`] reads the falling bytes and [`model::FallingBytes::shortest_path`] finds
+//! the way to the exit once some have fallen, which [`part2::first_blocking_byte`] uses to
+//! find the byte that cuts it off.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(
+    input: &str,
+    size: usize,
+    num_bytes: usize,
+) -> miette::Result<(String, String), AocError> {
+    let bytes = model::parse(input)?;
+    Ok((
+        part1::solve(&bytes, size, num_bytes)?,
+        part2::solve(&bytes, size)?,
+    ))
+}
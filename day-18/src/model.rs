@@ -0,0 +1,131 @@
+use crate::custom_error::AocError;
+use glam::IVec2;
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::character::complete::newline;
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{separated_pair, terminated};
+use nom::{Finish, Parser};
+use pathfinding::prelude::dijkstra;
+use serde::Serialize;
+use std::fmt;
+
+/// The positions of the falling bytes, in the order they fall.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FallingBytes(pub Vec<IVec2>);
+
+const NEIGHBORS: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+];
+
+impl FallingBytes {
+    /// The fewest steps from the top left to the bottom right corner of a memory space of
+    /// `size` by `size` once the first `num_bytes` bytes have fallen, or `None` if they block
+    /// every path.
+    pub fn shortest_path(&self, size: usize, num_bytes: usize) -> Option<usize> {
+        self.path(size, num_bytes).map(|path| path.len() - 1)
+    }
+
+    /// The memory space the way the puzzle draws it, with the first `num_bytes` fallen bytes
+    /// as `#` and a shortest path, if there is one, as `O`.
+    pub fn draw(&self, size: usize, num_bytes: usize) -> String {
+        let fallen = &self.0[..num_bytes.min(self.0.len())];
+        let path = self.path(size, num_bytes).unwrap_or_default();
+
+        (0..size as i32)
+            .map(|y| {
+                (0..size as i32)
+                    .map(|x| {
+                        let coords = IVec2::new(x, y);
+                        if fallen.contains(&coords) {
+                            '#'
+                        } else if path.contains(&coords) {
+                            'O'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn path(&self, size: usize, num_bytes: usize) -> Option<Vec<IVec2>> {
+        let fallen = &self.0[..num_bytes.min(self.0.len())];
+        let in_bounds = |coords: &IVec2| {
+            (0..size as i32).contains(&coords.x) && (0..size as i32).contains(&coords.y)
+        };
+
+        dijkstra(
+            &IVec2::new(0, 0),
+            |coords| {
+                NEIGHBORS
+                    .iter()
+                    .map(|dir| coords + dir)
+                    .filter(|successor| in_bounds(successor) && !fallen.contains(successor))
+                    .map(|successor| (successor, 1))
+                    .collect::<Vec<(IVec2, usize)>>()
+            },
+            |IVec2 { x, y }| *x == size as i32 - 1 && *y == size as i32 - 1,
+        )
+        .map(|(path, _steps)| path)
+    }
+}
+
+/// Checks that a memory space of `size` by `size` has a corner to start from and that its
+/// coordinates fit an `i32`.
+pub fn check_size(size: usize) -> Result<(), AocError> {
+    if size == 0 {
+        return Err(AocError::invalid_parameter(
+            "The memory space is 0 by 0, it needs at least one position",
+        ));
+    }
+    if i32::try_from(size).is_err() {
+        return Err(AocError::invalid_parameter(format!(
+            "The memory space is {size} by {size}, which is too large"
+        )));
+    }
+    Ok(())
+}
+
+pub fn parse(input: &str) -> Result<FallingBytes, AocError> {
+    all_consuming(terminated(
+        separated_list1(
+            newline,
+            separated_pair(complete::i32, tag(","), complete::i32).map(|(x, y)| IVec2::new(x, y)),
+        ),
+        opt(newline),
+    ))(input)
+    .finish()
+    .map(|(_, bytes)| FallingBytes(bytes))
+    .map_err(|err: nom::error::Error<&str>| {
+        AocError::malformed(format!("Invalid input at: {:.20}", err.input))
+    })
+}
+
+impl fmt::Display for FallingBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|byte| writeln!(f, "{},{}", byte.x, byte.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw() -> miette::Result<()> {
+        let bytes = parse("1,0\n1,1\n1,2")?;
+        assert_eq!("O#.\nO#.\nOOO", bytes.draw(3, 2));
+        assert_eq!(Some(4), bytes.shortest_path(3, 2));
+        assert_eq!(".#.\n.#.\n.#.", bytes.draw(3, 3));
+        Ok(())
+    }
+}
@@ -1,86 +1,27 @@
 use crate::custom_error::AocError;
+use crate::model::{check_size, parse, FallingBytes};
 use glam::IVec2;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::multi::separated_list0;
-use nom::sequence::separated_pair;
-use pathfinding::prelude::dijkstra;
-use std::io::Error;
 
-#[derive(PartialEq, Eq, Clone, Copy, Hash)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
+/// The first byte that cuts off the exit of a memory space of `size` by `size`, or `None` if
+/// the exit stays reachable.
+pub fn first_blocking_byte(bytes: &FallingBytes, size: usize) -> Option<IVec2> {
+    (1..=bytes.0.len())
+        .find(|num_bytes| bytes.shortest_path(size, *num_bytes).is_none())
+        .map(|num_bytes| bytes.0[num_bytes - 1])
 }
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::South,
-    Direction::West,
-    Direction::East,
-];
+/// Solves part 2 for the parsed falling bytes.
+pub fn solve(bytes: &FallingBytes, size: usize) -> Result<String, AocError> {
+    check_size(size)?;
+    let cutting_byte =
+        first_blocking_byte(bytes, size).ok_or(AocError::no_solution("No byte blocks"))?;
 
-impl From<Direction> for IVec2 {
-    fn from(dir: Direction) -> IVec2 {
-        match dir {
-            Direction::North => IVec2::new(0, -1),
-            Direction::South => IVec2::new(0, 1),
-            Direction::West => IVec2::new(-1, 0),
-            Direction::East => IVec2::new(1, 0),
-        }
-    }
-}
-
-fn parse(input: &str) -> Result<Vec<IVec2>, AocError> {
-    let (_, result) = separated_list0(
-        tag("\n"),
-        separated_pair(
-            complete::i32::<&str, ()>,
-            tag(","),
-            complete::i32::<&str, ()>,
-        ),
-    )(input)
-    .map_err(|_| Error::other("Invalid input"))?;
-
-    Ok(result.iter().map(|(x, y)| IVec2::new(*x, *y)).collect())
+    Ok(format!("{},{}", cutting_byte.x, cutting_byte.y))
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str, size: usize) -> miette::Result<String, AocError> {
-    let byte_coordinates = parse(_input)?;
-
-    let (_position, cutting_byte) = byte_coordinates
-        .iter()
-        .enumerate()
-        .find(|(i, _coords)| {
-            let shortest_path = dijkstra(
-                &IVec2::new(0, 0),
-                |coords| {
-                    DIRECTIONS
-                        .iter()
-                        .filter_map(|dir| {
-                            let successor = coords + IVec2::from(*dir);
-                            if !byte_coordinates[..=*i].contains(&successor)
-                                && (0..size as i32).contains(&coords.x)
-                                && (0..size as i32).contains(&coords.y)
-                            {
-                                Some((successor, 1))
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<(IVec2, usize)>>()
-                },
-                |IVec2 { x, y }| *x == size as i32 - 1 && *y == size as i32 - 1,
-            );
-
-            shortest_path.is_none()
-        })
-        .ok_or(Error::other("No byte blocks"))?;
-
-    Ok(format!("{},{}", cutting_byte.x, cutting_byte.y))
+    solve(&parse(_input)?, size)
 }
 
 #[cfg(test)]
@@ -1,4 +1,21 @@
+//! Day 19: Linen Layout.
+//!
+//! [`model::parse`] reads the towel patterns and designs, which [`part1::possible_designs`]
+//! and [`part2::total_arrangements`] try to arrange.
+//!
+//! [`stream::solve_both`] arranges the designs as they are read, keeping only the patterns.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+pub mod stream;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let towels = model::parse(input)?;
+    Ok((part1::solve(&towels)?, part2::solve(&towels)?))
+}
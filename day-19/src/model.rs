@@ -0,0 +1,45 @@
+use crate::custom_error::AocError;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alphanumeric1, newline};
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{separated_pair, terminated};
+use nom::Finish;
+use serde::Serialize;
+use std::fmt;
+
+/// The available towel patterns and the designs to arrange from them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Towels {
+    pub patterns: Vec<String>,
+    pub designs: Vec<String>,
+}
+
+pub fn parse(input: &str) -> Result<Towels, AocError> {
+    let (_, (patterns, designs)) = all_consuming(terminated(
+        separated_pair(
+            separated_list1(tag(", "), alphanumeric1),
+            tag("\n\n"),
+            separated_list1(newline, alphanumeric1),
+        ),
+        opt(newline),
+    ))(input)
+    .finish()
+    .map_err(|err: nom::error::Error<&str>| {
+        AocError::malformed(format!("Invalid input at: {:.20}", err.input))
+    })?;
+
+    Ok(Towels {
+        patterns: patterns.into_iter().map(str::to_string).collect(),
+        designs: designs.into_iter().map(str::to_string).collect(),
+    })
+}
+
+impl fmt::Display for Towels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}\n", self.patterns.join(", "))?;
+        self.designs
+            .iter()
+            .try_for_each(|design| writeln!(f, "{design}"))
+    }
+}
@@ -1,50 +1,48 @@
 use crate::custom_error::AocError;
-use cached::proc_macro::cached;
-use cached::UnboundCache;
-use nom::bytes::complete::tag;
-use nom::character::complete::alphanumeric1;
-use nom::multi::separated_list0;
-use nom::sequence::separated_pair;
-use std::io::Error;
+use crate::model::{parse, Towels};
+use aoc_common::memo::Memo;
 
-fn parse(input: &str) -> Result<(Vec<&str>, Vec<&str>), AocError> {
-    let (_, result) = separated_pair(
-        separated_list0(tag(", "), alphanumeric1::<&str, ()>),
-        tag("\n\n"),
-        separated_list0(tag("\n"), alphanumeric1::<&str, ()>),
-    )(input)
-    .map_err(|_| Error::other("Invalid input"))?;
+/// Whether `design` can be made from the patterns, remembering in `memo` which of its endings
+/// can be made.
+pub fn match_design<'a>(
+    design: &'a str,
+    patterns: &[&str],
+    memo: &mut Memo<&'a str, bool>,
+) -> bool {
+    if design.is_empty() {
+        return true;
+    }
+    memo.get_or_compute(design, |memo| {
+        patterns
+            .iter()
+            .any(|&p| design.starts_with(p) && match_design(&design[p.len()..], patterns, memo))
+    })
+}
 
-    Ok((result.0, result.1))
+/// The number of designs that can be made from the available towel patterns.
+pub fn possible_designs(towels: &Towels) -> usize {
+    let patterns = towels
+        .patterns
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let mut memo = Memo::new();
+
+    towels
+        .designs
+        .iter()
+        .filter(|design| match_design(design, &patterns, &mut memo))
+        .count()
 }
 
-#[cached(
-    ty = "UnboundCache<String, bool>",
-    create = "{ UnboundCache::new() }",
-    convert = r#"{ format!("{}", design) }"#
-)]
-fn match_design(design: &str, patterns: &Vec<&str>) -> bool {
-    if design.is_empty() {
-        true
-    } else {
-        patterns.iter().any(|&p| {
-            design.len() >= p.len()
-                && (design[..p.len()] == *p)
-                && match_design(&design[p.len()..], patterns)
-        })
-    }
+/// Solves part 1 for the parsed towels.
+pub fn solve(towels: &Towels) -> Result<String, AocError> {
+    Ok(possible_designs(towels).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (patterns, designs) = parse(_input)?;
-
-    let result = designs
-        .into_iter()
-        .filter(|&design| match_design(design, &patterns))
-        .count();
-
-    Ok(result.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -66,4 +64,12 @@ bbrgwb";
         assert_eq!("6", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_inputs_in_sequence() -> miette::Result<()> {
+        assert_eq!("1", process("a, b\n\nab")?);
+        // The same design with other patterns can't be made
+        assert_eq!("0", process("a, c\n\nab")?);
+        Ok(())
+    }
 }
@@ -1,55 +1,51 @@
 use crate::custom_error::AocError;
-use cached::proc_macro::cached;
-use cached::UnboundCache;
-use nom::bytes::complete::tag;
-use nom::character::complete::alphanumeric1;
-use nom::multi::separated_list0;
-use nom::sequence::separated_pair;
-use std::io::Error;
+use crate::model::{parse, Towels};
+use aoc_common::memo::Memo;
 
-fn parse(input: &str) -> Result<(Vec<&str>, Vec<&str>), AocError> {
-    let (_, result) = separated_pair(
-        separated_list0(tag(", "), alphanumeric1::<&str, ()>),
-        tag("\n\n"),
-        separated_list0(tag("\n"), alphanumeric1::<&str, ()>),
-    )(input)
-    .map_err(|_| Error::other("Invalid input"))?;
-
-    Ok((result.0, result.1))
-}
-
-#[cached(
-    ty = "UnboundCache<String, u64>",
-    create = "{ UnboundCache::new() }",
-    convert = r#"{ format!("{}", design) }"#
-)]
-fn num_arrangements(design: &str, patterns: &Vec<&str>) -> u64 {
+/// The number of ways `design` can be made from the patterns, remembering the number for each
+/// of its endings in `memo`.
+pub fn num_arrangements<'a>(
+    design: &'a str,
+    patterns: &[&str],
+    memo: &mut Memo<&'a str, u64>,
+) -> u64 {
     if design.is_empty() {
-        1
-    } else {
+        return 1;
+    }
+    memo.get_or_compute(design, |memo| {
         patterns
             .iter()
-            .map(|&p| {
-                if design.len() >= p.len() && (design[..p.len()] == *p) {
-                    num_arrangements(&design[p.len()..], patterns)
-                } else {
-                    0
-                }
-            })
+            .filter(|&&p| design.starts_with(p))
+            .map(|&p| num_arrangements(&design[p.len()..], patterns, memo))
             .sum()
-    }
+    })
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (patterns, designs) = parse(_input)?;
+/// The number of different ways every design can be made from the available towel patterns,
+/// summed over all designs.
+pub fn total_arrangements(towels: &Towels) -> u64 {
+    let patterns = towels
+        .patterns
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let mut memo = Memo::new();
+
+    towels
+        .designs
+        .iter()
+        .map(|design| num_arrangements(design, &patterns, &mut memo))
+        .sum::<u64>()
+}
 
-    let result = designs
-        .into_iter()
-        .map(|design| num_arrangements(design, &patterns))
-        .sum::<u64>();
+/// Solves part 2 for the parsed towels.
+pub fn solve(towels: &Towels) -> Result<String, AocError> {
+    Ok(total_arrangements(towels).to_string())
+}
 
-    Ok(result.to_string())
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -71,4 +67,11 @@ bbrgwb";
         assert_eq!("16", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_inputs_in_sequence() -> miette::Result<()> {
+        assert_eq!("2", process("a, b, ab\n\nab")?);
+        assert_eq!("1", process("a, b\n\nab")?);
+        Ok(())
+    }
 }
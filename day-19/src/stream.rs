@@ -0,0 +1,79 @@
+use crate::custom_error::AocError;
+use crate::part1::match_design;
+use crate::part2::num_arrangements;
+use aoc_common::memo::Memo;
+use aoc_common::stream::for_each_line;
+use std::io::BufRead;
+
+/// Where in the input the next line is.
+enum Section {
+    Patterns,
+    Separator,
+    Designs,
+}
+
+fn is_towel(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Solves both parts a design at a time. Only the patterns are kept, and the memos last for a
+/// single design, so the memory grows with the longest design rather than with their number.
+pub fn solve_both(reader: impl BufRead) -> miette::Result<(String, String), AocError> {
+    let mut section = Section::Patterns;
+    let mut patterns = Vec::<String>::new();
+    let mut possible = 0_u64;
+    let mut arrangements = 0_u64;
+    let mut designs = 0_u64;
+    for_each_line(reader, |line| {
+        let malformed = || AocError::malformed(format!("Invalid input at: {line:.20}"));
+        match section {
+            Section::Patterns => {
+                patterns = line.split(", ").map(str::to_string).collect();
+                if !patterns.iter().all(|pattern| is_towel(pattern)) {
+                    return Err(malformed());
+                }
+                section = Section::Separator;
+            }
+            Section::Separator if line.is_empty() => section = Section::Designs,
+            Section::Separator => return Err(malformed()),
+            Section::Designs if is_towel(line) => {
+                let patterns = patterns.iter().map(String::as_str).collect::<Vec<_>>();
+                possible += u64::from(match_design(line, &patterns, &mut Memo::new()));
+                arrangements += num_arrangements(line, &patterns, &mut Memo::new());
+                designs += 1;
+            }
+            Section::Designs => return Err(malformed()),
+        }
+        Ok(())
+    })?;
+
+    if designs == 0 {
+        return Err(AocError::malformed("No designs after the towel patterns"));
+    }
+    Ok((possible.to_string(), arrangements.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_both() -> miette::Result<()> {
+        let input = "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+        assert_eq!(crate::solve_both(input)?, solve_both(input.as_bytes())?);
+        assert!(solve_both("r, wr\nbrwrr\n".as_bytes()).is_err());
+        assert!(solve_both("r, wr\n\n".as_bytes()).is_err());
+        assert!(solve_both("r, wr\n\nbr-wr\n".as_bytes()).is_err());
+        Ok(())
+    }
+}
@@ -1,4 +1,24 @@
+//! Day 20: Race Condition.
+//!
+//! [`model::parse`] reads the racetrack and [`model::RaceTrack::path`] finds the way through
+//! it, along which [`part1::count_cheats`] and [`part2::count_cheats`] look for shortcuts.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(
+    input: &str,
+    minimum_saving: usize,
+) -> miette::Result<(String, String), AocError> {
+    let track = model::parse(input)?;
+    Ok((
+        part1::solve(&track, minimum_saving)?,
+        part2::solve(&track, minimum_saving)?,
+    ))
+}
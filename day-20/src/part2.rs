@@ -1,95 +1,17 @@
 use crate::custom_error::AocError;
-use glam::IVec2;
-use pathfinding::prelude::dijkstra;
-use std::collections::HashSet;
-use std::io::Error;
+use crate::model::{parse, RaceTrack};
 
-struct Maze {
-    walls: HashSet<IVec2>,
-    start: IVec2,
-    end: IVec2,
-}
-
-#[derive(PartialEq, Eq, Clone, Copy, Hash)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
-const DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::South,
-    Direction::West,
-    Direction::East,
-];
-
-impl From<Direction> for IVec2 {
-    fn from(dir: Direction) -> IVec2 {
-        match dir {
-            Direction::North => IVec2::new(0, -1),
-            Direction::South => IVec2::new(0, 1),
-            Direction::West => IVec2::new(-1, 0),
-            Direction::East => IVec2::new(1, 0),
-        }
-    }
-}
-
-fn parse(input: &str) -> Result<Maze, AocError> {
-    let mut walls = HashSet::new();
-    let mut start: Option<IVec2> = None;
-    let mut end: Option<IVec2> = None;
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            match c {
-                '#' => {
-                    walls.insert(IVec2::new(x as i32, y as i32));
-                }
-                'S' => {
-                    start = Some(IVec2::new(x as i32, y as i32));
-                }
-                'E' => {
-                    end = Some(IVec2::new(x as i32, y as i32));
-                }
-                _ => {}
-            }
-        }
-    }
-
-    Ok(Maze {
-        walls,
-        start: start.ok_or(Error::other("No starting position"))?,
-        end: end.ok_or(Error::other("No starting position"))?,
-    })
-}
-
-#[tracing::instrument]
-pub fn process(_input: &str, minimum_saving: usize) -> miette::Result<String, AocError> {
-    let maze = parse(_input)?;
-
-    let normal_path = dijkstra(
-        &maze.start,
-        |pos| {
-            DIRECTIONS
-                .iter()
-                .map(|&dir| (pos + IVec2::from(dir), 1))
-                .filter(|(pos, _cost)| !maze.walls.contains(pos))
-                .collect::<Vec<(IVec2, usize)>>()
-        },
-        |pos| *pos == maze.end,
-    )
-    .ok_or(Error::other("No path found"))?;
-
-    let normal_time = normal_path.1;
+/// The number of cheats lasting up to 20 picoseconds that save at least `minimum_saving`
+/// picoseconds, or `None` if the end can't be reached at all.
+pub fn count_cheats(track: &RaceTrack, minimum_saving: usize) -> Option<usize> {
+    let normal_path = track.path()?;
+    let normal_time = normal_path.len() - 1;
 
     let cheated_path_times = normal_path
-        .0
         .iter()
         .enumerate()
         .flat_map(|(previous_steps, &pos)| {
-            normal_path.0.iter().enumerate().filter_map(
+            normal_path.iter().enumerate().filter_map(
                 move |(back_on_track_steps, back_on_track_pos)| {
                     let manhattan_distance = ((back_on_track_pos - pos).x.abs()
                         + (back_on_track_pos - pos).y.abs())
@@ -106,14 +28,27 @@ pub fn process(_input: &str, minimum_saving: usize) -> miette::Result<String, Ao
         })
         .collect::<Vec<usize>>();
 
-    let num_cheated_paths_with_minimum_savings = cheated_path_times
-        .iter()
-        .filter(|&&time| time <= normal_time - minimum_saving)
-        .count();
+    Some(
+        cheated_path_times
+            .iter()
+            .filter(|&&time| time + minimum_saving <= normal_time)
+            .count(),
+    )
+}
+
+/// Solves part 2 for the parsed racetrack.
+pub fn solve(track: &RaceTrack, minimum_saving: usize) -> Result<String, AocError> {
+    let num_cheated_paths_with_minimum_savings =
+        count_cheats(track, minimum_saving).ok_or(AocError::no_solution("No path found"))?;
 
     Ok(num_cheated_paths_with_minimum_savings.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(_input: &str, minimum_saving: usize) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?, minimum_saving)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,120 @@
+use glam::IVec2;
+use itertools::Itertools;
+use phf::{phf_map, Map};
+use std::iter;
+
+/// A keypad with the position of every button and of its gap, which a robot arm must never
+/// point at.
+pub struct Keypad {
+    pub name: &'static str,
+    pub buttons: &'static Map<char, IVec2>,
+    pub gap: IVec2,
+}
+
+static NUMERIC_BUTTONS: Map<char, IVec2> = phf_map! {
+    'A' => IVec2::new(2, 3),
+    '0' => IVec2::new(1, 3),
+    '1' => IVec2::new(0, 2),
+    '2' => IVec2::new(1, 2),
+    '3' => IVec2::new(2, 2),
+    '4' => IVec2::new(0, 1),
+    '5' => IVec2::new(1, 1),
+    '6' => IVec2::new(2, 1),
+    '7' => IVec2::new(0, 0),
+    '8' => IVec2::new(1, 0),
+    '9' => IVec2::new(2, 0),
+};
+
+static DIRECTIONAL_BUTTONS: Map<char, IVec2> = phf_map! {
+    '^' => IVec2::new(1, 0),
+    'v' => IVec2::new(1, 1),
+    '<' => IVec2::new(0, 1),
+    '>' => IVec2::new(2, 1),
+    'A' => IVec2::new(2, 0),
+};
+
+/// The keypad on the door
+pub static NUMERIC: Keypad = Keypad {
+    name: "numeric",
+    buttons: &NUMERIC_BUTTONS,
+    gap: IVec2::new(0, 3),
+};
+
+/// The keypad used to control a robot
+pub static DIRECTIONAL: Keypad = Keypad {
+    name: "directional",
+    buttons: &DIRECTIONAL_BUTTONS,
+    gap: IVec2::new(0, 0),
+};
+
+impl Keypad {
+    /// Every shortest sequence of directional buttons that moves an arm from `from` to `to`
+    /// and presses it, without passing over the gap.
+    ///
+    /// Panics if `from` or `to` is not a button on this keypad.
+    pub fn moves(&self, from: char, to: char) -> Vec<Vec<char>> {
+        let start_coords = self.buttons[&from];
+        let target_coords = self.buttons[&to];
+
+        let diff = target_coords - start_coords;
+
+        let horizontal_moves = if diff.x > 0 {
+            iter::repeat_n('>', diff.x as usize)
+        } else {
+            iter::repeat_n('<', -diff.x as usize)
+        };
+
+        let vertical_moves = if diff.y > 0 {
+            iter::repeat_n('v', diff.y as usize)
+        } else {
+            iter::repeat_n('^', -diff.y as usize)
+        };
+
+        horizontal_moves
+            .chain(vertical_moves)
+            .permutations((diff.x.abs() + diff.y.abs()) as usize)
+            .unique()
+            .filter(|candidate| {
+                let mut coords = start_coords;
+                for &c in candidate {
+                    coords += match c {
+                        '^' => IVec2::new(0, -1),
+                        'v' => IVec2::new(0, 1),
+                        '>' => IVec2::new(1, 0),
+                        '<' => IVec2::new(-1, 0),
+                        _ => IVec2::new(0, 0),
+                    };
+                    if coords == self.gap {
+                        return false;
+                    }
+                }
+                true
+            })
+            .map(|mut candidate| {
+                candidate.push('A');
+                candidate
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moves() {
+        assert_eq!(vec![vec!['<', 'A']], NUMERIC.moves('A', '0'));
+
+        // Going left first would pass over the gap
+        let moves = NUMERIC.moves('A', '7');
+        assert_eq!(9, moves.len());
+        assert!(moves.iter().all(|sequence| sequence.len() == 6));
+        assert!(!moves.contains(&vec!['<', '<', '^', '^', '^', 'A']));
+
+        assert_eq!(
+            vec![vec!['<', 'v', '<', 'A'], vec!['v', '<', '<', 'A']],
+            DIRECTIONAL.moves('A', '<')
+        );
+    }
+}
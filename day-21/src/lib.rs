@@ -1,4 +1,20 @@
+//! Day 21: Keypad Conundrum.
+//!
+//! [`model::parse`] reads the door codes and [`keypad`] describes the keypads the robots
+//! operate. [`part1::shortest_sequence`] finds the buttons to press for a code, and
+//! [`part1::complexity_sum`] and [`part2::complexity_sum`] add up the complexities.
+
 pub mod custom_error;
+pub mod keypad;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str, levels: u8) -> miette::Result<(String, String), AocError> {
+    let codes = model::parse(input)?;
+    Ok((part1::solve(&codes)?, part2::solve(&codes, levels)?))
+}
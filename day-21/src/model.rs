@@ -0,0 +1,33 @@
+use crate::custom_error::AocError;
+use serde::Serialize;
+use std::fmt;
+
+/// The codes to type on the door's numeric keypad, such as `029A`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoorCodes(pub Vec<String>);
+
+/// The numeric part of a code, ignoring leading zeroes.
+pub fn numeric_part(code: &str) -> Result<usize, AocError> {
+    code.trim_end_matches('A')
+        .parse()
+        .map_err(|_| AocError::malformed(format!("Invalid door code: {code}")))
+}
+
+pub fn parse(input: &str) -> Result<DoorCodes, AocError> {
+    input
+        .lines()
+        .map(|line| match line.strip_suffix('A') {
+            Some(digits) if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) => {
+                Ok(line.to_string())
+            }
+            _ => Err(AocError::malformed(format!("Invalid door code: {line}"))),
+        })
+        .collect::<Result<_, _>>()
+        .map(DoorCodes)
+}
+
+impl fmt::Display for DoorCodes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|code| writeln!(f, "{code}"))
+    }
+}
@@ -1,121 +1,113 @@
 use crate::custom_error::AocError;
-use cached::proc_macro::cached;
-use cached::UnboundCache;
-use glam::IVec2;
+use crate::keypad::{Keypad, DIRECTIONAL, NUMERIC};
+use crate::model::{numeric_part, parse, DoorCodes};
+use aoc_common::memo::Memo;
 use itertools::Itertools;
-use phf::{phf_map, Map};
-use std::io::Error;
 use std::iter;
-use std::num::ParseIntError;
 
-static DIGITMAP: Map<char, IVec2> = phf_map! {
-    'A' => IVec2::new(2, 3),
-    '0' => IVec2::new(1, 3),
-    '1' => IVec2::new(0, 2),
-    '2' => IVec2::new(1, 2),
-    '3' => IVec2::new(2, 2),
-    '4' => IVec2::new(0, 1),
-    '5' => IVec2::new(1, 1),
-    '6' => IVec2::new(2, 1),
-    '7' => IVec2::new(0, 0),
-    '8' => IVec2::new(1, 0),
-    '9' => IVec2::new(2, 0),
-};
+/// Moving an arm on a keypad from one button to another and pressing it, through `levels`
+/// keypads including that one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Press {
+    /// The [`Keypad::name`]
+    pub keypad: &'static str,
+    pub from: char,
+    pub to: char,
+    pub levels: u8,
+}
+
+/// The error for more presses than a `usize` counts, which a few dozen keypads already need.
+fn too_many_presses() -> AocError {
+    AocError::invalid_parameter("Typing through this many keypads takes too many presses to count")
+}
 
-static DIRMAP: Map<char, IVec2> = phf_map! {
-    '^' => IVec2::new(1, 0),
-    'v' => IVec2::new(1, 1),
-    '<' => IVec2::new(0, 1),
-    '>' => IVec2::new(2, 1),
-    'A' => IVec2::new(2, 0),
-};
+/// Adds up `lengths`, failing instead of overflowing.
+fn checked_sum(
+    mut lengths: impl Iterator<Item = Result<usize, AocError>>,
+) -> Result<usize, AocError> {
+    lengths.try_fold(0_usize, |total, length| {
+        total.checked_add(length?).ok_or_else(too_many_presses)
+    })
+}
 
-#[cached(
-    ty = "UnboundCache<String, usize>",
-    create = "{ UnboundCache::new() }",
-    convert = r#"{ format!("{}, {}, Level {}", from, to, levels) }"#
-)]
+/// The fewest buttons to press on the outermost keypad to make the press, remembering the
+/// counts for the presses it takes in `memo`.
 fn shortest_paths(
     from: char,
     to: char,
-    keymap: &Map<char, IVec2>,
-    forbidden: IVec2,
+    keypad: &Keypad,
     levels: u8,
-) -> usize {
+    memo: &mut Memo<Press, usize>,
+) -> Result<usize, AocError> {
     if levels == 0 {
-        return 1;
+        return Ok(1);
     }
+    let press = Press {
+        keypad: keypad.name,
+        from,
+        to,
+        levels,
+    };
 
-    let start_coords = keymap.get(&from).unwrap();
-    let target_coords = keymap.get(&to).unwrap();
+    memo.try_get_or_compute(press, |memo| {
+        keypad
+            .moves(from, to)
+            .into_iter()
+            .map(|candidate| {
+                let lengths = iter::once('A')
+                    .chain(candidate)
+                    .tuple_windows()
+                    .map(|(from, to)| shortest_paths(from, to, &DIRECTIONAL, levels - 1, memo));
+                checked_sum(lengths)
+            })
+            .process_results(|lengths| lengths.min())?
+            .ok_or_else(|| AocError::internal(format!("No moves from {from} to {to}")))
+    })
+}
 
-    let diff = target_coords - start_coords;
+/// The length of the shortest sequence of buttons to press to type `code`, through `levels`
+/// keypads including the numeric one.
+pub fn shortest_sequence_length(code: &str, levels: u8) -> Result<usize, AocError> {
+    sequence_length(code, levels, &mut Memo::new())
+}
 
-    let horizontal_moves = if diff.x > 0 {
-        iter::repeat_n('>', diff.x as usize)
-    } else {
-        iter::repeat_n('<', -diff.x as usize)
-    };
+fn sequence_length(
+    code: &str,
+    levels: u8,
+    memo: &mut Memo<Press, usize>,
+) -> Result<usize, AocError> {
+    let lengths = iter::once('A')
+        .chain(code.chars())
+        .tuple_windows()
+        .map(|(from, to)| shortest_paths(from, to, &NUMERIC, levels, memo));
+    checked_sum(lengths)
+}
 
-    let vertical_moves = if diff.y > 0 {
-        iter::repeat_n('v', diff.y as usize)
-    } else {
-        iter::repeat_n('^', -diff.y as usize)
-    };
+/// The sum of every code's complexity, the length of its shortest sequence times its numeric
+/// part, through `levels` keypads including the numeric one.
+pub fn complexity_sum(codes: &DoorCodes, levels: u8) -> Result<usize, AocError> {
+    if levels == 0 {
+        return Err(AocError::invalid_parameter(
+            "There are 0 keypads, the numeric one on the door counts as well",
+        ));
+    }
+    let mut memo = Memo::new();
+    let complexities = codes.0.iter().map(|code| {
+        sequence_length(code, levels, &mut memo)?
+            .checked_mul(numeric_part(code)?)
+            .ok_or_else(too_many_presses)
+    });
+    checked_sum(complexities)
+}
 
-    horizontal_moves
-        .chain(vertical_moves)
-        .permutations((diff.x.abs() + diff.y.abs()) as usize)
-        .unique()
-        .filter(|candidate| {
-            let mut coords = start_coords.clone();
-            for &c in candidate {
-                coords += match c {
-                    '^' => IVec2::new(0, -1),
-                    'v' => IVec2::new(0, 1),
-                    '>' => IVec2::new(1, 0),
-                    '<' => IVec2::new(-1, 0),
-                    _ => IVec2::new(0, 0),
-                };
-                if coords == forbidden {
-                    return false;
-                }
-            }
-            true
-        })
-        .map(|candidate| {
-            iter::once('A')
-                .chain(candidate)
-                .chain(iter::once('A'))
-                .tuple_windows()
-                .map(|(from, to)| shortest_paths(from, to, &DIRMAP, IVec2::new(0, 0), levels - 1))
-                .sum()
-        })
-        .min()
-        .unwrap()
+/// Solves part 2 for the parsed door codes.
+pub fn solve(codes: &DoorCodes, levels: u8) -> Result<String, AocError> {
+    Ok(complexity_sum(codes, levels)?.to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str, levels: u8) -> miette::Result<String, AocError> {
-    let results = _input
-        .lines()
-        .map(|line| {
-            let number: usize = line[..line.len() - 1].parse::<usize>()?;
-
-            let shortest_length = iter::once('A')
-                .chain(line.chars())
-                .tuple_windows()
-                .map(|(from, to)| shortest_paths(from, to, &DIGITMAP, IVec2::new(0, 3), levels))
-                .sum::<usize>();
-
-            Ok::<usize, ParseIntError>(shortest_length * number)
-        })
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|_| Error::other("Invalid input"))?;
-
-    let sum = results.iter().sum::<usize>();
-
-    Ok(sum.to_string())
+    solve(&parse(_input)?, levels)
 }
 
 #[cfg(test)]
@@ -133,4 +125,28 @@ mod tests {
         assert_eq!("126384", process(input, 3)?);
         Ok(())
     }
+
+    #[test]
+    fn test_levels_in_sequence() -> miette::Result<()> {
+        assert_eq!(68, shortest_sequence_length("029A", 3)?);
+        assert_eq!(12, shortest_sequence_length("029A", 1)?);
+        assert_eq!(28, shortest_sequence_length("029A", 2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_keypads() {
+        assert!(matches!(
+            process("029A\n", 0),
+            Err(AocError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_too_many_keypads() {
+        assert!(matches!(
+            process("029A\n", u8::MAX),
+            Err(AocError::InvalidParameter(_))
+        ));
+    }
 }
@@ -1,4 +1,23 @@
+//! Day 22: Monkey Market.
+//!
+//! [`model::parse`] reads the buyers' initial secret numbers and [`model::step`] generates the
+//! next one. [`part1::sum_of_secrets`] and [`part2::most_bananas`] are built on
+//! [`model::secrets`].
+//!
+//! [`stream::solve_both`] reads the buyers one at a time, adding up the bananas per sequence
+//! of price changes as it goes.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+pub mod stream;
+
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    let buyers = model::parse(input)?;
+    Ok((part1::solve(&buyers)?, part2::solve(&buyers)?))
+}
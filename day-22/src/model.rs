@@ -0,0 +1,75 @@
+use crate::custom_error::AocError;
+use aoc_common::cycle;
+use serde::Serialize;
+use std::fmt;
+use std::iter;
+use std::ops::{BitAnd, BitXor, Shl, Shr};
+
+/// The initial secret number of every buyer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Buyers(pub Vec<u64>);
+
+/// The next secret number a buyer generates after `n`.
+pub fn step(mut n: u64) -> u64 {
+    n = n.bitxor(n.shl(6));
+    n = n.bitand(0xffffff);
+    n = n.bitxor(n.shr(5));
+    n = n.bitand(0xffffff);
+    n = n.bitxor(n.shl(11));
+    n = n.bitand(0xffffff);
+    n
+}
+
+/// The endless sequence of secret numbers starting with `initial` itself.
+pub fn secrets(initial: u64) -> impl Iterator<Item = u64> {
+    iter::successors(Some(initial), |&n| Some(step(n)))
+}
+
+/// The `n`th new secret number after `initial`. The generator cycles, so `n` can be far larger
+/// than the number of distinct secrets.
+pub fn nth_secret(initial: u64, n: usize) -> u64 {
+    cycle::fast_forward(initial, |&n| Some(step(n)), n).expect("secret numbers never run out")
+}
+
+pub fn parse(input: &str) -> Result<Buyers, AocError> {
+    input
+        .lines()
+        .map(|line| {
+            line.parse()
+                .map_err(|_| AocError::malformed(format!("Invalid secret number: {line}")))
+        })
+        .collect::<Result<_, _>>()
+        .map(Buyers)
+}
+
+impl fmt::Display for Buyers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|secret| writeln!(f, "{secret}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets() {
+        assert_eq!(15887950, step(123));
+        assert_eq!(
+            vec![123, 15887950, 16495136, 527345],
+            secrets(123).take(4).collect::<Vec<_>>()
+        );
+        assert_eq!(secrets(123).nth(2000), Some(nth_secret(123, 2000)));
+    }
+
+    #[test]
+    fn test_nth_secret_cycle() {
+        let cycle = cycle::brent(123, |&n| Some(step(n))).unwrap();
+        assert_eq!(0, cycle.start);
+        assert_eq!(
+            nth_secret(123, 10),
+            nth_secret(123, cycle.period * 1_000_000 + 10)
+        );
+        assert_eq!(0, nth_secret(0, usize::MAX));
+    }
+}
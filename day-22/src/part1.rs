@@ -1,30 +1,23 @@
 use crate::custom_error::AocError;
-use std::ops::{BitAnd, BitXor, Shl, Shr};
+use crate::model::{nth_secret, parse, Buyers};
 
-fn step(mut n: u64) -> u64 {
-    n = n.bitxor(n.shl(6));
-    n = n.bitand(0xffffff);
-    n = n.bitxor(n.shr(5));
-    n = n.bitand(0xffffff);
-    n = n.bitxor(n.shl(11));
-    n = n.bitand(0xffffff);
-    n
+/// The sum of every buyer's 2000th new secret number.
+pub fn sum_of_secrets(buyers: &Buyers) -> u64 {
+    buyers
+        .0
+        .iter()
+        .map(|&number| nth_secret(number, 2000))
+        .sum()
+}
+
+/// Solves part 1 for the parsed buyers.
+pub fn solve(buyers: &Buyers) -> Result<String, AocError> {
+    Ok(sum_of_secrets(buyers).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let result = _input
-        .lines()
-        .map(|line| line.parse::<u64>().unwrap())
-        .map(|mut number| {
-            for _ in 0..2000 {
-                number = step(number);
-            }
-            number
-        })
-        .sum::<u64>();
-
-    Ok(result.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -1,32 +1,23 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, secrets, Buyers};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::iter;
-use std::ops::{BitAnd, BitXor, Shl, Shr};
 
-fn step(mut n: u64) -> u64 {
-    n = n.bitxor(n.shl(6));
-    n = n.bitand(0xffffff);
-    n = n.bitxor(n.shr(5));
-    n = n.bitand(0xffffff);
-    n = n.bitxor(n.shl(11));
-    n = n.bitand(0xffffff);
-    n
+/// The prices a buyer offers, the ones digit of the initial and the next 2000 secret numbers.
+pub fn prices(initial: u64) -> Vec<i8> {
+    secrets(initial)
+        .take(2001)
+        .map(|number| (number % 10) as i8)
+        .collect()
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let prices_all_monkeys = _input
-        .lines()
-        .map(|line| line.parse::<u64>().unwrap())
-        .map(|mut number| {
-            iter::once((number % 10) as i8)
-                .chain((0..2000).into_iter().map(|_| {
-                    number = step(number);
-                    (number % 10) as i8
-                }))
-                .collect()
-        })
+/// The most bananas to get by selling to every buyer at the first occurrence of the same
+/// sequence of four price changes.
+pub fn most_bananas(buyers: &Buyers) -> u64 {
+    let prices_all_monkeys = buyers
+        .0
+        .iter()
+        .map(|&number| prices(number))
         .collect::<Vec<Vec<i8>>>();
 
     let differences_all_monkeys = prices_all_monkeys
@@ -61,22 +52,30 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
         .flat_map(|differences_price_mappings| differences_price_mappings.keys().copied())
         .collect::<HashSet<(i8, i8, i8, i8)>>();
 
-    let result: u64 = all_possible_difference_sequences
+    all_possible_difference_sequences
         .iter()
         .map(|difference_sequence| {
             differences_price_mappings_all_monkeys
                 .iter()
                 .map(|differences_price_mappings| {
                     *differences_price_mappings
-                        .get(&difference_sequence)
+                        .get(difference_sequence)
                         .unwrap_or(&0) as u64
                 })
                 .sum()
         })
         .max()
-        .unwrap();
+        .unwrap_or_default()
+}
 
-    Ok(result.to_string())
+/// Solves part 2 for the parsed buyers.
+pub fn solve(buyers: &Buyers) -> Result<String, AocError> {
+    Ok(most_bananas(buyers).to_string())
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
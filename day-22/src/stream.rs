@@ -0,0 +1,79 @@
+use crate::custom_error::AocError;
+use crate::model::{nth_secret, secrets};
+use aoc_common::stream::for_each_line;
+use std::io::BufRead;
+
+/// The number of different sequences of four price changes, each change being one of -9..=9.
+const SEQUENCES: usize = 19 * 19 * 19 * 19;
+
+/// The bananas each sequence of price changes would get, summed over the buyers so far.
+struct Market {
+    bananas: Vec<u64>,
+    /// The last buyer, counting from one, that offered each sequence
+    seen_by: Vec<u64>,
+    buyers: u64,
+}
+
+impl Market {
+    fn new() -> Self {
+        Market {
+            bananas: vec![0; SEQUENCES],
+            seen_by: vec![0; SEQUENCES],
+            buyers: 0,
+        }
+    }
+
+    /// Adds what a buyer offers at the first occurrence of each sequence, like
+    /// [`crate::part2::most_bananas`] does with the buyer's prices.
+    fn add_buyer(&mut self, initial: u64) {
+        self.buyers += 1;
+        let mut prices = secrets(initial)
+            .take(2001)
+            .map(|number| (number % 10) as usize);
+        let mut previous = prices.next().expect("the initial secret has a price");
+        let mut sequence = 0;
+        for (i, price) in prices.enumerate() {
+            sequence = (sequence * 19 + price + 9 - previous) % SEQUENCES;
+            previous = price;
+            if i >= 3 && self.seen_by[sequence] != self.buyers {
+                self.seen_by[sequence] = self.buyers;
+                self.bananas[sequence] += price as u64;
+            }
+        }
+    }
+
+    fn most_bananas(&self) -> u64 {
+        self.bananas.iter().copied().max().unwrap_or_default()
+    }
+}
+
+/// Solves both parts a buyer at a time. Part 2 keeps the bananas for every possible sequence of
+/// price changes, a fixed amount of memory however many buyers there are.
+pub fn solve_both(reader: impl BufRead) -> miette::Result<(String, String), AocError> {
+    let mut sum = 0_u64;
+    let mut market = Market::new();
+    for_each_line(reader, |line| {
+        let initial = line
+            .parse()
+            .map_err(|_| AocError::malformed(format!("Invalid secret number: {line}")))?;
+        sum += nth_secret(initial, 2000);
+        market.add_buyer(initial);
+        Ok::<_, AocError>(())
+    })?;
+
+    Ok((sum.to_string(), market.most_bananas().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_both() -> miette::Result<()> {
+        for input in ["1\n10\n100\n2024\n", "1\n2\n3\n2024\n"] {
+            assert_eq!(crate::solve_both(input)?, solve_both(input.as_bytes())?);
+        }
+        assert!(solve_both("1\n1O\n".as_bytes()).is_err());
+        Ok(())
+    }
+}
@@ -1,4 +1,31 @@
+//! Day 23: LAN Party.
+//!
+//! [`model::parse`] reads the connections and [`model::Network::to_graph`] turns them into a
+//! graph of the LAN, in which [`part1::count_t_triangles`] and [`part2::largest_clique`]
+//! look for sets of interconnected computers.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
+
+use aoc_common::cancel::CancelToken;
+use custom_error::AocError;
+
+/// Solves both parts, parsing the input only once.
+pub fn solve_both(input: &str) -> miette::Result<(String, String), AocError> {
+    solve_both_cancellable(input, &CancelToken::new())
+}
+
+/// Like [`solve_both`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+pub fn solve_both_cancellable(
+    input: &str,
+    cancel: &CancelToken,
+) -> miette::Result<(String, String), AocError> {
+    let network = model::parse(input)?;
+    Ok((
+        part1::solve(&network, cancel)?,
+        part2::solve(&network, cancel)?,
+    ))
+}
@@ -0,0 +1,61 @@
+use crate::custom_error::AocError;
+use petgraph::{Graph, Undirected};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The network map, one connection between two computers per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Network<'a> {
+    pub connections: Vec<(&'a str, &'a str)>,
+}
+
+/// The LAN with one node per computer and one edge per connection.
+pub type LanGraph<'a> = Graph<&'a str, (), Undirected>;
+
+impl<'a> Network<'a> {
+    pub fn to_graph(&self) -> LanGraph<'a> {
+        let computers = self
+            .connections
+            .iter()
+            .flat_map(|&(computer1, computer2)| [computer1, computer2])
+            .collect::<HashSet<_>>();
+
+        let mut graph = Graph::new_undirected();
+
+        let index_mapping = computers
+            .iter()
+            .map(|&computer| (computer, graph.add_node(computer)))
+            .collect::<HashMap<_, _>>();
+
+        self.connections.iter().for_each(|(computer1, computer2)| {
+            graph.add_edge(index_mapping[*computer1], index_mapping[*computer2], ());
+        });
+
+        graph
+    }
+}
+
+pub fn parse(input: &str) -> Result<Network<'_>, AocError> {
+    let connections = input
+        .lines()
+        .map(|line| match line.split_once('-') {
+            Some((computer1, computer2))
+                if !computer1.is_empty() && !computer2.is_empty() && !computer2.contains('-') =>
+            {
+                Ok((computer1, computer2))
+            }
+            _ => Err(AocError::malformed(format!("Invalid connection: {line}"))),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Network { connections })
+}
+
+impl fmt::Display for Network<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.connections
+            .iter()
+            .try_for_each(|(computer1, computer2)| writeln!(f, "{computer1}-{computer2}"))
+    }
+}
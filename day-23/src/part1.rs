@@ -1,71 +1,49 @@
 use crate::custom_error::AocError;
-use itertools::Itertools;
-use petgraph::Graph;
-use std::collections::{HashMap, HashSet};
-use std::io::Error;
+use crate::model::{self, Network};
+use aoc_common::cancel::CancelToken;
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let mut computers = HashSet::new();
-
-    let connections = _input
-        .lines()
-        .map(|line| {
-            let mut connection = line.split("-");
-            let computer1 = connection.next().ok_or(Error::other("Invalid input"))?;
-            let computer2 = connection.next().ok_or(Error::other("Invalid input"))?;
-
-            computers.insert(computer1);
-            computers.insert(computer2);
-
-            Ok((computer1, computer2))
-        })
-        .collect::<Result<Vec<_>, AocError>>()?;
+/// The number of sets of three interconnected computers where at least one name starts with
+/// `t`. Gives up with [`AocError::Timeout`] once `cancel` expires.
+pub fn count_t_triangles(network: &Network, cancel: &CancelToken) -> Result<usize, AocError> {
+    let graph = network.to_graph();
 
-    let mut graph = Graph::new_undirected();
-
-    let index_mapping = computers
-        .iter()
-        .map(|&computer| (computer, graph.add_node(computer)))
-        .collect::<HashMap<_, _>>();
+    // The triples grow with the cube of the computers, so the budget is checked per first one
+    graph.node_indices().try_fold(0, |count, n1| {
+        cancel.check()?;
+        let triangles = graph
+            .node_indices()
+            .flat_map(|n2| graph.node_indices().map(move |n3| (n2, n3)))
+            .filter(|&(n2, n3)| {
+                n1.index() < n2.index()
+                    && n2.index() < n3.index()
+                    && graph.contains_edge(n1, n2)
+                    && graph.contains_edge(n2, n3)
+                    && graph.contains_edge(n3, n1)
+            })
+            .filter(|&(n2, n3)| {
+                [n1, n2, n3]
+                    .iter()
+                    .any(|node| graph[*node].starts_with('t'))
+            })
+            .count();
+        Ok(count + triangles)
+    })
+}
 
-    connections.iter().for_each(|(computer1, computer2)| {
-        graph.add_edge(index_mapping[*computer1], index_mapping[*computer2], ());
-    });
+/// Solves part 1 for the parsed network map.
+pub fn solve(network: &Network, cancel: &CancelToken) -> Result<String, AocError> {
+    Ok(count_t_triangles(network, cancel)?.to_string())
+}
 
-    let cliques = graph
-        .node_indices()
-        .into_iter()
-        .flat_map(|n1| {
-            graph.node_indices().into_iter().flat_map({
-                let graph = graph.clone();
-                move |n2| {
-                    graph.node_indices().into_iter().filter_map({
-                        let graph = graph.clone();
-                        move |n3| {
-                            if n1.index() < n2.index()
-                                && n2.index() < n3.index()
-                                && graph.contains_edge(n1, n2)
-                                && graph.contains_edge(n2, n3)
-                                && graph.contains_edge(n3, n1)
-                            {
-                                Some((n1, n2, n3))
-                            } else {
-                                None
-                            }
-                        }
-                    })
-                }
-            })
-        })
-        .filter(|(n1, n2, n3)| {
-            graph.node_weight(*n1).unwrap().starts_with("t")
-                || graph.node_weight(*n2).unwrap().starts_with("t")
-                || graph.node_weight(*n3).unwrap().starts_with("t")
-        })
-        .collect_vec();
+#[tracing::instrument]
+pub fn process(_input: &str) -> miette::Result<String, AocError> {
+    process_cancellable(_input, &CancelToken::new())
+}
 
-    Ok(cliques.len().to_string())
+/// Like [`process`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+#[tracing::instrument(skip(cancel))]
+pub fn process_cancellable(_input: &str, cancel: &CancelToken) -> miette::Result<String, AocError> {
+    solve(&model::parse(_input)?, cancel)
 }
 
 #[cfg(test)]
@@ -109,4 +87,14 @@ td-yn";
         assert_eq!("7", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(matches!(
+            process_cancellable("kh-tc\n", &cancel),
+            Err(AocError::Timeout(_))
+        ));
+    }
 }
@@ -1,18 +1,19 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, LanGraph, Network};
+use aoc_common::cancel::{CancelToken, Cancelled};
 use itertools::Itertools;
-use petgraph::data::DataMap;
 use petgraph::graph::NodeIndex;
-use petgraph::{Graph, Undirected};
 use std::collections::{HashMap, HashSet};
-use std::io::Error;
 
 fn augment_cliques(
-    cliques: &Vec<Vec<NodeIndex>>,
-    graph: &Graph<&str, (), Undirected>,
-) -> Vec<Vec<NodeIndex>> {
+    cliques: &[Vec<NodeIndex>],
+    graph: &LanGraph,
+    cancel: &CancelToken,
+) -> Result<Vec<Vec<NodeIndex>>, Cancelled> {
     let mut augmented_cliques = Vec::new();
 
     for clique in cliques {
+        cancel.check()?;
         for node in graph.node_indices() {
             if clique.iter().all(|n| graph.contains_edge(node, *n)) {
                 let mut augmented_clique = clique.clone();
@@ -23,43 +24,38 @@ fn augment_cliques(
         }
     }
 
-    augmented_cliques.into_iter().unique().collect_vec()
+    Ok(augmented_cliques.into_iter().unique().collect_vec())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let mut computers = HashSet::new();
-
-    let connections = _input
-        .lines()
-        .map(|line| {
-            let mut connection = line.split("-");
-            let computer1 = connection.next().ok_or(Error::other("Invalid input"))?;
-            let computer2 = connection.next().ok_or(Error::other("Invalid input"))?;
-
-            computers.insert(computer1);
-            computers.insert(computer2);
-
-            Ok((computer1, computer2))
-        })
-        .collect::<Result<Vec<_>, AocError>>()?;
+    process_cancellable(_input, &CancelToken::new())
+}
 
-    let mut graph = Graph::new_undirected();
+/// Solves part 2 for the parsed network map.
+pub fn solve(network: &Network, cancel: &CancelToken) -> Result<String, AocError> {
+    Ok(largest_clique(network, cancel)?.join(","))
+}
 
-    let index_mapping = computers
-        .iter()
-        .map(|&computer| (computer, graph.add_node(computer)))
-        .collect::<HashMap<_, _>>();
+/// Like [`process`], but gives up with [`AocError::Timeout`] once `cancel` expires.
+#[tracing::instrument(skip(cancel))]
+pub fn process_cancellable(_input: &str, cancel: &CancelToken) -> miette::Result<String, AocError> {
+    solve(&parse(_input)?, cancel)
+}
 
-    connections.iter().for_each(|(computer1, computer2)| {
-        graph.add_edge(index_mapping[*computer1], index_mapping[*computer2], ());
-    });
+/// The computers of the largest set of interconnected computers, sorted by name. Gives up with
+/// [`AocError::Timeout`] once `cancel` expires.
+pub fn largest_clique<'a>(
+    network: &Network<'a>,
+    cancel: &CancelToken,
+) -> Result<Vec<&'a str>, AocError> {
+    let graph = network.to_graph();
 
     let mut cliques: Vec<Vec<_>> = graph.node_indices().map(|node| vec![node]).collect();
     let mut maximum_clique_size = 1usize;
 
     loop {
-        let augmented_cliques = augment_cliques(&cliques, &graph);
+        let augmented_cliques = augment_cliques(&cliques, &graph, cancel)?;
 
         if let Some(new_maximum_clique_size) =
             augmented_cliques.iter().map(|clique| clique.len()).max()
@@ -78,15 +74,88 @@ pub fn process(_input: &str) -> miette::Result<String, AocError> {
         }
     }
 
-    let result = cliques
-        .get(0)
-        .ok_or(Error::other("No computers in the data"))?
+    Ok(cliques
+        .first()
+        .ok_or(AocError::no_solution("No computers in the data"))?
         .iter()
-        .map(|n| graph.node_weight(*n).unwrap().to_string())
+        .map(|n| graph[*n])
         .sorted()
-        .join(",");
+        .collect())
+}
 
-    Ok(result)
+/// Like [`largest_clique`], but enumerates the maximal cliques with the Bron–Kerbosch
+/// algorithm with pivoting instead of growing every clique one computer at a time.
+pub fn largest_clique_bron_kerbosch<'a>(
+    network: &Network<'a>,
+    cancel: &CancelToken,
+) -> Result<Vec<&'a str>, AocError> {
+    let graph = network.to_graph();
+    let neighbors = graph
+        .node_indices()
+        .map(|node| (node, graph.neighbors(node).collect()))
+        .collect::<HashMap<_, HashSet<_>>>();
+
+    let mut largest = Vec::new();
+    bron_kerbosch(
+        &neighbors,
+        Vec::new(),
+        graph.node_indices().collect(),
+        HashSet::new(),
+        &mut largest,
+        cancel,
+    )?;
+
+    if largest.is_empty() {
+        Err(AocError::no_solution("No computers in the data"))?;
+    }
+    Ok(largest.iter().map(|n| graph[*n]).sorted().collect())
+}
+
+fn bron_kerbosch(
+    neighbors: &HashMap<NodeIndex, HashSet<NodeIndex>>,
+    clique: Vec<NodeIndex>,
+    mut candidates: HashSet<NodeIndex>,
+    mut excluded: HashSet<NodeIndex>,
+    largest: &mut Vec<NodeIndex>,
+    cancel: &CancelToken,
+) -> Result<(), Cancelled> {
+    cancel.check()?;
+
+    let Some(pivot) = candidates
+        .union(&excluded)
+        .max_by_key(|node| neighbors[node].len())
+    else {
+        if clique.len() > largest.len() {
+            *largest = clique;
+        }
+        return Ok(());
+    };
+
+    // Every maximal clique contains the pivot or one of its non-neighbours
+    let branches = candidates
+        .difference(&neighbors[pivot])
+        .copied()
+        .collect_vec();
+    for node in branches {
+        let mut extended = clique.clone();
+        extended.push(node);
+        bron_kerbosch(
+            neighbors,
+            extended,
+            candidates
+                .intersection(&neighbors[&node])
+                .copied()
+                .collect(),
+            excluded.intersection(&neighbors[&node]).copied().collect(),
+            largest,
+            cancel,
+        )?;
+
+        candidates.remove(&node);
+        excluded.insert(node);
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -128,6 +197,20 @@ wh-qp
 tb-vc
 td-yn";
         assert_eq!("co,de,ka,ta", process(input)?);
+        assert_eq!(
+            vec!["co", "de", "ka", "ta"],
+            largest_clique_bron_kerbosch(&parse(input)?, &CancelToken::new())?
+        );
         Ok(())
     }
+
+    #[test]
+    fn test_process_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(matches!(
+            process_cancellable("kh-tc\nqp-kh", &cancel),
+            Err(AocError::Timeout(_))
+        ));
+    }
 }
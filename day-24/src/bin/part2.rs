@@ -1,3 +1,4 @@
+use aoc_common::input;
 use day_24::part2::process;
 use miette::Context;
 
@@ -13,7 +14,7 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let _result = process(file).context("process part 2")?;
+    let file = &input::load(env!("CARGO_MANIFEST_DIR"), 2).context("load the input")?;
+    process(file).context("process part 2")?;
     Ok(())
 }
@@ -0,0 +1,118 @@
+use crate::custom_error::AocError;
+use crate::model::{Device, GateType};
+use petgraph::dot::Dot;
+use petgraph::graphmap::GraphMap;
+use petgraph::visit::{Topo, Walker};
+use petgraph::Directed;
+use std::collections::HashMap;
+use std::ops::Shl;
+
+/// Initial wire values and the gates, keyed by the wire each gate drives.
+#[derive(Debug)]
+pub struct GateConfiguration<'a> {
+    pub input: HashMap<&'a str, bool>,
+    pub gates: HashMap<&'a str, ((&'a str, &'a str), GateType)>,
+}
+
+impl<'a> From<&Device<'a>> for GateConfiguration<'a> {
+    fn from(device: &Device<'a>) -> Self {
+        GateConfiguration {
+            input: device.initial_values.iter().copied().collect(),
+            gates: device
+                .gates
+                .iter()
+                .map(|gate| (gate.output, (gate.inputs, gate.gate_type)))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> GateConfiguration<'a> {
+    /// A graph of the wires with an edge from each gate input to the gate's output, labelled
+    /// with the gate type.
+    pub fn to_graph(&self) -> GraphMap<&'a str, GateType, Directed> {
+        let edges = self
+            .gates
+            .iter()
+            .flat_map(|(out, ((in1, in2), gate))| [(*in1, *out, *gate), (*in2, *out, *gate)])
+            .collect::<Vec<_>>();
+
+        GraphMap::from_edges(&edges)
+    }
+
+    /// The circuit in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        format!("{}", Dot::new(&self.to_graph()))
+    }
+
+    /// The value of every wire once the signals have propagated through all gates.
+    pub fn evaluate(&self) -> Result<HashMap<&'a str, bool>, AocError> {
+        let graph = self.to_graph();
+        let mut values = self.input.clone();
+
+        for node in Topo::new(&graph).iter(&graph) {
+            if let Some(((in1, in2), gate)) = self.gates.get(node) {
+                let value = |wire: &str| {
+                    values
+                        .get(wire)
+                        .copied()
+                        .ok_or_else(|| AocError::malformed(format!("Wire without a value: {wire}")))
+                };
+                let (value1, value2) = (value(in1)?, value(in2)?);
+                let result = match *gate {
+                    GateType::And => value1 && value2,
+                    GateType::Or => value1 || value2,
+                    GateType::Xor => value1 ^ value2,
+                };
+                values.insert(node, result);
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// The number formed by the wires starting with `prefix`, with the lowest-numbered wire as the
+/// least significant bit.
+pub fn number(values: &HashMap<&str, bool>, prefix: &str) -> u64 {
+    let mut wires = values
+        .iter()
+        .filter(|(node, _value)| node.starts_with(prefix))
+        .collect::<Vec<_>>();
+    wires.sort_by_key(|(node, _value)| **node);
+    wires.iter().rev().fold(0u64, |mut acc, (_, value)| {
+        acc = acc.shl(1);
+        if **value {
+            acc += 1;
+        }
+        acc
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse;
+
+    #[test]
+    fn test_evaluate() -> miette::Result<()> {
+        let device = parse(
+            "x00: 1
+x01: 1
+y00: 0
+y01: 1
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+z01 OR x00 -> z02
+",
+        )?;
+        let configuration = GateConfiguration::from(&device);
+
+        let values = configuration.evaluate()?;
+        assert_eq!(0b100, number(&values, "z"));
+        assert_eq!(0b11, number(&values, "x"));
+        assert!(configuration.to_dot().contains("label = \"XOR\""));
+        Ok(())
+    }
+}
@@ -1,4 +1,12 @@
+//! Day 24: Crossed Wires.
+//!
+//! [`model::parse`] reads the wires and gates, and [`circuit::GateConfiguration`] evaluates
+//! them. [`part1::z_output`] reads the number off the `z` wires, part 2 prints the circuit
+//! for checking it against a full adder by hand.
+
+pub mod circuit;
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
 pub mod part2;
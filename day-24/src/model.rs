@@ -0,0 +1,107 @@
+use crate::custom_error::AocError;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alphanumeric1, newline};
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{separated_pair, terminated, tuple};
+use nom::{Finish, IResult, Parser};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum GateType {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Gate<'a> {
+    pub inputs: (&'a str, &'a str),
+    pub gate_type: GateType,
+    pub output: &'a str,
+}
+
+/// The initial wire values and the gates of the device, in input order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Device<'a> {
+    pub initial_values: Vec<(&'a str, bool)>,
+    pub gates: Vec<Gate<'a>>,
+}
+
+fn gate(input: &str) -> IResult<&str, Gate<'_>> {
+    separated_pair(
+        tuple((
+            alphanumeric1,
+            alt((
+                tag(" AND ").map(|_| GateType::And),
+                tag(" OR ").map(|_| GateType::Or),
+                tag(" XOR ").map(|_| GateType::Xor),
+            )),
+            alphanumeric1,
+        )),
+        tag(" -> "),
+        alphanumeric1,
+    )
+    .map(|((input1, gate_type, input2), output)| Gate {
+        inputs: (input1, input2),
+        gate_type,
+        output,
+    })
+    .parse(input)
+}
+
+fn device(input: &str) -> IResult<&str, Device<'_>> {
+    terminated(
+        separated_pair(
+            separated_list1(
+                newline,
+                separated_pair(
+                    alphanumeric1,
+                    tag(": "),
+                    alt((tag("0").map(|_| false), tag("1").map(|_| true))),
+                ),
+            ),
+            tag("\n\n"),
+            separated_list1(newline, gate),
+        ),
+        opt(newline),
+    )
+    .map(|(initial_values, gates)| Device {
+        initial_values,
+        gates,
+    })
+    .parse(input)
+}
+
+pub fn parse(input: &str) -> Result<Device<'_>, AocError> {
+    all_consuming(device)(input)
+        .finish()
+        .map(|(_, device)| device)
+        .map_err(|err| AocError::malformed(format!("Invalid input at: {:.20}", err.input)))
+}
+
+impl fmt::Display for GateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateType::And => write!(f, "AND"),
+            GateType::Or => write!(f, "OR"),
+            GateType::Xor => write!(f, "XOR"),
+        }
+    }
+}
+
+impl fmt::Display for Device<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (wire, value) in &self.initial_values {
+            writeln!(f, "{wire}: {}", u8::from(*value))?;
+        }
+        writeln!(f)?;
+        for gate in &self.gates {
+            let (input1, input2) = gate.inputs;
+            writeln!(f, "{input1} {} {input2} -> {}", gate.gate_type, gate.output)?;
+        }
+        Ok(())
+    }
+}
@@ -1,124 +1,20 @@
+use crate::circuit::{number, GateConfiguration};
 use crate::custom_error::AocError;
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{alphanumeric1, newline};
-use nom::multi::separated_list0;
-use nom::sequence::{separated_pair, tuple};
-use nom::Parser;
-use petgraph::graphmap::GraphMap;
-use petgraph::visit::{Topo, Walker};
-use petgraph::Directed;
-use std::collections::HashMap;
-use std::io::Error;
-use std::ops::Shl;
+use crate::model::{self, Device};
 
-#[derive(Debug)]
-enum GateType {
-    And,
-    Or,
-    Xor,
+/// The number output on the wires starting with `z`.
+pub fn z_output(configuration: &GateConfiguration) -> Result<u64, AocError> {
+    Ok(number(&configuration.evaluate()?, "z"))
 }
 
-#[derive(Debug)]
-struct GateConfiguration<'a> {
-    input: HashMap<&'a str, bool>,
-    gates: HashMap<&'a str, ((&'a str, &'a str), GateType)>,
-}
-
-fn parse(input: &str) -> Result<GateConfiguration, AocError> {
-    let (_, parsed) = separated_pair(
-        separated_list0(
-            newline::<&str, ()>,
-            separated_pair(alphanumeric1, tag(": "), alt((tag("0"), tag("1")))).map(
-                |(name, value)| {
-                    (
-                        name,
-                        match value {
-                            "1" => true,
-                            _ => false,
-                        },
-                    )
-                },
-            ),
-        )
-        .map(|inputs| inputs.into_iter().collect::<HashMap<_, _>>()),
-        tag("\n\n"),
-        separated_list0(
-            newline,
-            separated_pair(
-                tuple((
-                    alphanumeric1,
-                    alt((tag(" AND "), tag(" OR "), tag(" XOR "))),
-                    alphanumeric1,
-                ))
-                .map(|(name1, gate, name2)| {
-                    (
-                        (name1, name2),
-                        match gate {
-                            " AND " => GateType::And,
-                            " OR " => GateType::Or,
-                            _ => GateType::Xor,
-                        },
-                    )
-                }),
-                tag(" -> "),
-                alphanumeric1,
-            ),
-        )
-        .map(|gates| {
-            gates
-                .into_iter()
-                .map(|(gate, output)| (output, gate))
-                .collect::<HashMap<_, _>>()
-        }),
-    )(input)
-    .map_err(|_| Error::other("Invalid input"))?;
-
-    Ok(GateConfiguration {
-        input: parsed.0,
-        gates: parsed.1,
-    })
+/// Solves part 1 for the parsed device.
+pub fn solve(device: &Device) -> Result<String, AocError> {
+    Ok(z_output(&GateConfiguration::from(device))?.to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let gate_configuration = parse(_input)?;
-
-    let edges = gate_configuration
-        .gates
-        .iter()
-        .flat_map(|(out, ((in1, in2), _))| [(*in1, *out), (*in2, *out)])
-        .collect::<Vec<_>>();
-
-    let graph: GraphMap<&str, (), Directed> = GraphMap::from_edges(&edges);
-
-    let mut values = gate_configuration.input.clone();
-
-    for node in Topo::new(&graph).iter(&graph) {
-        if let Some(((in1, in2), gate)) = gate_configuration.gates.get(node) {
-            let result = match *gate {
-                GateType::And => *values.get(*in1).unwrap() && *values.get(*in2).unwrap(),
-                GateType::Or => *values.get(*in1).unwrap() || *values.get(*in2).unwrap(),
-                GateType::Xor => *values.get(*in1).unwrap() ^ *values.get(*in2).unwrap(),
-            };
-            values.insert(node, result);
-        }
-    }
-
-    let mut z = values
-        .into_iter()
-        .filter(|(node, _value)| node.starts_with("z"))
-        .collect::<Vec<_>>();
-    z.sort_by_key(|(node, _value)| *node);
-    let result = z.iter().rev().fold(0u64, |mut acc, (_, value)| {
-        acc = acc.shl(1);
-        if *value {
-            acc += 1;
-        }
-        acc
-    });
-
-    Ok(result.to_string())
+    solve(&model::parse(_input)?)
 }
 
 #[cfg(test)]
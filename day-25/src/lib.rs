@@ -1,3 +1,9 @@
+//! Day 25: Code Chronicle.
+//!
+//! [`model::parse`] reads the lock and key schematics, which [`part1::fitting_pairs`] tries
+//! against each other.
+
 pub mod custom_error;
+pub mod model;
 
 pub mod part1;
@@ -0,0 +1,125 @@
+use crate::custom_error::AocError;
+use serde::Serialize;
+use std::fmt;
+
+const WIDTH: usize = 5;
+const HEIGHT: usize = 7;
+
+/// A schematic with the height of each of its pin columns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Schematic {
+    Lock(Vec<u8>),
+    Key(Vec<u8>),
+}
+
+/// The schematics of all locks and keys, in input order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Schematics(pub Vec<Schematic>);
+
+impl Schematic {
+    fn rows(&self) -> Vec<String> {
+        (0..HEIGHT)
+            .map(|row| {
+                (0..WIDTH)
+                    .map(|column| {
+                        let filled = match self {
+                            Schematic::Lock(heights) => row <= heights[column] as usize,
+                            Schematic::Key(heights) => HEIGHT - 1 - row <= heights[column] as usize,
+                        };
+                        if filled {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn parse_schematic(block: &str) -> Result<Schematic, AocError> {
+    let rows = block.lines().collect::<Vec<_>>();
+    let height_of = |rows: &mut dyn Iterator<Item = &&str>| {
+        rows.enumerate()
+            .fold(vec![0u8; WIDTH], |mut heights, (level, row)| {
+                row.chars().enumerate().for_each(|(column, c)| {
+                    if c == '#' && column < WIDTH {
+                        heights[column] = level as u8;
+                    }
+                });
+                heights
+            })
+    };
+
+    let schematic = match rows.first() {
+        Some(&"#####") => Schematic::Lock(height_of(&mut rows.iter())),
+        Some(&".....") => Schematic::Key(height_of(&mut rows.iter().rev())),
+        _ => return Err(AocError::malformed("Schematic is neither a lock nor a key")),
+    };
+
+    // Pins have to be solid columns, which printing them again checks
+    match schematic.rows() == rows {
+        true => Ok(schematic),
+        false => Err(AocError::malformed(format!("Invalid schematic:\n{block}"))),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Schematics, AocError> {
+    input
+        .split("\n\n")
+        .map(parse_schematic)
+        .collect::<Result<_, _>>()
+        .map(Schematics)
+}
+
+impl fmt::Display for Schematics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, schematic) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            schematic
+                .rows()
+                .iter()
+                .try_for_each(|row| writeln!(f, "{row}"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let input = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+";
+        let schematics = parse(input)?;
+        assert_eq!(
+            vec![
+                Schematic::Lock(vec![0, 5, 3, 4, 3]),
+                Schematic::Key(vec![5, 0, 2, 1, 3])
+            ],
+            schematics.0
+        );
+        assert_eq!(input, schematics.to_string());
+        assert_eq!(schematics, parse(&schematics.to_string())?);
+        Ok(())
+    }
+}
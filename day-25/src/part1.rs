@@ -1,55 +1,41 @@
 use crate::custom_error::AocError;
+use crate::model::{parse, Schematic, Schematics};
 use itertools::Itertools;
-use std::io::Error;
 
-fn parse(input: &str) -> Result<(Vec<Vec<u8>>, Vec<Vec<u8>>), AocError> {
-    let mut locks = Vec::new();
-    let mut keys = Vec::new();
-
-    for object_str in input.split("\n\n") {
-        let mut levels = object_str.lines().peekable();
-        let is_key = match levels.peek() {
-            Some(&".....") => Ok(true),
-            Some(&"#####") => Ok(false),
-            _ => Err(Error::other("Invalid input")),
-        }?;
+/// Whether the key fits the lock, that is no pin column overlaps.
+pub fn fits(lock: &[u8], key: &[u8]) -> bool {
+    lock.iter()
+        .zip(key.iter())
+        .all(|(&lock_level, &key_level)| lock_level + key_level <= 5)
+}
 
-        let level_folder = |mut acc, (level, line)| {
-            line.chars().enumerate().for_each(|(pos, c)| {
-                if c == '#' {
-                    acc[pos] = level as u8;
-                }
-            });
-            acc
-        };
+/// The number of unique lock and key pairs that fit together.
+pub fn fitting_pairs(schematics: &Schematics) -> usize {
+    let (locks, keys): (Vec<_>, Vec<_>) = schematics
+        .0
+        .iter()
+        .partition(|schematic| matches!(schematic, Schematic::Lock(_)));
+    let heights = |schematic: &Schematic| match schematic {
+        Schematic::Lock(heights) | Schematic::Key(heights) => heights.clone(),
+    };
+    let locks = locks.into_iter().map(heights).collect::<Vec<_>>();
+    let keys = keys.into_iter().map(heights).collect::<Vec<_>>();
 
-        if is_key {
-            let key = levels.rev().enumerate().fold(vec![0u8; 5], level_folder);
-            keys.push(key);
-        } else {
-            let lock = levels.enumerate().fold(vec![0u8; 5], level_folder);
-            locks.push(lock);
-        }
-    }
+    locks
+        .iter()
+        .cartesian_product(keys.iter())
+        .filter(|(lock, key)| fits(lock, key))
+        .count()
+}
 
-    Ok((locks, keys))
+/// Solves part 1 for the parsed schematics.
+pub fn solve(schematics: &Schematics) -> Result<String, AocError> {
+    Ok(fitting_pairs(schematics).to_string())
 }
 
 #[tracing::instrument]
 pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let (locks, keys) = parse(_input)?;
-
-    let result = locks
-        .into_iter()
-        .cartesian_product(keys.into_iter())
-        .filter(|(lock, key)| {
-            lock.iter()
-                .zip(key.iter())
-                .all(|(&lock_level, &key_level)| lock_level + key_level <= 5)
-        })
-        .count();
-
-    Ok(result.to_string())
+    solve(&parse(_input)?)
 }
 
 #[cfg(test)]
@@ -5,19 +5,23 @@
 edition = "2021"
 
 [dependencies]
+aoc-common = { path = "../aoc-common" }
 clap = { version = "4.2", features = ["derive"] }
+miette = { version = "5.10", features = ["fancy"] }
 nom = "7.1.3"
 reqwest = { version = "0.11.22", features=["blocking"] }
 ---
 
+use aoc_common::article;
+use aoc_common::input::SEALED_INPUT;
+use aoc_common::sealed::{self, InputKey};
 use clap::{error::ErrorKind, CommandFactory, Parser};
 use nom::{
     bytes::complete::tag, character::complete,
     sequence::preceded, IResult,
 };
 use reqwest::{blocking::Client, header::COOKIE};
-use std::fs::File;
-use std::io::Write;
+use std::fs;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -33,6 +37,10 @@ struct Args {
     /// doing any shenanigans
     #[clap(long)]
     current_working_directory: PathBuf,
+    /// only refresh `PUZZLE.md`, e.g. to add part
+    /// two once part one is solved
+    #[clap(long)]
+    puzzle_only: bool,
 }
 
 fn parse_day(input: &str) -> IResult<&str, u32> {
@@ -55,31 +63,53 @@ fn main() -> Result<(), reqwest::Error> {
         .exit()
     };
 
-    let url = format!(
-        "https://adventofcode.com/2024/day/{day}/input"
-    );
-    println!("sending to `{}`", url);
-
     let client = Client::new();
-    let input_data = client
-        .get(url)
-        .header(COOKIE, format!("session={session}"))
-        .send()?
-        .text()?;
+    let day_dir = args.current_working_directory.join(&args.day);
 
-    for filename in ["input1.txt", "input2.txt"] {
-        let file_path = args
-            .current_working_directory
-            .join(&args.day)
-            .join(filename);
-        let mut file = File::create(&file_path)
-            .expect("should be able to create a file");
+    if !args.puzzle_only {
+        // Fail before downloading when there is no key to store the input with
+        let key = InputKey::from_env().unwrap_or_else(|err| {
+            eprintln!("{:?}", miette::Report::new(err));
+            std::process::exit(1)
+        });
 
-        file.write_all(input_data.as_bytes()).expect(
-            "should be able to write to input file",
+        let url = format!(
+            "https://adventofcode.com/2024/day/{day}/input"
         );
+        println!("sending to `{}`", url);
+
+        let input_data = client
+            .get(url)
+            .header(COOKIE, format!("session={session}"))
+            .send()?
+            .text()?;
+
+        // Inputs are only committed encrypted, see aoc-common/src/sealed.rs
+        let file_path = day_dir.join(SEALED_INPUT);
+        fs::write(&file_path, sealed::seal(&key, &input_data))
+            .expect("should be able to write the sealed input");
         println!("wrote {}", file_path.display());
     }
 
+    // The page shows part two only once part one is solved, so
+    // fetch it again with `just get-puzzle` after that
+    let url = format!("https://adventofcode.com/2024/day/{day}");
+    println!("sending to `{}`", url);
+
+    let page = client
+        .get(&url)
+        .header(COOKIE, format!("session={session}"))
+        .send()?
+        .text()?;
+    let Some(markdown) = article::to_markdown(&page, &url) else {
+        eprintln!("no puzzle description found at `{url}`");
+        std::process::exit(1)
+    };
+
+    let file_path = day_dir.join(article::PUZZLE_FILE);
+    fs::write(&file_path, markdown)
+        .expect("should be able to write the puzzle description");
+    println!("wrote {}", file_path.display());
+
     Ok(())
 }
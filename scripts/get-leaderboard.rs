@@ -0,0 +1,83 @@
+#!/usr/bin/env -S cargo +nightly -Zscript
+
+---cargo
+[package]
+edition = "2021"
+
+[dependencies]
+clap = { version = "4.2", features = ["derive"] }
+reqwest = { version = "0.11.22", features=["blocking"] }
+---
+
+use clap::Parser;
+use reqwest::{blocking::Client, header::COOKIE};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Advent of Code asks not to fetch a
+/// leaderboard more than once every 15 minutes
+const REFRESH_INTERVAL: Duration =
+    Duration::from_secs(15 * 60);
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// the number at the end of the
+    /// leaderboard's URL
+    #[clap(long)]
+    id: u64,
+    #[clap(long, default_value_t = 2024)]
+    year: u32,
+    /// where to save the JSON, which is
+    /// reused while it is fresh
+    #[clap(long)]
+    output: PathBuf,
+}
+
+fn main() -> Result<(), reqwest::Error> {
+    let session = std::env::var("SESSION")
+        .expect("should have a session token set");
+    let args = Args::parse();
+
+    let age = fs::metadata(&args.output)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| {
+            SystemTime::now().duration_since(modified).ok()
+        });
+    if let Some(age) =
+        age.filter(|age| *age < REFRESH_INTERVAL)
+    {
+        eprintln!(
+            "reusing {}, fetched {} minutes ago",
+            args.output.display(),
+            age.as_secs() / 60
+        );
+        return Ok(());
+    }
+
+    let url = format!(
+        "https://adventofcode.com/{}/leaderboard/private/view/{}.json",
+        args.year, args.id
+    );
+    eprintln!("sending to `{}`", url);
+
+    let client = Client::new();
+    let json = client
+        .get(url)
+        .header(COOKIE, format!("session={session}"))
+        .send()?
+        .error_for_status()?
+        .text()?;
+
+    if let Some(dir) = args.output.parent() {
+        fs::create_dir_all(dir)
+            .expect("should be able to create the output directory");
+    }
+    fs::write(&args.output, json)
+        .expect("should be able to write the leaderboard");
+    eprintln!("wrote {}", args.output.display());
+
+    Ok(())
+}